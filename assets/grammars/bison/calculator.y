@@ -0,0 +1,46 @@
+/* Calculator with the usual precedence and associativity of arithmetic operators. */
+
+%{
+#include <stdio.h>
+
+int yylex(void);
+void yyerror(const char *message);
+%}
+
+%union {
+    double value;
+}
+
+%token <value> NUMBER
+%token LPAREN "(" RPAREN ")"
+%type <value> expression
+
+%left '+' '-'
+%left '*' '/'
+%right '^'
+%right UMINUS
+
+%start expression
+
+%%
+
+expression
+    : expression '+' expression { $$ = $1 + $3; }
+    | expression '-' expression { $$ = $1 - $3; }
+    | expression '*' expression { $$ = $1 * $3; }
+    | expression '/' expression {
+          /* Division by zero is reported instead of evaluated, e.g. { 1 / 0 }. */
+          if ($3 == 0) { yyerror("division by zero }"); YYERROR; }
+          $$ = $1 / $3;
+      }
+    | expression '^' expression { $$ = pow($1, $3); }
+    | '-' expression %prec UMINUS { $$ = -$2; }
+    | "(" expression ")" { $$ = $2; }
+    | NUMBER
+    ;
+
+%%
+
+int main(void) {
+    return yyparse();
+}
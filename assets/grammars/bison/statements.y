@@ -0,0 +1,24 @@
+// Statements with error recovery and constructs that can't be translated.
+
+%glr-parser
+%expect 1
+
+%token IDENTIFIER NUMBER
+%token IF "if" ELSE "else"
+
+%%
+
+program: %empty | program statement ;
+
+statement
+    : IDENTIFIER[name] '=' value[v] ';' { assign($name, $v); }
+    | "if" '(' value ')' { enter(); } statement %dprec 1
+    | "if" '(' value ')' statement ELSE statement %dprec 2
+    | error ';' { yyerrok; }
+    ;
+
+value: NUMBER | IDENTIFIER | STRING ;
+
+%%
+
+void assign(const char *name, int value) {}
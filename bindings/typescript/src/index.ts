@@ -10,8 +10,13 @@ import {
   FollowTable,
   GoToTable,
   GrammarError,
+  GrammarWarning,
   LALR1ParserOfGrammar,
   LR1ParserOfGrammar,
+  NullableTable,
+  EbnfConstruct,
+  ModeTransition,
+  Precedence,
   ParserError,
   ParsingError,
   ParsingTables,
@@ -33,8 +38,19 @@ export class Grammar<
     symbols: null as NT[] | null,
     constant_tokens: null as T[] | null,
     start_symbol: null as NT | null,
+    entry_symbols: null as NT[] | null,
     regex_tokens: null as Map<R, string> | null,
+    priorities: null as Map<R, number> | null,
+    keywords: null as T[] | null,
+    case_insensitive_tokens: null as T[] | null,
+    modes: null as Map<string, Token<T, R>[]> | null,
+    transitions: null as Map<string, Map<Token<T, R>, ModeTransition>> | null,
+    ignored_regexes: null as string[] | null,
     productions: null as Rule<Token<T, R>>[] | null,
+    auxiliary_symbols: null as Map<NT, EbnfConstruct> | null,
+    instantiations: null as Map<NT, NT> | null,
+    precedences: null as Map<Token<T, R>, Precedence> | null,
+    warnings: null as GrammarWarning<Token<T, R>>[] | null,
     stringify: null as string | null,
   };
 
@@ -55,6 +71,27 @@ export class Grammar<
     }
   }
 
+  static parseAllErrors<
+    T extends string = string,
+    NT extends string = string,
+    R extends string = string,
+  >(grammar: string) {
+    try {
+      const res = _Grammar.parse_all_errors_wasm(grammar);
+      return Ok(new Grammar<T, NT, R>(res));
+    } catch (e) {
+      return Err(e as GrammarError[]);
+    }
+  }
+
+  static format(grammar: string) {
+    try {
+      return Ok(_Grammar.format_wasm(grammar) as string);
+    } catch (e) {
+      return Err(e as GrammarError);
+    }
+  }
+
   getSymbols() {
     return (this.cache.symbols ??= this.grammar.symbols_wasm() as NT[]);
   }
@@ -68,6 +105,11 @@ export class Grammar<
     return (this.cache.start_symbol ??= this.grammar.start_symbol_wasm() as NT);
   }
 
+  getEntrySymbols() {
+    return (this.cache.entry_symbols ??=
+      this.grammar.entry_symbols_wasm() as NT[]);
+  }
+
   getProductions() {
     return (this.cache.productions ??= this.grammar.rules_wasm() as Rule<
       Token<T, R>
@@ -79,6 +121,59 @@ export class Grammar<
       this.grammar.regular_expressions_wasm() as Map<R, string>);
   }
 
+  getPriorities() {
+    return (this.cache.priorities ??=
+      this.grammar.priorities_wasm() as Map<R, number>);
+  }
+
+  getKeywords() {
+    return (this.cache.keywords ??= this.grammar.keywords_wasm() as T[]);
+  }
+
+  isCaseInsensitive() {
+    return this.grammar.case_insensitive_wasm();
+  }
+
+  getCaseInsensitiveTokens() {
+    return (this.cache.case_insensitive_tokens ??=
+      this.grammar.case_insensitive_tokens_wasm() as T[]);
+  }
+
+  getModes() {
+    return (this.cache.modes ??=
+      this.grammar.modes_wasm() as Map<string, Token<T, R>[]>);
+  }
+
+  getTransitions() {
+    return (this.cache.transitions ??=
+      this.grammar.transitions_wasm() as Map<string, Map<Token<T, R>, ModeTransition>>);
+  }
+
+  getIgnoredRegexes() {
+    return (this.cache.ignored_regexes ??=
+      this.grammar.ignored_regular_expressions_wasm() as string[]);
+  }
+
+  getAuxiliarySymbols() {
+    return (this.cache.auxiliary_symbols ??=
+      this.grammar.auxiliary_symbols_wasm() as Map<NT, EbnfConstruct>);
+  }
+
+  getInstantiations() {
+    return (this.cache.instantiations ??=
+      this.grammar.instantiations_wasm() as Map<NT, NT>);
+  }
+
+  getPrecedences() {
+    return (this.cache.precedences ??=
+      this.grammar.precedences_wasm() as Map<Token<T, R>, Precedence>);
+  }
+
+  getWarnings() {
+    return (this.cache.warnings ??=
+      this.grammar.lint_wasm() as GrammarWarning<Token<T, R>>[]);
+  }
+
   stringify() {
     return (this.cache.stringify ??= this.grammar.to_string_wasm() as string);
   }
@@ -99,6 +194,7 @@ export class Parser<
     goto_table: null as GoToTable<NT> | null,
     parsing_tables: null as ParsingTables<NT, Token<T, R>> | null,
     automaton: null as Automaton<Token<T, R>> | null,
+    nullable_table: null as NullableTable | null,
     first_table: null as FirstTable<Token<T, R>> | null,
     follow_table: null as FollowTable<Token<T, R>> | null,
   };
@@ -107,9 +203,11 @@ export class Parser<
     this.parser = parser;
   }
 
-  parse(input: string) {
+  parse(input: string, entrySymbol?: NT) {
     try {
-      return Ok(this.parser.parse_wasm(input) as Tree<NT, Token<T, R>>);
+      return Ok(
+        this.parser.parse_wasm(input, entrySymbol) as Tree<NT, Token<T, R>>,
+      );
     } catch (e) {
       return Err(e as ParsingError);
     }
@@ -136,6 +234,11 @@ export class Parser<
     >);
   }
 
+  getNullableTable() {
+    return (this.cache.nullable_table ??=
+      this.parser.nullable_table_wasm() as NullableTable);
+  }
+
   getFirstTable() {
     return (this.cache.first_table ??=
       this.parser.first_table_wasm() as FirstTable<Token<T, R>>);
@@ -163,9 +266,27 @@ export class Parser<
     }
   }
 
-  trace(input: string) {
+  tokenizeWithTrivia(input: string) {
+    try {
+      const [tokens, trivia] = this.parser.tokenize_with_trivia_wasm(input) as [
+        [Spanned<Token<T, R>>, string][],
+        Spanned<string>[],
+      ];
+      return Ok({
+        tokens: tokens.map(([token, slice]) => ({
+          token,
+          slice,
+        })),
+        trivia,
+      });
+    } catch (e) {
+      return Err(e as ParsingError);
+    }
+  }
+
+  trace(input: string, entrySymbol?: NT) {
     try {
-      const [trace, tree] = this.parser.trace_wasm(input) as [
+      const [trace, tree] = this.parser.trace_wasm(input, entrySymbol) as [
         Trace<Tree<NT, Token<T, R>>>,
         Tree<NT, Token<T, R>>,
       ];
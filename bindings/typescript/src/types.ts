@@ -3,6 +3,10 @@ import { Grammar, LALR1Parser, LR1Parser, Parser } from "./index";
 export type Rule<T extends Token = Token> = {
   symbol: string;
   pattern: AtomicPattern<T>[];
+  labels: (string | null)[];
+  precedence: T | null;
+  span: Span | null;
+  pattern_spans: (Span | null)[];
 };
 
 //TODO not sure how to type Symbol
@@ -28,6 +32,7 @@ export type Tree<NT extends string = string, T extends Token = Token> = {
     value: {
         symbol: NT,
         pattern: Tree<NT, T>[]
+        labels: (string | null)[]
     }
 }
 
@@ -48,12 +53,14 @@ export type GrammarError = {
     value: {
         line: number,
         column: number,
+        span: Span,
         token: string
         expected: string[]
     }
 } | {
     type: "UnexpectedEof",
     value: {
+        span: Span,
         expected: string[]
     }
 } | {
@@ -61,8 +68,99 @@ export type GrammarError = {
     value: {
         line: number,
         column: number,
+        span: Span,
         regex: string
     }
+} | {
+    type: "UndefinedParameterizedSymbol",
+    value: {
+        line: number,
+        column: number,
+        span: Span,
+        symbol: string
+    }
+} | {
+    type: "ArgumentCountMismatch",
+    value: {
+        line: number,
+        column: number,
+        span: Span,
+        symbol: string,
+        expected: number,
+        got: number
+    }
+} | {
+    type: "UnboundedInstantiation",
+    value: {
+        line: number,
+        column: number,
+        span: Span,
+        symbol: string
+    }
+} | {
+    type: "UndefinedMode",
+    value: {
+        line: number,
+        column: number,
+        span: Span,
+        mode: string
+    }
+} | {
+    type: "UnresolvedImport",
+    value: {
+        line: number,
+        column: number,
+        span: Span,
+        path: string,
+        reason: string
+    }
+} | {
+    type: "CyclicImport",
+    value: {
+        line: number,
+        column: number,
+        span: Span,
+        path: string
+    }
+} | {
+    type: "InImportedGrammar",
+    value: {
+        grammar: string,
+        error: GrammarError
+    }
+}
+
+//prettier-ignore
+export type GrammarWarning<T extends Token = Token> = {
+    type: "UnreachableSymbol",
+    value: {
+        symbol: string
+        span: Span | null
+    }
+} | {
+    type: "UnproductiveSymbol",
+    value: {
+        symbol: string
+        span: Span | null
+    }
+} | {
+    type: "UnusedRegexToken",
+    value: {
+        regex_token: string
+        span: Span | null
+    }
+} | {
+    type: "DuplicateRule",
+    value: {
+        rule: Rule<T>
+        span: Span | null
+    }
+} | {
+    type: "TrivialCycle",
+    value: {
+        rule: Rule<T>
+        span: Span | null
+    }
 }
 
 //prettier-ignore
@@ -73,12 +171,20 @@ export type ParserError<P extends Parser = Parser> = {
     value: {
         symbol: string
         rule: Rule<TokenOfParser<P>>
+        span: Span | null
     }
 } | {
     type: "UndefinedRegexToken",
     value: {
         regex_token: string
         rule: Rule<TokenOfParser<P>>
+        span: Span | null
+    }
+} | {
+    type: "UndefinedEntrySymbol",
+    value: {
+        symbol: string
+        span: Span | null
     }
 } | {
     type: "Conflict",
@@ -86,6 +192,7 @@ export type ParserError<P extends Parser = Parser> = {
         parser: P
         state: number,
         token: TokenOfParser<P>
+        spans: Span[]
     }
 }
 
@@ -109,6 +216,11 @@ export type ParsingError<T extends Token = Token> = {
         span: Span
         expected: T[]
     }
+} | {
+    type: "UnknownEntrySymbol"
+    value: {
+        symbol: string
+    }
 }
 
 export type Trace<Tr extends Tree = Tree> = {
@@ -132,6 +244,7 @@ export type State<T extends Token = Token> = {
 };
 export type Automaton<T extends Token = Token> = {
   states: State<T>[];
+  start_states: Map<string, number>;
 };
 
 //prettier-ignore
@@ -146,16 +259,14 @@ export type Action = {
         rule_index: number
     }
 } | {
-    type: 'Accept',
-    value: {
-        rule_index: number
-    }
+    type: 'Accept'
 }
 export type Span = {
   offset: number;
   len: number;
   column: number;
   line: number;
+  source: string | null;
 };
 
 export type Spanned<T> = {
@@ -163,6 +274,38 @@ export type Spanned<T> = {
   value: T;
 };
 
+export type NullableTable = string[];
+
+export type EbnfConstruct = "Optional" | "ZeroOrMore" | "OneOrMore" | "Group";
+
+export type Associativity = "Left" | "Right" | "NonAssoc";
+
+export type Precedence = {
+  level: number;
+  associativity: Associativity;
+};
+
+//prettier-ignore
+export type ModeTransition = {
+    type: "Push",
+    value: string
+} | {
+    type: "Pop"
+} | {
+    type: "Switch",
+    value: string
+}
+
+export type ResolvedConflict<T extends Token = Token> = {
+  state: number;
+  token: T;
+  shift: Action;
+  reduce: Action;
+  token_precedence: Precedence;
+  rule_precedence: Precedence;
+  resolution: Action | null;
+};
+
 export type FirstTable<T extends Token = Token> = Map<string, T[]>;
 
 export type FollowTable<T extends Token = Token> = Map<string, T[]>;
@@ -177,6 +320,7 @@ export type ParsingTables<
 > = {
   action_table: ActionTable<T>;
   goto_table: GoToTable<NT>;
+  resolved_conflicts: ResolvedConflict<T>[];
 };
 
 export type TokenOfParser<P extends Parser> =
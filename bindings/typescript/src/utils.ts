@@ -44,7 +44,7 @@ export function stringifyLookahead(item: Token[], noApostrophes = false) {
 }
 
 export function stringifyAction(action: Action) {
-  if (action.type === "Accept") return `a${action.value.rule_index + 1}`;
+  if (action.type === "Accept") return "acc";
   if (action.type === "Reduce") return `r${action.value.rule_index + 1}`;
   if (action.type === "Shift") return `s${action.value.next_state}`;
   return "";
@@ -58,7 +58,7 @@ export function stringifyActionVerbose(
   if (action.type === "Shift") {
     return `Shift ${action.value.next_state}`;
   } else if (action.type === "Accept") {
-    return `Accept ${action.value.rule_index + 1} (${stringifyRule(rules[action.value.rule_index], noApostrophes)})`;
+    return "Accept";
   } else if (action.type === "Reduce") {
     return `Reduce ${action.value.rule_index + 1} (${stringifyRule(rules[action.value.rule_index], noApostrophes)})`;
   }
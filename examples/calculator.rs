@@ -16,7 +16,7 @@ use {
 fn main() -> ExitCode {
     let mut args = std::env::args().skip(1);
 
-    let grammar_string = include_str!("../assets/grammars/correct/calculator.lr");
+    let grammar_string = include_str!("../assets/grammars/correct/calculator.labeled.lr");
     let grammar = Grammar::parse(grammar_string).expect("invalid grammar");
     let parser = Parser::lr(grammar).expect("unsupported grammar");
 
@@ -72,7 +72,7 @@ fn calculate(parser: &Parser, input: &str) -> ExitCode {
     };
     match parser.parse(tokens) {
         Ok(parse_tree) => {
-            println!("{}", evaluate(parse_tree));
+            println!("{}", evaluate(&parse_tree));
             ExitCode::SUCCESS
         },
         Err(error) => {
@@ -82,7 +82,7 @@ fn calculate(parser: &Parser, input: &str) -> ExitCode {
     }
 }
 
-fn evaluate(tree: Tree<'_>) -> f64 {
+fn evaluate(tree: &Tree<'_>) -> f64 {
     match tree {
         Tree::Terminal { token, slice, .. } => {
             match token {
@@ -97,80 +97,25 @@ fn evaluate(tree: Tree<'_>) -> f64 {
                 },
             }
         },
-        Tree::NonTerminal { symbol, pattern } => {
-            let mut pattern = pattern.into_iter();
-            match symbol.as_str() {
-                "Expr" => {
-                    if pattern.len() == 1 {
-                        evaluate(pattern.next().unwrap())
-                    } else {
-                        assert_eq!(pattern.len(), 3);
-                        let lhs = pattern.next().unwrap();
-                        let operation = pattern.next().unwrap();
-                        let rhs = pattern.next().unwrap();
-                        match operation {
-                            Tree::Terminal { slice, .. } => {
-                                match slice {
-                                    "+" => evaluate(lhs) + evaluate(rhs),
-                                    "-" => evaluate(lhs) - evaluate(rhs),
-                                    _ => unreachable!(),
-                                }
-                            },
-                            _ => unreachable!(),
-                        }
-                    }
-                },
-                "Factor" => {
-                    if pattern.len() == 1 {
-                        evaluate(pattern.next().unwrap())
-                    } else {
-                        assert_eq!(pattern.len(), 3);
-                        let lhs = pattern.next().unwrap();
-                        let operation = pattern.next().unwrap();
-                        let rhs = pattern.next().unwrap();
-                        match operation {
-                            Tree::Terminal { slice, .. } => {
-                                match slice {
-                                    "*" => evaluate(lhs) * evaluate(rhs),
-                                    "/" => evaluate(lhs) / evaluate(rhs),
-                                    _ => unreachable!(),
-                                }
-                            },
-                            _ => unreachable!(),
-                        }
-                    }
-                },
-                "Exponent" => {
-                    if pattern.len() == 1 {
-                        evaluate(pattern.next().unwrap())
-                    } else {
-                        assert_eq!(pattern.len(), 3);
-                        let lhs = pattern.next().unwrap();
-                        let operation = pattern.next().unwrap();
-                        let rhs = pattern.next().unwrap();
-                        match operation {
-                            Tree::Terminal { slice, .. } => {
-                                if slice == "^" {
-                                    evaluate(lhs).powf(evaluate(rhs))
-                                } else {
-                                    unreachable!()
-                                }
-                            },
-                            _ => unreachable!(),
-                        }
-                    }
-                },
-                "Term" => {
-                    if pattern.len() == 1 {
-                        evaluate(pattern.next().unwrap())
-                    } else {
-                        assert_eq!(pattern.len(), 3);
-                        evaluate(pattern.nth(1).unwrap())
+        Tree::NonTerminal { .. } => {
+            if let Some(value) = tree.child("value") {
+                return evaluate(value);
+            }
+
+            let lhs = evaluate(tree.child("lhs").unwrap());
+            let rhs = evaluate(tree.child("rhs").unwrap());
+            match tree.child("op") {
+                Some(Tree::Terminal { slice, .. }) => {
+                    match *slice {
+                        "+" => lhs + rhs,
+                        "-" => lhs - rhs,
+                        "*" => lhs * rhs,
+                        "/" => lhs / rhs,
+                        "^" => lhs.powf(rhs),
+                        _ => unreachable!(),
                     }
                 },
-                _ => {
-                    unreachable!();
-                },
+                _ => unreachable!(),
             }
         },
     }
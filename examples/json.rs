@@ -43,7 +43,7 @@ impl From<Tree<'_>> for Value {
                     },
                 }
             },
-            Tree::NonTerminal { symbol, pattern } => {
+            Tree::NonTerminal { symbol, pattern, .. } => {
                 let mut pattern = pattern.into_iter();
                 match symbol.as_str() {
                     "Json" | "Value" | "Null" | "Boolean" | "Number" | "String" => {
@@ -59,7 +59,7 @@ impl From<Tree<'_>> for Value {
                             // Array -> '[' ArrayElements ']'
                             fn collect(elements: Tree, values: &mut Vec<Value>) {
                                 match elements {
-                                    Tree::NonTerminal { symbol, pattern }
+                                    Tree::NonTerminal { symbol, pattern, .. }
                                         if symbol.as_str() == "ArrayElements" =>
                                     {
                                         let mut pattern = pattern.into_iter();
@@ -94,7 +94,7 @@ impl From<Tree<'_>> for Value {
                             // Object -> '{' ObjectElements '}'
                             fn collect(elements: Tree, entries: &mut IndexMap<String, Value>) {
                                 match elements {
-                                    Tree::NonTerminal { symbol, pattern }
+                                    Tree::NonTerminal { symbol, pattern, .. }
                                         if symbol.as_str() == "ObjectElements" =>
                                     {
                                         let mut pattern = pattern.into_iter();
@@ -2,7 +2,7 @@ use crate::prelude::*;
 
 
 /// Item of a state of an LR(1) automaton.
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Item {
@@ -16,6 +16,13 @@ impl Item {
     pub fn new(rule: Rule, dot: usize, lookahead: impl Into<IndexSet<Token>>) -> Item {
         Item { rule, dot, lookahead: lookahead.into() }
     }
+
+    /// Creates the augmented item (e.g., `S' -> . S`) of an entry symbol, which accepts the input.
+    pub fn augmented(entry_symbol: &Symbol) -> Item {
+        let symbol = format_smolstr!("{}'", entry_symbol);
+        let rule = Rule::new(symbol, [AtomicPattern::Symbol(entry_symbol.clone())]);
+        Item { rule, dot: 0, lookahead: IndexSet::from([Token::Eof]) }
+    }
 }
 
 impl Item {
@@ -62,7 +69,7 @@ impl Display for Item {
 
 /// State of an LR(1) automaton.
 #[derive(Clone, Debug, Default, Eq)]
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
 pub struct State {
     id: usize,
@@ -100,7 +107,12 @@ impl State {
 
 impl State {
     /// Computes the closure of the state.
-    fn compute_closure(&mut self, grammar: &Grammar, first_table: &FirstTable) {
+    fn compute_closure(
+        &mut self,
+        grammar: &Grammar,
+        nullable_table: &NullableTable,
+        first_table: &FirstTable,
+    ) {
         loop {
             let mut new_items = vec![];
             for item in self.items.iter() {
@@ -108,17 +120,13 @@ impl State {
                     continue;
                 }
                 if let AtomicPattern::Symbol(symbol) = &item.rule.pattern()[item.dot] {
-                    let lookahead = if item.dot == item.rule.pattern().len() - 1 {
-                        item.lookahead.clone()
-                    } else {
-                        let next_atomic_pattern = &item.rule.pattern()[item.dot + 1];
-                        match next_atomic_pattern {
-                            AtomicPattern::Symbol(symbol) => {
-                                first_table.get(symbol).cloned().unwrap_or_default()
-                            },
-                            AtomicPattern::Token(token) => IndexSet::from([token.clone()]),
-                        }
-                    };
+                    let remaining_atomic_patterns = &item.rule.pattern()[item.dot + 1..];
+
+                    let mut lookahead =
+                        first_table.first_of(nullable_table, remaining_atomic_patterns);
+                    if nullable_table.is_nullable(remaining_atomic_patterns) {
+                        lookahead.extend(item.lookahead.iter().cloned());
+                    }
                     for rule in grammar.rules().iter().filter(|rule| rule.symbol() == symbol) {
                         let new_item =
                             Item { rule: rule.clone(), dot: 0, lookahead: lookahead.clone() };
@@ -186,35 +194,40 @@ impl PartialEq for State {
 
 /// LR(1) automaton of a grammar.
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
 #[derive(Debug)]
 pub struct Automaton {
     states: Vec<State>,
+    start_states: IndexMap<Symbol, usize>,
 }
 
 impl Automaton {
     /// Constructs the LR(1) automaton of a grammar.
-    pub fn construct(grammar: &Grammar, first_table: &FirstTable) -> Automaton {
-        let first_state = State {
-            id: 0,
-            items: grammar
-                .rules()
-                .iter()
-                .filter(|rule| rule.symbol() == grammar.start_symbol())
-                .map(|rule| {
-                    Item { rule: rule.clone(), dot: 0, lookahead: IndexSet::from([Token::Eof]) }
-                })
-                .collect(),
-            transitions: IndexMap::new(),
-        };
-
-        let mut states_to_process = vec![first_state];
+    pub fn construct(
+        grammar: &Grammar,
+        nullable_table: &NullableTable,
+        first_table: &FirstTable,
+    ) -> Automaton {
+        // Each entry symbol has its own augmented start state (e.g., `S' -> . S`),
+        // which are the first states of the automaton.
+        let mut start_states = IndexMap::new();
+        let mut states_to_process = Vec::with_capacity(grammar.entry_symbols().len());
+        for (id, entry_symbol) in grammar.entry_symbols().iter().enumerate() {
+            start_states.insert(entry_symbol.clone(), id);
+            states_to_process.push(State {
+                id,
+                items: smallvec![Item::augmented(entry_symbol)],
+                transitions: IndexMap::new(),
+            });
+        }
+        states_to_process.reverse();
+
         let mut processed_states = BTreeMap::<usize, State>::new();
 
-        let mut state_counter = 1;
+        let mut state_counter = states_to_process.len();
         while let Some(mut state_to_process) = states_to_process.pop() {
-            state_to_process.compute_closure(grammar, first_table);
+            state_to_process.compute_closure(grammar, nullable_table, first_table);
 
             if let Some(existing_state_with_same_items) = processed_states
                 .values()
@@ -256,8 +269,11 @@ impl Automaton {
                 *transition_target = *transition_map.get(transition_target).unwrap();
             }
         }
+        for start_state in start_states.values_mut() {
+            *start_state = *transition_map.get(start_state).unwrap();
+        }
 
-        Automaton { states: final_states }
+        Automaton { states: final_states, start_states }
     }
 }
 
@@ -399,8 +415,15 @@ impl Automaton {
             new_states.push(new_state);
         }
 
+        // Update the start states according to `state_map`.
+        let start_states = self
+            .start_states
+            .into_iter()
+            .map(|(entry_symbol, start_state)| (entry_symbol, state_map[&start_state]))
+            .collect();
+
         // Crate the LALR(1) automaton using the new states.
-        Automaton { states: new_states }
+        Automaton { states: new_states, start_states }
     }
 }
 
@@ -409,4 +432,9 @@ impl Automaton {
     pub fn states(&self) -> &[State] {
         &self.states
     }
+
+    /// Gets the start states of the entry symbols of the automaton.
+    pub fn start_states(&self) -> &IndexMap<Symbol, usize> {
+        &self.start_states
+    }
 }
@@ -0,0 +1,831 @@
+use crate::prelude::*;
+
+
+/// Bison warning of a construct of a yacc/bison grammar that could not be translated.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum BisonWarning {
+    /// A directive (e.g., `%expect`, `%glr-parser`, `%dprec`) has no equivalent in grammars.
+    #[error(
+        "directive {} at line {} column {} is not translated",
+        directive.green(),
+        format_smolstr!("{}", span.line).cyan(),
+        format_smolstr!("{}", span.column).cyan(),
+    )]
+    UntranslatedDirective { directive: SmolStr, span: Span },
+
+    /// A rule uses the `error` token for error recovery, which grammars don't have.
+    #[error(
+        "rule of {} at line {} column {} uses the error token so it is removed",
+        symbol.green(),
+        format_smolstr!("{}", span.line).cyan(),
+        format_smolstr!("{}", span.column).cyan(),
+    )]
+    ErrorRuleRemoved { symbol: SmolStr, span: Span },
+
+    /// A symbol without rules is used without being declared as a token.
+    #[error(
+        "symbol {} at line {} column {} is neither declared as a token nor defined by rules \
+         so it is translated as a token",
+        token.green(),
+        format_smolstr!("{}", span.line).cyan(),
+        format_smolstr!("{}", span.column).cyan(),
+    )]
+    UndeclaredToken { token: SmolStr, span: Span },
+
+    /// A named token has no lexical definition, so it's translated as a regex token matching its name.
+    #[error(
+        "token {} at line {} column {} has no lexical definition so it is translated as {} matching its name",
+        token.green(),
+        format_smolstr!("{}", span.line).cyan(),
+        format_smolstr!("{}", span.column).cyan(),
+        format_smolstr!("{}", regex_token).green(),
+    )]
+    PlaceholderRegex { token: SmolStr, regex_token: RegexToken, span: Span },
+}
+
+impl BisonWarning {
+    /// Gets the span of the construct the warning is about.
+    pub fn span(&self) -> &Span {
+        match self {
+            BisonWarning::UntranslatedDirective { span, .. }
+            | BisonWarning::ErrorRuleRemoved { span, .. }
+            | BisonWarning::UndeclaredToken { span, .. }
+            | BisonWarning::PlaceholderRegex { span, .. } => span,
+        }
+    }
+}
+
+
+impl Grammar {
+    /// Creates a grammar from a yacc/bison grammar string, stripping its semantic actions.
+    ///
+    /// Spans of the rules of the grammar refer to the translated grammar string
+    /// (see `Grammar::translate_bison`), and the constructs that could not be translated
+    /// are reported as warnings.
+    pub fn parse_bison(bison_string: &str) -> Result<(Grammar, Vec<BisonWarning>), GrammarError> {
+        let (grammar_string, warnings) = Grammar::translate_bison(bison_string)?;
+        Ok((Grammar::parse(&grammar_string)?, warnings))
+    }
+
+    /// Translates a yacc/bison grammar string into a grammar string, stripping its semantic actions.
+    ///
+    /// Declarations section (`%token`, `%left`, `%right`, `%nonassoc` and `%start`) and rules section
+    /// are translated, while the prologue, the epilogue and the semantic value declarations
+    /// (e.g., `%union`, `%type`) are skipped. Named tokens with string aliases
+    /// (e.g., `%token ARROW "->"`) are translated as constant tokens of their aliases,
+    /// and the other named tokens are translated as regex tokens matching their names,
+    /// since their lexical definitions are not part of yacc/bison grammars.
+    pub fn translate_bison(
+        bison_string: &str,
+    ) -> Result<(String, Vec<BisonWarning>), GrammarError> {
+        bison_translator::translate(bison_string)
+    }
+}
+
+
+/// Internal module to translate yacc/bison grammar strings.
+mod bison_translator {
+    use crate::prelude::*;
+
+    #[derive(Clone, Debug, Logos, PartialEq)]
+    #[logos(skip r"[ \t\r\n\f]+")]
+    #[logos(skip r"//[^\n]*")]
+    #[logos(skip r"/\*[^*]*\*+([^*/][^*]*\*+)*/")]
+    enum BisonToken {
+        /// %%
+        #[token("%%")]
+        Separator,
+
+        /// %{ ... %}
+        #[token("%{", skip_prologue)]
+        Prologue,
+
+        /// %token, %left, %prec, %empty, ...
+        #[regex(r"%[a-zA-Z_][a-zA-Z0-9_-]*", |lexer| SmolStr::from(lexer.slice()))]
+        Directive(SmolStr),
+
+        /// exp, NUM, expr_list, ...
+        #[regex(r"[a-zA-Z_.][a-zA-Z0-9_.]*", |lexer| SmolStr::from(lexer.slice()))]
+        Identifier(SmolStr),
+
+        /// [left], [value], ...
+        #[regex(r"\[[a-zA-Z_.][a-zA-Z0-9_.]*\]", |lexer| {
+            let slice = lexer.slice();
+            SmolStr::from(&slice[1..slice.len() - 1])
+        })]
+        NamedReference(SmolStr),
+
+        /// '+', '\n', ...
+        #[regex(r"'([^'\\\n]|\\[^\n])+'", |lexer| {
+            let slice = lexer.slice();
+            unescape(&slice[1..slice.len() - 1])
+        })]
+        Character(SmolStr),
+
+        /// "if", "<=", ...
+        #[regex(r#""([^"\\\n]|\\[^\n])*""#, |lexer| {
+            let slice = lexer.slice();
+            unescape(&slice[1..slice.len() - 1])
+        })]
+        String(SmolStr),
+
+        /// <value>, <*>, ...
+        #[regex(r"<[^<>\n]*>")]
+        TypeTag,
+
+        /// 1, 300, 0x2A, ...
+        #[regex(r"[0-9]+|0[xX][0-9a-fA-F]+")]
+        Number,
+
+        /// { ... }
+        #[token("{", skip_action)]
+        Action,
+
+        /// :
+        #[token(":")]
+        Colon,
+
+        /// |
+        #[token("|")]
+        Pipe,
+
+        /// ;
+        #[token(";")]
+        Semicolon,
+
+        /// =
+        #[token("=")]
+        Equals,
+    }
+
+    /// Skips the C code of a prologue until its closing `%}`.
+    fn skip_prologue(lexer: &mut Lexer<BisonToken>) -> bool {
+        match lexer.remainder().find("%}") {
+            Some(index) => {
+                lexer.bump(index + 2);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Skips the C code of an action until its closing brace, including nested braces.
+    fn skip_action(lexer: &mut Lexer<BisonToken>) -> bool {
+        let remainder = lexer.remainder();
+        let bytes = remainder.as_bytes();
+
+        let mut depth = 1;
+        let mut index = 0;
+        while index < bytes.len() {
+            match bytes[index] {
+                b'{' => depth += 1,
+                b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        lexer.bump(index + 1);
+                        return true;
+                    }
+                },
+                quote @ (b'"' | b'\'') => {
+                    index += 1;
+                    while index < bytes.len() && bytes[index] != quote {
+                        if bytes[index] == b'\\' {
+                            index += 1;
+                        }
+                        index += 1;
+                    }
+                },
+                b'/' if remainder[index..].starts_with("//") => {
+                    index += remainder[index..].find('\n').unwrap_or(remainder.len() - index);
+                },
+                b'/' if remainder[index..].starts_with("/*") => {
+                    match remainder[index + 2..].find("*/") {
+                        Some(end) => index += end + 3,
+                        None => return false,
+                    }
+                },
+                _ => {},
+            }
+            index += 1;
+        }
+        false
+    }
+
+    /// Unescapes the C escape sequences (e.g., `\n`, `\'`, `\x41`) of a character or a string literal.
+    fn unescape(literal: &str) -> SmolStr {
+        let mut unescaped = String::with_capacity(literal.len());
+        let mut chars = literal.chars().peekable();
+        while let Some(char) = chars.next() {
+            if char != '\\' {
+                unescaped.push(char);
+                continue;
+            }
+            let escaped = match chars.next() {
+                Some('n') => '\n',
+                Some('t') => '\t',
+                Some('r') => '\r',
+                Some('a') => '\u{7}',
+                Some('b') => '\u{8}',
+                Some('f') => '\u{c}',
+                Some('v') => '\u{b}',
+                Some('x') => {
+                    let mut code = String::new();
+                    while let Some(digit) = chars.next_if(|char| char.is_ascii_hexdigit()) {
+                        code.push(digit);
+                    }
+                    u32::from_str_radix(&code, 16).ok().and_then(char::from_u32).unwrap_or('x')
+                },
+                Some(digit @ '0'..='7') => {
+                    let mut code = String::from(digit);
+                    while code.len() < 3 {
+                        match chars.next_if(|char| ('0'..='7').contains(char)) {
+                            Some(digit) => code.push(digit),
+                            None => break,
+                        }
+                    }
+                    u32::from_str_radix(&code, 8).ok().and_then(char::from_u32).unwrap_or('0')
+                },
+                Some(escaped) => escaped,
+                None => '\\',
+            };
+            unescaped.push(escaped);
+        }
+        unescaped.into()
+    }
+
+
+    /// Element (e.g., `exp`, `'+'`, `"if"`, `exp[left]`) of a rule of a bison grammar.
+    #[derive(Clone, Debug)]
+    enum Element {
+        Name { name: SmolStr, label: Option<SmolStr>, span: Span },
+        Character(SmolStr),
+        String(SmolStr),
+    }
+
+    /// Alternative of a rule of a bison grammar, with its actions stripped.
+    #[derive(Clone, Debug)]
+    struct Alternative {
+        elements: Vec<Element>,
+        precedence: Option<Element>,
+        span: Span,
+    }
+
+    /// Rule (e.g., `exp: exp '+' exp | NUM ;`) of a bison grammar.
+    #[derive(Clone, Debug)]
+    struct BisonRule {
+        symbol: SmolStr,
+        alternatives: Vec<Alternative>,
+    }
+
+    /// Declarations and rules of a bison grammar.
+    #[derive(Debug, Default)]
+    struct BisonGrammar {
+        tokens: IndexMap<SmolStr, Span>,
+        aliases: IndexMap<SmolStr, SmolStr>,
+        precedences: Vec<(Associativity, Vec<Element>)>,
+        start_symbol: Option<(SmolStr, Span)>,
+        rules: Vec<BisonRule>,
+    }
+
+
+    /// Translates a bison grammar string into a grammar string.
+    pub fn translate(bison_string: &str) -> Result<(String, Vec<BisonWarning>), GrammarError> {
+        let tokens = tokenize(bison_string)?;
+
+        let mut warnings = Vec::new();
+        let mut grammar = BisonGrammar::default();
+
+        let rules_start = parse_declarations(&tokens, &mut grammar, &mut warnings, bison_string)?;
+        parse_rules(&tokens[rules_start..], &mut grammar, &mut warnings, bison_string)?;
+
+        Ok((write(&grammar, &mut warnings), warnings))
+    }
+
+    /// Tokenizes the declarations and the rules sections of a bison grammar string.
+    fn tokenize(bison_string: &str) -> Result<Vec<(BisonToken, Span)>, GrammarError> {
+        let mut lexer = BisonToken::lexer(bison_string);
+        let mut tokens = Vec::new();
+
+        let mut separators = 0;
+        while let Some(token) = lexer.next() {
+            let span = span_of(bison_string, lexer.span());
+            match token {
+                Ok(token) => {
+                    // Epilogue after the rules section is C code, so it's not tokenized.
+                    if token == BisonToken::Separator {
+                        separators += 1;
+                        if separators == 2 {
+                            break;
+                        }
+                    }
+                    tokens.push((token, span));
+                },
+                Err(_) => {
+                    let expected = if separators == 0 { "declaration" } else { "rule" };
+                    return Err(unexpected_token(lexer.slice(), span, &[expected]));
+                },
+            }
+        }
+
+        Ok(tokens)
+    }
+
+    /// Parses the declarations section, returning the index of the first token of the rules section.
+    fn parse_declarations(
+        tokens: &[(BisonToken, Span)],
+        grammar: &mut BisonGrammar,
+        warnings: &mut Vec<BisonWarning>,
+        bison_string: &str,
+    ) -> Result<usize, GrammarError> {
+        let mut index = 0;
+        loop {
+            let (token, span) = match tokens.get(index) {
+                Some(token_and_span) => token_and_span,
+                None => return Err(unexpected_eof(bison_string, &["%%"])),
+            };
+            let directive = match token {
+                BisonToken::Separator => return Ok(index + 1),
+                BisonToken::Prologue => {
+                    index += 1;
+                    continue;
+                },
+                BisonToken::Directive(directive) => directive,
+                _ => {
+                    let slice = &bison_string[span.offset..span.offset + span.len];
+                    return Err(unexpected_token(slice, span.clone(), &["declaration"]));
+                },
+            };
+
+            // Arguments of a declaration continue until the next declaration.
+            let arguments_end = tokens[index + 1..]
+                .iter()
+                .position(|(token, _)| {
+                    matches!(
+                        token,
+                        BisonToken::Directive(_) | BisonToken::Separator | BisonToken::Prologue,
+                    )
+                })
+                .map(|position| index + 1 + position)
+                .unwrap_or(tokens.len());
+            let arguments = &tokens[index + 1..arguments_end];
+
+            match directive.as_str() {
+                "%token" => {
+                    let mut last_token = None;
+                    for (argument, argument_span) in arguments {
+                        match argument {
+                            BisonToken::Identifier(name) => {
+                                grammar
+                                    .tokens
+                                    .entry(name.clone())
+                                    .or_insert_with(|| argument_span.clone());
+                                last_token = Some(name.clone());
+                            },
+                            BisonToken::String(alias) => {
+                                if let Some(name) = &last_token {
+                                    grammar.aliases.entry(name.clone()).or_insert(alias.clone());
+                                }
+                            },
+                            _ => {},
+                        }
+                    }
+                },
+                "%left" | "%right" | "%nonassoc" => {
+                    let associativity = match directive.as_str() {
+                        "%left" => Associativity::Left,
+                        "%right" => Associativity::Right,
+                        _ => Associativity::NonAssoc,
+                    };
+                    let elements = arguments
+                        .iter()
+                        .filter_map(|(argument, argument_span)| element(argument, argument_span))
+                        .collect();
+                    grammar.precedences.push((associativity, elements));
+                },
+                "%start" => {
+                    if let Some((BisonToken::Identifier(symbol), symbol_span)) = arguments.first() {
+                        grammar.start_symbol = Some((symbol.clone(), symbol_span.clone()));
+                    }
+                },
+                // Semantic values and the code to handle them have no effect on the language.
+                "%union" | "%type" | "%nterm" | "%code" | "%destructor" | "%printer" => {},
+                _ => {
+                    warnings.push(BisonWarning::UntranslatedDirective {
+                        directive: directive.clone(),
+                        span: span.clone(),
+                    });
+                },
+            }
+
+            index = arguments_end;
+        }
+    }
+
+    /// Parses the rules section.
+    fn parse_rules(
+        tokens: &[(BisonToken, Span)],
+        grammar: &mut BisonGrammar,
+        warnings: &mut Vec<BisonWarning>,
+        bison_string: &str,
+    ) -> Result<(), GrammarError> {
+        // Rules are separated by `;`, or by the `symbol:` of the next rule.
+        let starts_rule = |index: usize| {
+            let mut index = index + 1;
+            if let Some((BisonToken::NamedReference(_), _)) = tokens.get(index) {
+                index += 1;
+            }
+            matches!(tokens.get(index), Some((BisonToken::Colon, _)))
+        };
+
+        let mut index = 0;
+        while let Some((token, span)) = tokens.get(index) {
+            let symbol = match token {
+                BisonToken::Separator => break,
+                BisonToken::Semicolon => {
+                    index += 1;
+                    continue;
+                },
+                BisonToken::Identifier(symbol) if starts_rule(index) => symbol.clone(),
+                BisonToken::Identifier(_) => {
+                    // Symbol of a rule needs to be followed by a colon.
+                    return match tokens.get(index + 1) {
+                        Some((_, span)) => {
+                            let slice = &bison_string[span.offset..span.offset + span.len];
+                            Err(unexpected_token(slice, span.clone(), &[":"]))
+                        },
+                        None => Err(unexpected_eof(bison_string, &[":"])),
+                    };
+                },
+                _ => {
+                    let slice = &bison_string[span.offset..span.offset + span.len];
+                    return Err(unexpected_token(slice, span.clone(), &["rule"]));
+                },
+            };
+
+            index += 1;
+            while !matches!(tokens.get(index), Some((BisonToken::Colon, _))) {
+                index += 1;
+            }
+            index += 1;
+
+            let new_alternative = |index: usize| {
+                Alternative {
+                    elements: vec![],
+                    precedence: None,
+                    span: tokens.get(index).map(|(_, span)| span.clone()).unwrap_or(span.clone()),
+                }
+            };
+
+            let mut rule = BisonRule { symbol, alternatives: vec![] };
+            let mut alternative = new_alternative(index);
+            while let Some((token, token_span)) = tokens.get(index) {
+                match token {
+                    BisonToken::Identifier(_) if starts_rule(index) => break,
+                    BisonToken::Separator => break,
+                    BisonToken::Semicolon => {
+                        index += 1;
+                        break;
+                    },
+                    BisonToken::Pipe => {
+                        rule.alternatives.push(alternative);
+                        alternative = new_alternative(index + 1);
+                    },
+                    BisonToken::Identifier(name) => {
+                        let label = match tokens.get(index + 1) {
+                            Some((BisonToken::NamedReference(label), _)) => {
+                                index += 1;
+                                Some(label.clone())
+                            },
+                            _ => None,
+                        };
+                        alternative.elements.push(Element::Name {
+                            name: name.clone(),
+                            label,
+                            span: token_span.clone(),
+                        });
+                    },
+                    BisonToken::Character(_) | BisonToken::String(_) => {
+                        alternative.elements.extend(element(token, token_span));
+                        // Named references of literals are not kept, as labels are for symbols.
+                        if let Some((BisonToken::NamedReference(_), _)) = tokens.get(index + 1) {
+                            index += 1;
+                        }
+                    },
+                    BisonToken::Action => {},
+                    BisonToken::Directive(directive) if directive == "%empty" => {},
+                    BisonToken::Directive(directive) if directive == "%prec" => {
+                        match tokens.get(index + 1) {
+                            Some((token, span)) => {
+                                match element(token, span) {
+                                    Some(precedence) => alternative.precedence = Some(precedence),
+                                    None => {
+                                        let slice =
+                                            &bison_string[span.offset..span.offset + span.len];
+                                        return Err(unexpected_token(
+                                            slice,
+                                            span.clone(),
+                                            &["token"],
+                                        ));
+                                    },
+                                }
+                            },
+                            None => return Err(unexpected_eof(bison_string, &["token"])),
+                        }
+                        index += 1;
+                    },
+                    BisonToken::Directive(directive) => {
+                        warnings.push(BisonWarning::UntranslatedDirective {
+                            directive: directive.clone(),
+                            span: token_span.clone(),
+                        });
+                        // Arguments of the directive (e.g., `%dprec 1`, `%merge <select>`) are skipped.
+                        if let Some((BisonToken::Number | BisonToken::TypeTag, _)) =
+                            tokens.get(index + 1)
+                        {
+                            index += 1;
+                        }
+                    },
+                    _ => {
+                        let slice =
+                            &bison_string[token_span.offset..token_span.offset + token_span.len];
+                        return Err(unexpected_token(
+                            slice,
+                            token_span.clone(),
+                            &["symbol", "token"],
+                        ));
+                    },
+                }
+                index += 1;
+            }
+            rule.alternatives.push(alternative);
+            grammar.rules.push(rule);
+        }
+
+        Ok(())
+    }
+
+    /// Translator of the elements of a bison grammar.
+    struct Translator<'g> {
+        grammar: &'g BisonGrammar,
+        symbols: IndexMap<SmolStr, Symbol>,
+        regex_tokens: IndexMap<SmolStr, RegexToken>,
+        declared_tokens: IndexSet<SmolStr>,
+        used_tokens: IndexMap<SmolStr, Span>,
+        warnings: Vec<BisonWarning>,
+    }
+
+    impl Translator<'_> {
+        /// Translates an element, keeping track of the named tokens used in the rules.
+        fn translate(&mut self, element: &Element, in_rule: bool) -> String {
+            match element {
+                Element::Name { name, label, span } => {
+                    let label = match label {
+                        Some(label) => {
+                            let label =
+                                label.chars().map(|char| if char == '.' { '_' } else { char });
+                            format!("{}:", label.collect::<String>())
+                        },
+                        None => String::new(),
+                    };
+                    if let Some(symbol) = self.symbols.get(name) {
+                        return format!("{}{}", label, symbol);
+                    }
+                    // String aliases of named tokens (e.g., `"->"` of `ARROW`) are their spellings.
+                    if let Some(alias) = self.grammar.aliases.get(name) {
+                        return format!("{}{}", label, ConstantToken::from(alias.clone()));
+                    }
+                    if in_rule && !self.used_tokens.contains_key(name) {
+                        if !self.declared_tokens.contains(name) {
+                            self.warnings.push(BisonWarning::UndeclaredToken {
+                                token: name.clone(),
+                                span: span.clone(),
+                            });
+                        }
+                        let declaration_span = self.grammar.tokens.get(name).unwrap_or(span);
+                        self.used_tokens.insert(name.clone(), declaration_span.clone());
+                    }
+                    format!("{}{}", label, self.regex_token(name))
+                },
+                Element::Character(character) => ConstantToken::from(character.clone()).to_string(),
+                Element::String(string) => ConstantToken::from(string.clone()).to_string(),
+            }
+        }
+
+        /// Gets the regex token of a named token.
+        fn regex_token(&mut self, name: &SmolStr) -> RegexToken {
+            if let Some(regex_token) = self.regex_tokens.get(name) {
+                return regex_token.clone();
+            }
+            let regex_token = RegexToken::from(unique_name(regex_token_name(name), |name| {
+                self.regex_tokens.values().any(|regex_token| regex_token.as_str() == name)
+            }));
+            self.regex_tokens.insert(name.clone(), regex_token.clone());
+            regex_token
+        }
+    }
+
+
+    /// Writes the grammar string of a bison grammar.
+    fn write(grammar: &BisonGrammar, warnings: &mut Vec<BisonWarning>) -> String {
+        let mut symbols = IndexMap::<SmolStr, Symbol>::new();
+        for rule in grammar.rules.iter() {
+            if !symbols.contains_key(&rule.symbol) {
+                let symbol = unique_name(symbol_name(&rule.symbol), |name| {
+                    symbols.values().any(|symbol| symbol.as_str() == name)
+                });
+                symbols.insert(rule.symbol.clone(), Symbol::from(symbol));
+            }
+        }
+
+        let mut declared_tokens = grammar.tokens.keys().cloned().collect::<IndexSet<_>>();
+        for (_, elements) in grammar.precedences.iter() {
+            for element in elements {
+                if let Element::Name { name, .. } = element {
+                    declared_tokens.insert(name.clone());
+                }
+            }
+        }
+
+        let mut translator = Translator {
+            grammar,
+            symbols,
+            regex_tokens: IndexMap::new(),
+            declared_tokens,
+            used_tokens: IndexMap::new(),
+            warnings: Vec::new(),
+        };
+
+        let mut declarations = Vec::new();
+        if let Some((start_symbol, span)) = &grammar.start_symbol {
+            match translator.symbols.get(start_symbol) {
+                Some(symbol) => declarations.push(format!("%start {}", symbol)),
+                None => {
+                    translator.warnings.push(BisonWarning::UntranslatedDirective {
+                        directive: "%start".into(),
+                        span: span.clone(),
+                    });
+                },
+            }
+        }
+        for (associativity, elements) in grammar.precedences.iter() {
+            if elements.is_empty() {
+                continue;
+            }
+            let tokens = elements.iter().map(|element| translator.translate(element, false));
+            declarations.push(format!(
+                "{} {}",
+                associativity,
+                tokens.collect::<Vec<_>>().join(" ")
+            ));
+        }
+
+        let mut sections = Vec::new();
+        if !declarations.is_empty() {
+            sections.push(declarations);
+        }
+
+        for rule in grammar.rules.iter() {
+            let symbol = translator.symbols[&rule.symbol].clone();
+
+            let mut lines = Vec::new();
+            for alternative in rule.alternatives.iter() {
+                let uses_error_token = alternative.elements.iter().any(
+                    |element| matches!(element, Element::Name { name, .. } if name == "error"),
+                );
+                if uses_error_token && !translator.symbols.contains_key("error") {
+                    translator.warnings.push(BisonWarning::ErrorRuleRemoved {
+                        symbol: rule.symbol.clone(),
+                        span: alternative.span.clone(),
+                    });
+                    continue;
+                }
+
+                let mut pattern = alternative
+                    .elements
+                    .iter()
+                    .map(|element| translator.translate(element, true))
+                    .collect::<Vec<_>>();
+                if pattern.is_empty() {
+                    pattern.push("ε".to_owned());
+                }
+                if let Some(precedence) = &alternative.precedence {
+                    pattern.push(format!("%prec {}", translator.translate(precedence, false)));
+                }
+                lines.push(format!("{} -> {}", symbol, pattern.join(" ")));
+            }
+            if !lines.is_empty() {
+                sections.push(lines);
+            }
+        }
+
+        let mut definitions = Vec::new();
+        for (name, span) in translator.used_tokens.clone() {
+            let regex_token = translator.regex_token(&name);
+            definitions.push(format!("{} -> /{}/", regex_token, regex::escape(&name)));
+            translator.warnings.push(BisonWarning::PlaceholderRegex {
+                token: name,
+                regex_token,
+                span,
+            });
+        }
+        if !definitions.is_empty() {
+            sections.push(definitions);
+        }
+
+        warnings.extend(translator.warnings);
+        sections.iter().map(|lines| format!("{}\n", lines.join("\n"))).join("\n")
+    }
+
+    /// Creates the name of the symbol of a nonterminal (e.g., `expr_list` to `ExprList`).
+    fn symbol_name(nonterminal: &str) -> String {
+        let name = nonterminal
+            .split(|char: char| !char.is_ascii_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                let mut chars = part.chars();
+                let first = chars.next().unwrap().to_ascii_uppercase();
+                format!("{}{}", first, chars.as_str())
+            })
+            .collect::<String>();
+        if name.is_empty() { "Symbol".to_owned() } else { name }
+    }
+
+    /// Creates the name of the regex token of a named token (e.g., `T_NUMBER` to `tNumber`).
+    fn regex_token_name(token: &str) -> String {
+        let name = token
+            .split(|char: char| !char.is_ascii_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .enumerate()
+            .map(|(index, part)| {
+                let part = if part.chars().any(|char| char.is_ascii_lowercase()) {
+                    part.to_owned()
+                } else {
+                    part.to_ascii_lowercase()
+                };
+                let mut chars = part.chars();
+                let first = chars.next().unwrap();
+                let first = if index == 0 {
+                    first.to_ascii_lowercase()
+                } else {
+                    first.to_ascii_uppercase()
+                };
+                format!("{}{}", first, chars.as_str())
+            })
+            .collect::<String>();
+        if name.is_empty() { "token".to_owned() } else { name }
+    }
+
+    /// Makes a name unique by appending a number to it if it's already taken.
+    fn unique_name(name: String, is_taken: impl Fn(&str) -> bool) -> String {
+        let mut unique_name = name.clone();
+        let mut counter = 2;
+        while is_taken(&unique_name) {
+            unique_name = format!("{}{}", name, counter);
+            counter += 1;
+        }
+        unique_name
+    }
+
+    /// Converts a token of a bison grammar to an element, if it's a symbol or a token.
+    fn element(token: &BisonToken, span: &Span) -> Option<Element> {
+        match token {
+            BisonToken::Identifier(name) => {
+                Some(Element::Name { name: name.clone(), label: None, span: span.clone() })
+            },
+            BisonToken::Character(character) => Some(Element::Character(character.clone())),
+            BisonToken::String(string) => Some(Element::String(string.clone())),
+            _ => None,
+        }
+    }
+
+    /// Gets the span of a range of a bison grammar string.
+    fn span_of(bison_string: &str, range: std::ops::Range<usize>) -> Span {
+        let preceding = &bison_string[..range.start];
+        let line = preceding.matches('\n').count() + 1;
+        let line_start = preceding.rfind('\n').map(|index| index + 1).unwrap_or(0);
+        let column = bison_string[line_start..range.start].chars().count() + 1;
+        Span { offset: range.start, len: range.len(), line, column, source: None }
+    }
+
+    /// Creates an unexpected token error.
+    fn unexpected_token(slice: &str, span: Span, expected: &[&str]) -> GrammarError {
+        GrammarError::UnexpectedToken {
+            line: span.line,
+            column: span.column,
+            span,
+            token: slice.into(),
+            expected: expected.iter().map(|expected| SmolStr::from(*expected)).collect(),
+        }
+    }
+
+    /// Creates an unexpected end of file error.
+    fn unexpected_eof(bison_string: &str, expected: &[&str]) -> GrammarError {
+        GrammarError::UnexpectedEof {
+            span: span_of(bison_string, bison_string.len()..bison_string.len()),
+            expected: expected.iter().map(|expected| SmolStr::from(*expected)).collect(),
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use crate::prelude::*;
+
+
+/// Comparison of the languages of two parsers on the sentences up to a length.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    max_length: usize,
+    compared_sentences: usize,
+    exhaustive: bool,
+    only_accepted_by_first: Vec<Vec<Token>>,
+    only_accepted_by_second: Vec<Vec<Token>>,
+}
+
+impl Comparison {
+    /// Gets the maximum number of tokens in the compared sentences.
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Gets the number of sentences enumerated from both grammars.
+    pub fn compared_sentences(&self) -> usize {
+        self.compared_sentences
+    }
+
+    /// Gets whether all sentences up to the maximum length are compared.
+    pub fn is_exhaustive(&self) -> bool {
+        self.exhaustive
+    }
+
+    /// Gets the sentences accepted by the first parser but not by the second parser.
+    pub fn only_accepted_by_first(&self) -> &[Vec<Token>] {
+        &self.only_accepted_by_first
+    }
+
+    /// Gets the sentences accepted by the second parser but not by the first parser.
+    pub fn only_accepted_by_second(&self) -> &[Vec<Token>] {
+        &self.only_accepted_by_second
+    }
+
+    /// Gets whether no difference is found between the languages of the parsers.
+    pub fn is_equivalent(&self) -> bool {
+        self.only_accepted_by_first.is_empty() && self.only_accepted_by_second.is_empty()
+    }
+}
+
+
+impl Grammar {
+    /// Compares the language of the grammar with the language of another grammar,
+    /// using LR(1) parsers of the grammars (see `Parser::compare`).
+    pub fn compare(
+        &self,
+        other: &Grammar,
+        max_length: usize,
+        max_sentences: usize,
+    ) -> Result<Comparison, ParserError> {
+        let parser = Parser::lr(self.clone())?;
+        let other_parser = Parser::lr(other.clone())?;
+        Ok(parser.compare(&other_parser, max_length, max_sentences))
+    }
+}
+
+impl Parser {
+    /// Compares the language of the parser with the language of another parser.
+    ///
+    /// Sentences of up to `max_length` tokens are enumerated from the grammars of both parsers,
+    /// keeping at most `max_sentences` sentences for each symbol, and each sentence accepted by
+    /// the parser of its grammar is parsed with the other parser. Tokens are compared by their
+    /// names, so regex tokens need to have the same names in both grammars.
+    pub fn compare(&self, other: &Parser, max_length: usize, max_sentences: usize) -> Comparison {
+        let (sentences, exhaustive) = self.grammar().sentences_internal(max_length, max_sentences);
+        let (other_sentences, other_exhaustive) =
+            other.grammar().sentences_internal(max_length, max_sentences);
+
+        let only_accepted_by_first = sentences
+            .iter()
+            .filter(|sentence| self.accepts_internal(sentence) && !other.accepts_internal(sentence))
+            .cloned()
+            .collect();
+        let only_accepted_by_second = other_sentences
+            .iter()
+            .filter(|sentence| other.accepts_internal(sentence) && !self.accepts_internal(sentence))
+            .cloned()
+            .collect();
+
+        Comparison {
+            max_length,
+            compared_sentences: sentences.len() + other_sentences.len(),
+            exhaustive: exhaustive && other_exhaustive,
+            only_accepted_by_first,
+            only_accepted_by_second,
+        }
+    }
+}
+
+impl Parser {
+    /// Internal check of whether the parser accepts a sentence.
+    fn accepts_internal(&self, sentence: &[Token]) -> bool {
+        let span = Span { offset: 0, len: 0, line: 1, column: 1, source: None };
+        let tokens = sentence
+            .iter()
+            .chain([&Token::Eof])
+            .map(|token| (Spanned::new(token.clone(), span.clone()), ""))
+            .collect();
+        self.parse(tokens).is_ok()
+    }
+}
+
+impl Grammar {
+    /// Internal enumeration of the sentences of the start symbol, ordered by their length,
+    /// together with whether all sentences up to the maximum length are enumerated.
+    fn sentences_internal(
+        &self,
+        max_length: usize,
+        max_sentences: usize,
+    ) -> (Vec<Vec<Token>>, bool) {
+        // Sentences of each symbol, grouped by their length.
+        let mut sentences = IndexMap::<&Symbol, Vec<IndexSet<Vec<Token>>>>::new();
+        let mut exhaustive = true;
+
+        for length in 0..=max_length {
+            let mut done = false;
+            while !done {
+                done = true;
+                for rule in self.rules() {
+                    let (known_sentences, known_count) = match sentences.get(rule.symbol()) {
+                        Some(symbol_sentences) => {
+                            let count = symbol_sentences.iter().map(|sentences| sentences.len());
+                            (symbol_sentences.get(length), count.sum())
+                        },
+                        None => (None, 0),
+                    };
+                    if known_count >= max_sentences {
+                        exhaustive = false;
+                        continue;
+                    }
+
+                    let mut derived_sentences = IndexSet::new();
+                    derive_sentences(
+                        &sentences,
+                        rule.pattern(),
+                        length,
+                        &mut Vec::new(),
+                        known_sentences,
+                        &mut derived_sentences,
+                        max_sentences - known_count,
+                    );
+                    if derived_sentences.is_empty() {
+                        continue;
+                    }
+
+                    sentences
+                        .entry(rule.symbol())
+                        .or_insert_with(|| vec![IndexSet::new(); max_length + 1])[length]
+                        .extend(derived_sentences);
+                    done = false;
+                }
+            }
+        }
+
+        let start_sentences = match sentences.swap_remove(self.start_symbol()) {
+            Some(start_sentences) => start_sentences.into_iter().flatten().collect(),
+            None => vec![],
+        };
+        (start_sentences, exhaustive)
+    }
+}
+
+
+/// Derives the sentences of a length from a pattern, using the known sentences of the symbols.
+///
+/// Derivation stops once `limit` sentences that are not in `known_sentences` are derived.
+fn derive_sentences(
+    sentences: &IndexMap<&Symbol, Vec<IndexSet<Vec<Token>>>>,
+    pattern: &[AtomicPattern],
+    length: usize,
+    prefix: &mut Vec<Token>,
+    known_sentences: Option<&IndexSet<Vec<Token>>>,
+    derived_sentences: &mut IndexSet<Vec<Token>>,
+    limit: usize,
+) {
+    if derived_sentences.len() >= limit {
+        return;
+    }
+    match pattern.split_first() {
+        None => {
+            let is_known = known_sentences.is_some_and(|known| known.contains(prefix));
+            if length == 0 && !is_known {
+                derived_sentences.insert(prefix.clone());
+            }
+        },
+        Some((AtomicPattern::Token(token), rest)) => {
+            if length > 0 {
+                prefix.push(token.clone());
+                derive_sentences(
+                    sentences,
+                    rest,
+                    length - 1,
+                    prefix,
+                    known_sentences,
+                    derived_sentences,
+                    limit,
+                );
+                prefix.pop();
+            }
+        },
+        Some((AtomicPattern::Symbol(symbol), rest)) => {
+            let symbol_sentences = match sentences.get(symbol) {
+                Some(symbol_sentences) => symbol_sentences,
+                None => return,
+            };
+            for (sentence_length, sentences_of_length) in
+                symbol_sentences.iter().enumerate().take(length + 1)
+            {
+                for sentence in sentences_of_length {
+                    let prefix_length = prefix.len();
+                    prefix.extend(sentence.iter().cloned());
+                    derive_sentences(
+                        sentences,
+                        rest,
+                        length - sentence_length,
+                        prefix,
+                        known_sentences,
+                        derived_sentences,
+                        limit,
+                    );
+                    prefix.truncate(prefix_length);
+                }
+            }
+        },
+    }
+}
@@ -22,7 +22,13 @@ pub enum GrammarError {
             )
         },
     )]
-    UnexpectedToken { line: usize, column: usize, token: SmolStr, expected: SmallVec<[SmolStr; 2]> },
+    UnexpectedToken {
+        line: usize,
+        column: usize,
+        span: Span,
+        token: SmolStr,
+        expected: SmallVec<[SmolStr; 2]>,
+    },
 
     /// An unexpected end of file has been encountered.
     #[error(
@@ -36,7 +42,7 @@ pub enum GrammarError {
             )
         },
     )]
-    UnexpectedEof { expected: SmallVec<[SmolStr; 2]> },
+    UnexpectedEof { span: Span, expected: SmallVec<[SmolStr; 2]> },
 
     /// Invalid regex has been encountered.
     #[error(
@@ -45,7 +51,93 @@ pub enum GrammarError {
         format_smolstr!("{}", line).cyan(),
         format_smolstr!("{}", column).cyan(),
     )]
-    InvalidRegex { line: usize, column: usize, regex: SmolStr },
+    InvalidRegex { line: usize, column: usize, span: Span, regex: SmolStr },
+
+    /// A parameterized symbol is applied without being defined.
+    #[error(
+        "parameterized symbol {} at line {} column {} is not defined",
+        format_smolstr!("{}", symbol).green(),
+        format_smolstr!("{}", line).cyan(),
+        format_smolstr!("{}", column).cyan(),
+    )]
+    UndefinedParameterizedSymbol { line: usize, column: usize, span: Span, symbol: Symbol },
+
+    /// A parameterized symbol is used with a different number of arguments than its parameters.
+    #[error(
+        "parameterized symbol {} at line {} column {} expects {} {} but got {}",
+        format_smolstr!("{}", symbol).green(),
+        format_smolstr!("{}", line).cyan(),
+        format_smolstr!("{}", column).cyan(),
+        expected,
+        if *expected == 1 { "argument" } else { "arguments" },
+        got,
+    )]
+    ArgumentCountMismatch {
+        line: usize,
+        column: usize,
+        span: Span,
+        symbol: Symbol,
+        expected: usize,
+        got: usize,
+    },
+
+    /// A parameterized symbol is instantiated with ever growing arguments.
+    #[error(
+        "instantiation of parameterized symbol {} at line {} column {} does not terminate",
+        format_smolstr!("{}", symbol).green(),
+        format_smolstr!("{}", line).cyan(),
+        format_smolstr!("{}", column).cyan(),
+    )]
+    UnboundedInstantiation { line: usize, column: usize, span: Span, symbol: Symbol },
+
+    /// A lexer mode is used in a transition without being declared.
+    #[error(
+        "mode {} at line {} column {} is not declared",
+        mode.green(),
+        format_smolstr!("{}", line).cyan(),
+        format_smolstr!("{}", column).cyan(),
+    )]
+    UndefinedMode { line: usize, column: usize, span: Span, mode: SmolStr },
+
+    /// An import cannot be resolved.
+    #[error(
+        "import {} at line {} column {} cannot be resolved ({})",
+        format_smolstr!("\"{}\"", path).green(),
+        format_smolstr!("{}", line).cyan(),
+        format_smolstr!("{}", column).cyan(),
+        reason,
+    )]
+    UnresolvedImport { line: usize, column: usize, span: Span, path: SmolStr, reason: SmolStr },
+
+    /// A grammar imports itself directly or indirectly.
+    #[error(
+        "import {} at line {} column {} is cyclic",
+        format_smolstr!("\"{}\"", path).green(),
+        format_smolstr!("{}", line).cyan(),
+        format_smolstr!("{}", column).cyan(),
+    )]
+    CyclicImport { line: usize, column: usize, span: Span, path: SmolStr },
+
+    /// An error has been encountered in an imported grammar.
+    #[error("{} in {}", error, grammar.green())]
+    InImportedGrammar { grammar: SmolStr, error: Box<GrammarError> },
+}
+
+
+/// Grammar builder error of a grammar tried to be constructed programmatically.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+#[derive(Debug, Error)]
+pub enum GrammarBuilderError {
+    /// Invalid regex is tried to be added for a regex token.
+    #[error(
+        "invalid regex {} for regex token {} ({})",
+        format_smolstr!("/{}/", regex).green(),
+        format_smolstr!("{}", regex_token).green(),
+        reason,
+    )]
+    InvalidRegex { regex_token: RegexToken, regex: SmolStr, reason: SmolStr },
 }
 
 
@@ -61,19 +153,25 @@ pub enum ParserError {
 
     /// An undefined symbol is used in a pattern.
     #[error(
-        "symbol {} in rule {} is not defined",
+        "symbol {} in rule {} is not defined{}",
         format_smolstr!("{}", symbol).green(),
         format_smolstr!("{}", rule).green(),
+        source(span.as_ref()),
     )]
-    UndefinedSymbol { symbol: Symbol, rule: Rule },
+    UndefinedSymbol { symbol: Symbol, rule: Rule, span: Option<Span> },
 
     /// An undefined symbol is used in a pattern.
     #[error(
-        "regex token {} in rule {} is not defined",
+        "regex token {} in rule {} is not defined{}",
         format_smolstr!("{}", regex_token).green(),
         format_smolstr!("{}", rule).green(),
+        source(span.as_ref()),
     )]
-    UndefinedRegexToken { regex_token: RegexToken, rule: Rule },
+    UndefinedRegexToken { regex_token: RegexToken, rule: Rule, span: Option<Span> },
+
+    /// An undefined symbol is declared as an entry symbol.
+    #[error("entry symbol {} is not defined", format_smolstr!("{}", symbol).green())]
+    UndefinedEntrySymbol { symbol: Symbol, span: Option<Span> },
 
     /// A conflict has been detected.
     #[error(
@@ -81,7 +179,7 @@ pub enum ParserError {
         format_smolstr!("{}", state).green(),
         format_smolstr!("{}", token).green(),
     )]
-    Conflict { parser: Box<Parser>, state: usize, token: Token },
+    Conflict { parser: Box<Parser>, state: usize, token: Token, spans: Vec<Span> },
 }
 
 #[cfg(feature = "wasm")]
@@ -160,4 +258,17 @@ pub enum ParsingError {
         },
     )]
     UnexpectedEof { expected: SmallVec<[Token; 2]>, span: Span },
+
+    /// Parsing is tried to be started from a symbol which is not an entry symbol.
+    #[error("symbol {} is not an entry symbol", format_smolstr!("{}", symbol).green())]
+    UnknownEntrySymbol { symbol: Symbol },
+}
+
+
+/// Formats the imported grammar a span is in for an error message.
+pub(crate) fn source(span: Option<&Span>) -> String {
+    match span.and_then(|span| span.source.as_ref()) {
+        Some(source) => format!(" in {}", source.green()),
+        None => String::new(),
+    }
 }
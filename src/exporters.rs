@@ -0,0 +1,1061 @@
+use crate::prelude::*;
+
+
+/// Format (e.g., bison, Lark, tree-sitter) a grammar is exported to.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ExportFormat {
+    /// Bison grammar skeleton (e.g., `grammar.y`).
+    Bison,
+    /// Lark grammar (e.g., `grammar.lark`).
+    Lark,
+    /// Tree-sitter grammar (e.g., `grammar.js`).
+    TreeSitter,
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportFormat::Bison => write!(f, "bison"),
+            ExportFormat::Lark => write!(f, "Lark"),
+            ExportFormat::TreeSitter => write!(f, "tree-sitter"),
+        }
+    }
+}
+
+
+/// Export warning of a part of a grammar that cannot be expressed in the exported format.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum ExportWarning {
+    /// A feature of the grammar (e.g., lexer modes, labels) has no equivalent in the format.
+    #[error(
+        "{} cannot be expressed in {}",
+        feature,
+        format_smolstr!("{}", format).cyan(),
+    )]
+    UnsupportedFeature { feature: SmolStr, format: ExportFormat },
+
+    /// A construct (e.g., unicode classes) in the regex of a regex token has no equivalent in the format.
+    #[error(
+        "{} in the regex of {} cannot be expressed in {}",
+        construct,
+        format_smolstr!("{}", regex_token).green(),
+        format_smolstr!("{}", format).cyan(),
+    )]
+    UnsupportedRegex { regex_token: RegexToken, construct: SmolStr, format: ExportFormat },
+}
+
+
+impl Grammar {
+    /// Exports the grammar to a bison grammar skeleton without semantic actions.
+    ///
+    /// Bison grammars don't have lexical definitions, so the regexes of the regex tokens
+    /// are written as comments for the lexer that needs to be written separately.
+    pub fn to_bison(&self) -> (String, Vec<ExportWarning>) {
+        let format = ExportFormat::Bison;
+        let warnings = self.unsupported_features_internal(format);
+
+        let mut names = Names::new(["error", "EOF", "NULL"]);
+        for symbol in self.symbols() {
+            names.symbol(symbol, symbol_name(self, symbol));
+        }
+        for regex_token in self.regular_expressions().keys() {
+            names.regex_token(regex_token, snake_case(regex_token).to_ascii_uppercase());
+        }
+        let precedence_tokens = self.rules().iter().filter_map(|rule| rule.precedence());
+        for token in self.precedences().keys().chain(precedence_tokens) {
+            if let Token::Regex(regex_token) = token {
+                names.regex_token(regex_token, snake_case(regex_token).to_ascii_uppercase());
+            }
+        }
+        for constant_token in self.constant_tokens() {
+            if bison_character(constant_token).is_none() {
+                names.constant_token(constant_token, constant_token_name(constant_token));
+            }
+        }
+
+        let token = |token: &Token| {
+            match token {
+                Token::Constant(constant_token) => {
+                    bison_character(constant_token).unwrap_or_else(|| c_string(constant_token))
+                },
+                Token::Regex(regex_token) => names.regex_tokens[regex_token].clone(),
+                Token::Eof => "YYEOF".to_owned(),
+            }
+        };
+
+        let mut output = String::new();
+        output.push_str("%{\nint yylex(void);\nvoid yyerror(const char *message);\n%}\n");
+
+        let mut declarations = Vec::new();
+        for (regex_token, regex) in self.regular_expressions() {
+            let regex = regex.as_str().strip_prefix('^').unwrap_or(regex.as_str());
+            let name = &names.regex_tokens[regex_token];
+            declarations.push(format!("%token {} /* {} */", name, regex.replace("*/", "*\\/")));
+        }
+        for (constant_token, name) in names.constant_tokens.iter() {
+            declarations.push(format!("%token {} {}", name, c_string(constant_token)));
+        }
+        if !declarations.is_empty() {
+            output.push_str(&format!("\n{}\n", declarations.join("\n")));
+        }
+
+        let mut declarations = Vec::new();
+        let precedences = self.precedences().iter().chunk_by(|(_, precedence)| **precedence);
+        for (precedence, tokens) in precedences.into_iter() {
+            let tokens = tokens.map(|(precedence_token, _)| token(precedence_token)).join(" ");
+            declarations.push(format!("{} {}", precedence.associativity(), tokens));
+        }
+        if self.rules().first().map(|rule| rule.symbol()) != Some(self.start_symbol()) {
+            declarations.push(format!("%start {}", names.symbols[self.start_symbol()]));
+        }
+        if !declarations.is_empty() {
+            output.push_str(&format!("\n{}\n", declarations.join("\n")));
+        }
+
+        output.push_str("\n%%\n");
+        for (symbol, rules) in rules_by_symbol(self.rules(), None) {
+            output.push_str(&format!("\n{}\n", names.symbols[symbol]));
+            for (index, rule) in rules.iter().enumerate() {
+                let mut elements = rule
+                    .pattern()
+                    .iter()
+                    .zip(rule.labels())
+                    .map(|(atomic_pattern, label)| {
+                        let element = match atomic_pattern {
+                            AtomicPattern::Symbol(symbol) => names.symbols[symbol].clone(),
+                            AtomicPattern::Token(atomic_token) => token(atomic_token),
+                        };
+                        match label {
+                            Some(label) => format!("{}[{}]", element, label),
+                            None => element,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                if elements.is_empty() {
+                    elements.push("%empty".to_owned());
+                }
+                if let Some(precedence_token) = rule.precedence() {
+                    elements.push(format!("%prec {}", token(precedence_token)));
+                }
+                let separator = if index == 0 { ':' } else { '|' };
+                output.push_str(&format!("    {} {}\n", separator, elements.join(" ")));
+            }
+            output.push_str("    ;\n");
+        }
+        output.push_str("\n%%\n");
+
+        (output, warnings)
+    }
+
+    /// Exports the grammar to a Lark grammar.
+    ///
+    /// Auxiliary symbols are written as the EBNF constructs they are generated for,
+    /// and the start symbol is inlined into the `start` rule of Lark.
+    pub fn to_lark(&self) -> (String, Vec<ExportWarning>) {
+        let format = ExportFormat::Lark;
+        let mut warnings = self.unsupported_features_internal(format);
+        if self.rules().iter().any(|rule| rule.labels().iter().any(Option::is_some)) {
+            warnings.push(ExportWarning::UnsupportedFeature { feature: "labels".into(), format });
+        }
+        if !self.precedences().is_empty() {
+            let feature = "precedence declarations".into();
+            warnings.push(ExportWarning::UnsupportedFeature { feature, format });
+        }
+
+        let mut names = Names::new(["start"]);
+        for symbol in self.symbols() {
+            if !self.auxiliary_symbols().contains_key(symbol) {
+                names.symbol(symbol, symbol_name(self, symbol));
+            }
+        }
+        for regex_token in self.regular_expressions().keys() {
+            names.regex_token(regex_token, snake_case(regex_token).to_ascii_uppercase());
+        }
+
+        let mut output = format!("?start: {}\n", names.symbols[self.start_symbol()]);
+        for (symbol, rules) in rules_by_symbol(self.rules(), None) {
+            if self.auxiliary_symbols().contains_key(symbol) {
+                continue;
+            }
+            let name = &names.symbols[symbol];
+            output.push('\n');
+            for (index, rule) in rules.iter().enumerate() {
+                let expression = self.pattern_expression_internal(rule.pattern(), rule.labels());
+                let line = if index == 0 {
+                    format!("{}: {}", name, expression.to_lark(self, &names))
+                } else {
+                    let indentation = name.len();
+                    format!("{:indentation$}| {}", "", expression.to_lark(self, &names))
+                };
+                output.push_str(line.trim_end());
+                output.push('\n');
+            }
+        }
+
+        if !self.regular_expressions().is_empty() {
+            output.push('\n');
+        }
+        for (regex_token, regex) in self.regular_expressions() {
+            let (regex, constructs) = translate_regex(regex, format);
+            for construct in constructs {
+                let regex_token = regex_token.clone();
+                warnings.push(ExportWarning::UnsupportedRegex { regex_token, construct, format });
+            }
+            let name = &names.regex_tokens[regex_token];
+            match self.priorities().get(regex_token) {
+                Some(priority) => output.push_str(&format!("{}.{}: /{}/\n", name, priority, regex)),
+                None => output.push_str(&format!("{}: /{}/\n", name, regex)),
+            }
+        }
+
+        output.push('\n');
+        if self.ignored_regular_expressions().is_empty() {
+            output.push_str("%import common.WS\n%ignore WS\n");
+        }
+        for ignored_regex in self.ignored_regular_expressions() {
+            let (regex, _) = translate_regex(ignored_regex, format);
+            output.push_str(&format!("%ignore /{}/\n", regex));
+        }
+
+        (output, warnings)
+    }
+
+    /// Exports the grammar to a tree-sitter grammar with a name.
+    ///
+    /// Auxiliary symbols are written as the EBNF constructs they are generated for. Tree-sitter
+    /// doesn't allow rules other than the start rule to match the empty string, so the rules
+    /// of the other nullable symbols only match the non-empty strings, and they are optional
+    /// where they are used instead.
+    pub fn to_tree_sitter(&self, name: &str) -> (String, Vec<ExportWarning>) {
+        let format = ExportFormat::TreeSitter;
+        let mut warnings = self.unsupported_features_internal(format);
+        let has_non_associative_rules = self.rules().iter().any(|rule| {
+            self.precedence_of(rule)
+                .is_some_and(|precedence| precedence.associativity() == Associativity::NonAssoc)
+        });
+        if has_non_associative_rules {
+            let feature = "non-associative precedence".into();
+            warnings.push(ExportWarning::UnsupportedFeature { feature, format });
+        }
+
+        let mut names = Names::new([]);
+        for symbol in self.symbols() {
+            if !self.auxiliary_symbols().contains_key(symbol) {
+                names.symbol(symbol, symbol_name(self, symbol));
+            }
+        }
+        for regex_token in self.regular_expressions().keys() {
+            names.regex_token(regex_token, snake_case(regex_token));
+        }
+
+        let mut definitions = IndexMap::<Symbol, Expression>::new();
+        for (symbol, rules) in rules_by_symbol(self.rules(), Some(self.start_symbol())) {
+            if self.auxiliary_symbols().contains_key(symbol) {
+                continue;
+            }
+            let alternatives = rules
+                .iter()
+                .map(|rule| {
+                    let expression =
+                        self.pattern_expression_internal(rule.pattern(), rule.labels());
+                    match self.precedence_of(rule) {
+                        Some(precedence) => {
+                            Expression::Precedence(precedence, Box::new(expression))
+                        },
+                        None => expression,
+                    }
+                })
+                .collect();
+            definitions.insert(symbol.clone(), Expression::choice(alternatives));
+        }
+
+        let mut nullable_symbols = NullableTable::construct(self)
+            .iter()
+            .filter(|symbol| definitions.contains_key(*symbol))
+            .cloned()
+            .collect::<IndexSet<_>>();
+        nullable_symbols.shift_remove(self.start_symbol());
+
+        // Symbols that only match the empty string are removed from the patterns they are used in.
+        let mut empty_symbols = IndexSet::new();
+        let mut done = false;
+        while !done {
+            done = true;
+            for symbol in nullable_symbols.iter() {
+                let definition = definitions[symbol].resolve(&nullable_symbols, &empty_symbols);
+                if !empty_symbols.contains(symbol) && definition.non_empty().is_none() {
+                    empty_symbols.insert(symbol.clone());
+                    done = false;
+                }
+            }
+        }
+
+        let name = match snake_case(name) {
+            name if name.is_empty() => "grammar".to_owned(),
+            name => name,
+        };
+        let mut output = format!("module.exports = grammar({{\n  name: '{}',\n", name);
+
+        if !self.ignored_regular_expressions().is_empty() {
+            output.push_str("\n  extras: $ => [\n");
+            for ignored_regex in self.ignored_regular_expressions() {
+                let (regex, _) = translate_regex(ignored_regex, format);
+                output.push_str(&format!("    /{}/,\n", regex));
+            }
+            output.push_str("  ],\n");
+        }
+
+        output.push_str("\n  rules: {\n");
+        let mut rules = Vec::new();
+        for (symbol, definition) in definitions.iter() {
+            if empty_symbols.contains(symbol) {
+                continue;
+            }
+            let mut definition = definition.resolve(&nullable_symbols, &empty_symbols);
+            if nullable_symbols.contains(symbol) {
+                definition = definition.non_empty().unwrap_or(Expression::Blank);
+            }
+            let name = &names.symbols[symbol];
+            match definition {
+                Expression::Choice(alternatives) => {
+                    let alternatives = alternatives
+                        .iter()
+                        .map(|alternative| {
+                            format!("      {},\n", alternative.to_tree_sitter(self, &names))
+                        })
+                        .join("");
+                    rules.push(format!("    {}: $ => choice(\n{}    ),\n", name, alternatives));
+                },
+                definition => {
+                    rules.push(format!(
+                        "    {}: $ => {},\n",
+                        name,
+                        definition.to_tree_sitter(self, &names)
+                    ));
+                },
+            }
+        }
+        for (regex_token, regex) in self.regular_expressions() {
+            let (regex, constructs) = translate_regex(regex, format);
+            for construct in constructs {
+                let regex_token = regex_token.clone();
+                warnings.push(ExportWarning::UnsupportedRegex { regex_token, construct, format });
+            }
+            let name = &names.regex_tokens[regex_token];
+            match self.priorities().get(regex_token) {
+                Some(priority) => {
+                    rules.push(format!(
+                        "    {}: $ => token(prec({}, /{}/)),\n",
+                        name, priority, regex
+                    ));
+                },
+                None => rules.push(format!("    {}: $ => /{}/,\n", name, regex)),
+            }
+        }
+        output.push_str(&rules.join("\n"));
+        output.push_str("  },\n});\n");
+
+        (output, warnings)
+    }
+}
+
+impl Grammar {
+    /// Internal detection of the features of the grammar that have no equivalent in a format.
+    fn unsupported_features_internal(&self, format: ExportFormat) -> Vec<ExportWarning> {
+        let mut features = Vec::new();
+        if self.entry_symbols().len() > 1 {
+            features.push("multiple entry symbols");
+        }
+        if !self.modes().is_empty() || !self.transitions().is_empty() {
+            features.push("lexer modes");
+        }
+        if format == ExportFormat::Bison {
+            if !self.priorities().is_empty() {
+                features.push("token priorities");
+            }
+            if !self.keywords().is_empty() {
+                features.push("keywords");
+            }
+            if self.case_insensitive() || !self.case_insensitive_tokens().is_empty() {
+                features.push("case insensitive tokens");
+            }
+            if !self.ignored_regular_expressions().is_empty() {
+                features.push("ignored regexes");
+            }
+        }
+        features
+            .into_iter()
+            .map(|feature| ExportWarning::UnsupportedFeature { feature: feature.into(), format })
+            .collect()
+    }
+
+    /// Internal creation of the expression of a pattern,
+    /// writing the auxiliary symbols as the EBNF constructs they are generated for.
+    fn pattern_expression_internal(
+        &self,
+        pattern: &[AtomicPattern],
+        labels: &[Option<SmolStr>],
+    ) -> Expression {
+        let elements = pattern
+            .iter()
+            .zip(labels)
+            .map(|(atomic_pattern, label)| {
+                let element = match atomic_pattern {
+                    AtomicPattern::Symbol(symbol) => {
+                        match self.auxiliary_symbols().get(symbol) {
+                            Some(construct) => {
+                                self.construct_expression_internal(symbol, *construct)
+                            },
+                            None => Expression::Symbol(symbol.clone()),
+                        }
+                    },
+                    AtomicPattern::Token(token) => Expression::Token(token.clone()),
+                };
+                match label {
+                    Some(label) => Expression::Field(label.clone(), Box::new(element)),
+                    None => element,
+                }
+            })
+            .collect();
+        Expression::sequence(elements)
+    }
+
+    /// Internal creation of the expression of an auxiliary symbol from its rules.
+    fn construct_expression_internal(
+        &self,
+        symbol: &Symbol,
+        construct: EbnfConstruct,
+    ) -> Expression {
+        let alternatives = self
+            .construct_alternatives_internal(symbol, construct)
+            .into_iter()
+            .map(|(pattern, labels)| self.pattern_expression_internal(pattern, labels))
+            .collect();
+
+        let alternatives = Box::new(Expression::choice(alternatives));
+        match construct {
+            EbnfConstruct::Optional => Expression::Optional(alternatives),
+            EbnfConstruct::ZeroOrMore => Expression::Repeat(alternatives),
+            EbnfConstruct::OneOrMore => Expression::RepeatOne(alternatives),
+            EbnfConstruct::Group => *alternatives,
+        }
+    }
+}
+
+
+/// Expression (e.g., `seq(...)`, `X*`) of the rules of a symbol in an exported format.
+#[derive(Clone, Debug)]
+enum Expression {
+    Blank,
+    Symbol(Symbol),
+    Token(Token),
+    Field(SmolStr, Box<Expression>),
+    Precedence(Precedence, Box<Expression>),
+    Sequence(Vec<Expression>),
+    Choice(Vec<Expression>),
+    Optional(Box<Expression>),
+    Repeat(Box<Expression>),
+    RepeatOne(Box<Expression>),
+}
+
+impl Expression {
+    /// Creates a sequence of expressions, flattening the nested sequences.
+    fn sequence(expressions: Vec<Expression>) -> Expression {
+        let mut flattened = Vec::with_capacity(expressions.len());
+        for expression in expressions {
+            match expression {
+                Expression::Blank => {},
+                Expression::Sequence(expressions) => flattened.extend(expressions),
+                expression => flattened.push(expression),
+            }
+        }
+        match flattened.len() {
+            0 => Expression::Blank,
+            1 => flattened.pop().unwrap(),
+            _ => Expression::Sequence(flattened),
+        }
+    }
+
+    /// Creates a choice between expressions.
+    fn choice(mut expressions: Vec<Expression>) -> Expression {
+        match expressions.len() {
+            0 => Expression::Blank,
+            1 => expressions.pop().unwrap(),
+            _ => Expression::Choice(expressions),
+        }
+    }
+}
+
+impl Expression {
+    /// Gets whether the expression matches the empty string.
+    fn is_nullable(&self, nullable_symbols: &IndexSet<Symbol>) -> bool {
+        match self {
+            Expression::Blank | Expression::Optional(_) | Expression::Repeat(_) => true,
+            Expression::Symbol(symbol) => nullable_symbols.contains(symbol),
+            Expression::Token(_) => false,
+            Expression::Field(_, expression)
+            | Expression::Precedence(_, expression)
+            | Expression::RepeatOne(expression) => expression.is_nullable(nullable_symbols),
+            Expression::Sequence(expressions) => {
+                expressions.iter().all(|expression| expression.is_nullable(nullable_symbols))
+            },
+            Expression::Choice(expressions) => {
+                expressions.iter().any(|expression| expression.is_nullable(nullable_symbols))
+            },
+        }
+    }
+
+    /// Makes the uses of the nullable symbols optional, and removes the uses of the empty symbols.
+    fn resolve(
+        &self,
+        nullable_symbols: &IndexSet<Symbol>,
+        empty_symbols: &IndexSet<Symbol>,
+    ) -> Expression {
+        let resolve = |expression: &Expression| expression.resolve(nullable_symbols, empty_symbols);
+        let no_symbols = IndexSet::new();
+        match self {
+            Expression::Symbol(symbol) if empty_symbols.contains(symbol) => Expression::Blank,
+            Expression::Symbol(symbol) if nullable_symbols.contains(symbol) => {
+                Expression::Optional(Box::new(self.clone()))
+            },
+            Expression::Blank | Expression::Symbol(_) | Expression::Token(_) => self.clone(),
+            Expression::Field(label, expression) => {
+                match resolve(expression) {
+                    Expression::Blank => Expression::Blank,
+                    expression => Expression::Field(label.clone(), Box::new(expression)),
+                }
+            },
+            Expression::Precedence(precedence, expression) => {
+                Expression::Precedence(*precedence, Box::new(resolve(expression)))
+            },
+            Expression::Sequence(expressions) => {
+                Expression::sequence(expressions.iter().map(resolve).collect())
+            },
+            Expression::Choice(expressions) => {
+                Expression::choice(expressions.iter().map(resolve).collect())
+            },
+            Expression::Optional(expression) => {
+                let expression = resolve(expression);
+                if expression.is_nullable(&no_symbols) {
+                    expression
+                } else {
+                    Expression::Optional(Box::new(expression))
+                }
+            },
+            Expression::Repeat(expression) | Expression::RepeatOne(expression) => {
+                let expression = resolve(expression);
+                let repeated = match expression.non_empty() {
+                    Some(repeated) => Box::new(repeated),
+                    None => return Expression::Blank,
+                };
+                if matches!(self, Expression::Repeat(_)) || expression.is_nullable(&no_symbols) {
+                    Expression::Repeat(repeated)
+                } else {
+                    Expression::RepeatOne(repeated)
+                }
+            },
+        }
+    }
+
+    /// Gets the expression matching the non-empty strings of a resolved expression, if there are any.
+    fn non_empty(&self) -> Option<Expression> {
+        let no_symbols = IndexSet::new();
+        match self {
+            Expression::Blank => None,
+            Expression::Symbol(_) | Expression::Token(_) => Some(self.clone()),
+            Expression::Field(label, expression) => {
+                expression
+                    .non_empty()
+                    .map(|expression| Expression::Field(label.clone(), Box::new(expression)))
+            },
+            Expression::Precedence(precedence, expression) => {
+                expression
+                    .non_empty()
+                    .map(|expression| Expression::Precedence(*precedence, Box::new(expression)))
+            },
+            Expression::Optional(expression) => expression.non_empty(),
+            Expression::Repeat(expression) | Expression::RepeatOne(expression) => {
+                expression.non_empty().map(|expression| Expression::RepeatOne(Box::new(expression)))
+            },
+            Expression::Choice(expressions) => {
+                let alternatives =
+                    expressions.iter().filter_map(Expression::non_empty).collect_vec();
+                if alternatives.is_empty() { None } else { Some(Expression::choice(alternatives)) }
+            },
+            Expression::Sequence(expressions) => {
+                if !self.is_nullable(&no_symbols) {
+                    return Some(self.clone());
+                }
+                // Non-empty strings of a nullable sequence start with a non-empty string
+                // of one of its expressions, preceded by the empty strings of the previous ones.
+                let alternatives = (0..expressions.len())
+                    .filter_map(|index| {
+                        let first = expressions[index].non_empty()?;
+                        let rest = expressions[index + 1..].iter().cloned();
+                        Some(Expression::sequence(std::iter::once(first).chain(rest).collect()))
+                    })
+                    .collect_vec();
+                if alternatives.is_empty() { None } else { Some(Expression::choice(alternatives)) }
+            },
+        }
+    }
+}
+
+impl Expression {
+    /// Writes the expression in the syntax of Lark.
+    fn to_lark(&self, grammar: &Grammar, names: &Names) -> String {
+        let postfix = |expression: &Expression, operator: &str| {
+            match expression {
+                Expression::Symbol(_) | Expression::Token(_) => {
+                    format!("{}{}", expression.to_lark(grammar, names), operator)
+                },
+                _ => format!("({}){}", expression.to_lark(grammar, names), operator),
+            }
+        };
+        match self {
+            Expression::Blank | Expression::Token(Token::Eof) => String::new(),
+            Expression::Symbol(symbol) => names.symbols[symbol].clone(),
+            Expression::Token(Token::Constant(constant_token)) => {
+                let string = python_string(constant_token);
+                if grammar.is_case_insensitive(constant_token) {
+                    format!("{}i", string)
+                } else {
+                    string
+                }
+            },
+            Expression::Token(Token::Regex(regex_token)) => names.regex_tokens[regex_token].clone(),
+            Expression::Field(_, expression) | Expression::Precedence(_, expression) => {
+                expression.to_lark(grammar, names)
+            },
+            Expression::Sequence(expressions) => {
+                expressions
+                    .iter()
+                    .map(|expression| {
+                        match expression {
+                            Expression::Choice(_) => {
+                                format!("({})", expression.to_lark(grammar, names))
+                            },
+                            _ => expression.to_lark(grammar, names),
+                        }
+                    })
+                    .join(" ")
+            },
+            Expression::Choice(expressions) => {
+                // Empty alternatives are written as optional choices.
+                if expressions.iter().any(|expression| matches!(expression, Expression::Blank)) {
+                    let alternatives = expressions
+                        .iter()
+                        .filter(|expression| !matches!(expression, Expression::Blank))
+                        .cloned()
+                        .collect();
+                    return postfix(&Expression::choice(alternatives), "?");
+                }
+                expressions.iter().map(|expression| expression.to_lark(grammar, names)).join(" | ")
+            },
+            Expression::Optional(expression) => postfix(expression, "?"),
+            Expression::Repeat(expression) => postfix(expression, "*"),
+            Expression::RepeatOne(expression) => postfix(expression, "+"),
+        }
+    }
+
+    /// Writes the expression in the syntax of tree-sitter.
+    fn to_tree_sitter(&self, grammar: &Grammar, names: &Names) -> String {
+        let join = |expressions: &[Expression]| {
+            expressions
+                .iter()
+                .map(|expression| expression.to_tree_sitter(grammar, names))
+                .join(", ")
+        };
+        match self {
+            Expression::Blank | Expression::Token(Token::Eof) => "blank()".to_owned(),
+            Expression::Symbol(symbol) => format!("$.{}", names.symbols[symbol]),
+            Expression::Token(Token::Constant(constant_token)) => {
+                if grammar.is_case_insensitive(constant_token) {
+                    format!("/{}/", case_insensitive_regex(constant_token))
+                } else {
+                    javascript_string(constant_token)
+                }
+            },
+            Expression::Token(Token::Regex(regex_token)) => {
+                format!("$.{}", names.regex_tokens[regex_token])
+            },
+            Expression::Field(label, expression) => {
+                format!("field('{}', {})", label, expression.to_tree_sitter(grammar, names))
+            },
+            Expression::Precedence(precedence, expression) => {
+                let function = match precedence.associativity() {
+                    Associativity::Left => "prec.left",
+                    Associativity::Right => "prec.right",
+                    Associativity::NonAssoc => "prec",
+                };
+                let expression = expression.to_tree_sitter(grammar, names);
+                format!("{}({}, {})", function, precedence.level(), expression)
+            },
+            Expression::Sequence(expressions) => format!("seq({})", join(expressions)),
+            Expression::Choice(expressions) => format!("choice({})", join(expressions)),
+            Expression::Optional(expression) => {
+                format!("optional({})", expression.to_tree_sitter(grammar, names))
+            },
+            Expression::Repeat(expression) => {
+                format!("repeat({})", expression.to_tree_sitter(grammar, names))
+            },
+            Expression::RepeatOne(expression) => {
+                format!("repeat1({})", expression.to_tree_sitter(grammar, names))
+            },
+        }
+    }
+}
+
+
+/// Names of the symbols and the tokens of a grammar in an exported format.
+struct Names {
+    taken: IndexSet<String>,
+    symbols: IndexMap<Symbol, String>,
+    regex_tokens: IndexMap<RegexToken, String>,
+    constant_tokens: IndexMap<ConstantToken, String>,
+}
+
+impl Names {
+    /// Creates names with reserved names of a format.
+    fn new<const N: usize>(reserved: [&str; N]) -> Names {
+        Names {
+            taken: reserved.iter().map(|name| name.to_string()).collect(),
+            symbols: IndexMap::new(),
+            regex_tokens: IndexMap::new(),
+            constant_tokens: IndexMap::new(),
+        }
+    }
+}
+
+impl Names {
+    /// Names a symbol, appending a number to the name if it's already taken.
+    fn symbol(&mut self, symbol: &Symbol, name: String) {
+        if !self.symbols.contains_key(symbol) {
+            let name = self.unique(name);
+            self.symbols.insert(symbol.clone(), name);
+        }
+    }
+
+    /// Names a regex token, appending a number to the name if it's already taken.
+    fn regex_token(&mut self, regex_token: &RegexToken, name: String) {
+        if !self.regex_tokens.contains_key(regex_token) {
+            let name = self.unique(name);
+            self.regex_tokens.insert(regex_token.clone(), name);
+        }
+    }
+
+    /// Names a constant token, appending a number to the name if it's already taken.
+    fn constant_token(&mut self, constant_token: &ConstantToken, name: String) {
+        if !self.constant_tokens.contains_key(constant_token) {
+            let name = self.unique(name);
+            self.constant_tokens.insert(constant_token.clone(), name);
+        }
+    }
+
+    /// Makes a name unique, and takes it.
+    fn unique(&mut self, name: String) -> String {
+        let mut unique_name = name.clone();
+        let mut counter = 2;
+        while self.taken.contains(&unique_name) {
+            unique_name = format!("{}{}", name, counter);
+            counter += 1;
+        }
+        self.taken.insert(unique_name.clone());
+        unique_name
+    }
+}
+
+
+/// Groups the rules by their symbols, putting the rules of a symbol first if given.
+fn rules_by_symbol<'g>(
+    rules: &'g [Rule],
+    first: Option<&Symbol>,
+) -> IndexMap<&'g Symbol, Vec<&'g Rule>> {
+    let mut rules_by_symbol = IndexMap::<&Symbol, Vec<&Rule>>::new();
+    if let Some(first) = first {
+        if let Some(rule) = rules.iter().find(|rule| rule.symbol() == first) {
+            rules_by_symbol.insert(rule.symbol(), vec![]);
+        }
+    }
+    for rule in rules {
+        rules_by_symbol.entry(rule.symbol()).or_default().push(rule);
+    }
+    rules_by_symbol
+}
+
+/// Converts a name (e.g., `ExprList`, `HTTPHeader`) to snake case (e.g., `expr_list`, `http_header`).
+fn snake_case(name: &str) -> String {
+    let chars =
+        name.chars().filter(|char| char.is_ascii_alphanumeric() || *char == '_').collect_vec();
+    let mut snake_case = String::with_capacity(chars.len() + 4);
+    for (index, char) in chars.iter().enumerate() {
+        if index > 0 && char.is_ascii_uppercase() {
+            let previous = chars[index - 1];
+            let next_is_lowercase = chars.get(index + 1).is_some_and(char::is_ascii_lowercase);
+            if previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next_is_lowercase)
+            {
+                snake_case.push('_');
+            }
+        }
+        snake_case.push(char.to_ascii_lowercase());
+    }
+    snake_case
+}
+
+/// Creates the name of a symbol, naming the auxiliary symbols after the EBNF constructs
+/// they are generated for (e.g., `Statement*` to `statement_star`).
+fn symbol_name(grammar: &Grammar, symbol: &Symbol) -> String {
+    let name = symbol
+        .split(|char: char| !char.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(snake_case)
+        .join("_");
+    match grammar.auxiliary_symbols().get(symbol) {
+        Some(EbnfConstruct::Optional) => format!("{}_opt", name),
+        Some(EbnfConstruct::ZeroOrMore) => format!("{}_star", name),
+        Some(EbnfConstruct::OneOrMore) => format!("{}_plus", name),
+        Some(EbnfConstruct::Group) => format!("{}_group", name),
+        None => name,
+    }
+}
+
+/// Creates the name of a constant token (e.g., `'if'` to `IF`, `'=='` to `EQUAL_EQUAL`).
+fn constant_token_name(constant_token: &ConstantToken) -> String {
+    let mut parts = Vec::new();
+    let mut word = String::new();
+    for char in constant_token.chars() {
+        if char.is_ascii_alphanumeric() {
+            word.push(char.to_ascii_uppercase());
+            continue;
+        }
+        if !word.is_empty() {
+            parts.push(std::mem::take(&mut word));
+        }
+        let name = match char {
+            '+' => "PLUS",
+            '-' => "MINUS",
+            '*' => "STAR",
+            '/' => "SLASH",
+            '%' => "PERCENT",
+            '=' => "EQUAL",
+            '<' => "LESS",
+            '>' => "GREATER",
+            '!' => "BANG",
+            '&' => "AMPERSAND",
+            '|' => "PIPE",
+            '^' => "CARET",
+            '~' => "TILDE",
+            '(' => "LPAREN",
+            ')' => "RPAREN",
+            '[' => "LBRACKET",
+            ']' => "RBRACKET",
+            '{' => "LBRACE",
+            '}' => "RBRACE",
+            ',' => "COMMA",
+            '.' => "DOT",
+            ':' => "COLON",
+            ';' => "SEMICOLON",
+            '?' => "QUESTION",
+            '@' => "AT",
+            '#' => "HASH",
+            '$' => "DOLLAR",
+            '\'' => "QUOTE",
+            '"' => "DOUBLE_QUOTE",
+            _ => continue,
+        };
+        parts.push(name.to_owned());
+    }
+    if !word.is_empty() {
+        parts.push(word);
+    }
+    if parts.is_empty() { "TOKEN".to_owned() } else { parts.join("_") }
+}
+
+/// Creates the bison character literal of a constant token, if it's a single printable character.
+fn bison_character(constant_token: &ConstantToken) -> Option<String> {
+    let mut chars = constant_token.chars();
+    match (chars.next(), chars.next()) {
+        (Some(char @ ('\'' | '\\')), None) => Some(format!("'\\{}'", char)),
+        (Some(char), None) if char.is_ascii_graphic() || char == ' ' => Some(format!("'{}'", char)),
+        _ => None,
+    }
+}
+
+/// Creates the C string literal of a constant token.
+fn c_string(constant_token: &ConstantToken) -> String {
+    let mut string = String::from('"');
+    for char in constant_token.chars() {
+        match char {
+            '"' => string.push_str("\\\""),
+            '\\' => string.push_str("\\\\"),
+            '\n' => string.push_str("\\n"),
+            '\r' => string.push_str("\\r"),
+            '\t' => string.push_str("\\t"),
+            char if char.is_ascii_control() => string.push_str(&format!("\\x{:02x}", char as u32)),
+            char => string.push(char),
+        }
+    }
+    string.push('"');
+    string
+}
+
+/// Creates the Python string literal of a constant token, which Lark uses for its strings.
+fn python_string(constant_token: &ConstantToken) -> String {
+    // C escapes used in string literals of constant tokens are the same in Python.
+    c_string(constant_token)
+}
+
+/// Creates the JavaScript string literal of a constant token.
+fn javascript_string(constant_token: &ConstantToken) -> String {
+    let mut string = String::from('\'');
+    for char in constant_token.chars() {
+        match char {
+            '\'' => string.push_str("\\'"),
+            '\\' => string.push_str("\\\\"),
+            '\n' => string.push_str("\\n"),
+            '\r' => string.push_str("\\r"),
+            '\t' => string.push_str("\\t"),
+            char if char.is_control() => string.push_str(&format!("\\u{{{:x}}}", char as u32)),
+            char => string.push(char),
+        }
+    }
+    string.push('\'');
+    string
+}
+
+/// Creates the regex matching a case insensitive constant token (e.g., `'if'` to `[iI][fF]`).
+fn case_insensitive_regex(constant_token: &ConstantToken) -> String {
+    constant_token
+        .chars()
+        .map(|char| {
+            if char.to_lowercase().eq(char.to_uppercase()) {
+                regex::escape(&char.to_string()).replace('/', "\\/")
+            } else {
+                format!("[{}{}]", char.to_lowercase(), char.to_uppercase())
+            }
+        })
+        .collect()
+}
+
+/// Translates a regex to the syntax of Lark or tree-sitter regex literals,
+/// together with the constructs in the regex that cannot be expressed in the format.
+fn translate_regex(regex: &Regex, format: ExportFormat) -> (String, Vec<SmolStr>) {
+    // Regexes are anchored at the start of the input, which is implicit in the formats.
+    let regex = regex.as_str().strip_prefix('^').unwrap_or(regex.as_str());
+    let chars = regex.chars().collect_vec();
+
+    let mut translated = String::with_capacity(regex.len());
+    let mut constructs = IndexSet::<SmolStr>::new();
+
+    let mut class_depth = 0;
+    let mut index = 0;
+    while index < chars.len() {
+        let char = chars[index];
+        let next = chars.get(index + 1).copied();
+        match char {
+            '\\' => {
+                match (next, format) {
+                    (Some('p' | 'P'), ExportFormat::Lark) => {
+                        constructs.insert("unicode classes".into());
+                    },
+                    (Some('z'), ExportFormat::Lark) => {
+                        translated.push_str("\\Z");
+                        index += 2;
+                        continue;
+                    },
+                    (Some('A' | 'z'), ExportFormat::TreeSitter) => {
+                        constructs.insert("text anchors".into());
+                    },
+                    _ => {},
+                }
+                translated.push('\\');
+                translated.extend(next);
+                index += 2;
+                continue;
+            },
+            '/' => translated.push_str("\\/"),
+            '[' if class_depth == 0 => {
+                class_depth += 1;
+                translated.push('[');
+                if next == Some('^') {
+                    translated.push('^');
+                    index += 1;
+                }
+                // Closing bracket at the start of a class is literal, except in JavaScript.
+                if chars.get(index + 1) == Some(&']') {
+                    translated
+                        .push_str(if format == ExportFormat::TreeSitter { "\\]" } else { "]" });
+                    index += 1;
+                }
+            },
+            '[' => {
+                class_depth += 1;
+                if next == Some(':') {
+                    constructs.insert("POSIX classes".into());
+                } else {
+                    constructs.insert("nested classes".into());
+                }
+                translated.push('[');
+            },
+            ']' if class_depth > 0 => {
+                class_depth -= 1;
+                translated.push(']');
+            },
+            '&' | '-' | '~' if class_depth > 0 && next == Some(char) => {
+                constructs.insert("class set operations".into());
+                translated.push(char);
+            },
+            '(' if class_depth == 0 && next == Some('?') => {
+                let rest = chars[index + 2..].iter().collect::<String>();
+                if rest.starts_with("P<") {
+                    // Named groups are written as `(?P<name>...)` in Python,
+                    // and as `(?<name>...)` in JavaScript.
+                    match format {
+                        ExportFormat::TreeSitter => translated.push_str("(?<"),
+                        _ => translated.push_str("(?P<"),
+                    }
+                    index += 4;
+                    continue;
+                }
+                if rest.starts_with('<') {
+                    match format {
+                        ExportFormat::Lark => translated.push_str("(?P<"),
+                        _ => translated.push_str("(?<"),
+                    }
+                    index += 3;
+                    continue;
+                }
+                if !rest.starts_with(':') {
+                    let flags = rest.chars().take_while(|char| *char != ':' && *char != ')');
+                    let flags = flags.collect::<String>();
+                    match format {
+                        ExportFormat::TreeSitter => {
+                            constructs.insert("inline flags".into());
+                        },
+                        _ => {
+                            for flag in flags.chars().filter(|flag| matches!(flag, 'U' | 'R')) {
+                                constructs.insert(format_smolstr!("inline flag {}", flag));
+                            }
+                            let is_global = rest[flags.len()..].starts_with(')');
+                            if is_global && index > 0 {
+                                constructs.insert("inline flags after the start".into());
+                            }
+                        },
+                    }
+                }
+                translated.push('(');
+            },
+            char => translated.push(char),
+        }
+        index += 1;
+    }
+
+    (translated, constructs.into_iter().collect())
+}
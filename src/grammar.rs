@@ -2,7 +2,7 @@ use crate::prelude::*;
 
 
 /// Symbol (e.g., `S`, `E`) in a grammar.
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Symbol(SmolStr);
@@ -29,7 +29,7 @@ impl<T: Into<SmolStr>> From<T> for Symbol {
 
 
 /// Constant token (e.g., `'+'`, `'-'`) in a grammar.
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct ConstantToken(SmolStr);
@@ -44,7 +44,7 @@ impl Deref for ConstantToken {
 
 impl Display for ConstantToken {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "'{}'", self.0)
+        write!(f, "'{}'", utils::escape_constant_token(&self.0))
     }
 }
 
@@ -56,7 +56,7 @@ impl<T: Into<SmolStr>> From<T> for ConstantToken {
 
 
 /// Regular expression token (e.g., `%f`, `%s`) in a grammar.
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct RegexToken(SmolStr);
@@ -83,7 +83,7 @@ impl<T: Into<SmolStr>> From<T> for RegexToken {
 
 
 /// Token (e.g., `'+'`, `%f`, `$`) in a grammar.
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
 #[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -126,7 +126,7 @@ impl From<RegexToken> for Token {
 
 
 /// Elements (e.g., `E`, `'+'`, `%f`) of the pattern of a rule.
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
 #[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -172,12 +172,16 @@ impl From<RegexToken> for AtomicPattern {
 
 
 /// Rule (e.g., `S -> E` `E -> F '+' E`) of a grammar.
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct Rule {
-    symbol: Symbol,
-    pattern: SmallVec<[AtomicPattern; 3]>,
+    pub(crate) symbol: Symbol,
+    pub(crate) pattern: SmallVec<[AtomicPattern; 3]>,
+    pub(crate) labels: SmallVec<[Option<SmolStr>; 3]>,
+    pub(crate) precedence: Option<Token>,
+    pub(crate) span: Option<Span>,
+    pub(crate) pattern_spans: SmallVec<[Option<Span>; 3]>,
 }
 
 impl Rule {
@@ -186,7 +190,10 @@ impl Rule {
         symbol: impl Into<Symbol>,
         pattern: impl IntoIterator<Item = AtomicPattern>,
     ) -> Rule {
-        Rule { symbol: symbol.into(), pattern: pattern.into_iter().collect() }
+        let pattern: SmallVec<[AtomicPattern; 3]> = pattern.into_iter().collect();
+        let labels = smallvec![None; pattern.len()];
+        let pattern_spans = smallvec![None; pattern.len()];
+        Rule { symbol: symbol.into(), pattern, labels, precedence: None, span: None, pattern_spans }
     }
 }
 
@@ -200,38 +207,253 @@ impl Rule {
     pub fn pattern(&self) -> &[AtomicPattern] {
         &self.pattern
     }
+
+    /// Gets the labels (e.g., `lhs` in `lhs:E`) of the elements of the pattern of the rule.
+    pub fn labels(&self) -> &[Option<SmolStr>] {
+        &self.labels
+    }
+
+    /// Gets the index of the element of the pattern of the rule with a label.
+    pub fn index_of(&self, label: &str) -> Option<usize> {
+        self.labels.iter().position(|candidate| candidate.as_deref() == Some(label))
+    }
+
+    /// Gets the token whose precedence is explicitly assigned to the rule with `%prec`.
+    pub fn precedence(&self) -> Option<&Token> {
+        self.precedence.as_ref()
+    }
+
+    /// Gets the span of the rule in the grammar string, or `None` if it's not parsed from one.
+    ///
+    /// Rules generated for EBNF constructs span the construct, and rules instantiated from
+    /// parameterized rules span the parameterized rule.
+    pub fn span(&self) -> Option<&Span> {
+        self.span.as_ref()
+    }
+
+    /// Gets the spans of the elements of the pattern of the rule in the grammar string.
+    pub fn pattern_spans(&self) -> &[Option<Span>] {
+        &self.pattern_spans
+    }
+}
+
+// Spans are not compared, so rules created with `Rule::new` are equal to the parsed ones.
+impl PartialEq for Rule {
+    fn eq(&self, other: &Rule) -> bool {
+        self.symbol == other.symbol
+            && self.pattern == other.pattern
+            && self.labels == other.labels
+            && self.precedence == other.precedence
+    }
 }
 
+impl Eq for Rule {}
+
 impl Display for Rule {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{} ->", self.symbol)?;
-        for atomic_pattern in self.pattern.iter() {
-            write!(f, " {}", atomic_pattern)?;
+        if self.pattern.is_empty() {
+            write!(f, " ε")?;
+        }
+        for (atomic_pattern, label) in self.pattern.iter().zip(self.labels.iter()) {
+            match label {
+                Some(label) => write!(f, " {}:{}", label, atomic_pattern)?,
+                None => write!(f, " {}", atomic_pattern)?,
+            }
+        }
+        if let Some(precedence) = &self.precedence {
+            write!(f, " %prec {}", precedence)?;
         }
         Ok(())
     }
 }
 
 
+/// EBNF construct (e.g., `X?`, `X*`, `X+`, `(X Y)`) an auxiliary symbol is generated for.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EbnfConstruct {
+    /// Optional element (e.g., `X?`).
+    Optional,
+    /// Element repeated zero or more times (e.g., `X*`).
+    ZeroOrMore,
+    /// Element repeated one or more times (e.g., `X+`).
+    OneOrMore,
+    /// Group of elements (e.g., `(X Y)`).
+    Group,
+}
+
+impl Display for EbnfConstruct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EbnfConstruct::Optional => write!(f, "?"),
+            EbnfConstruct::ZeroOrMore => write!(f, "*"),
+            EbnfConstruct::OneOrMore => write!(f, "+"),
+            EbnfConstruct::Group => write!(f, "()"),
+        }
+    }
+}
+
+
+/// Associativity (e.g., `%left`, `%right`, `%nonassoc`) of tokens with the same precedence.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Associativity {
+    /// Left associative (e.g., `a - b - c` is `(a - b) - c`).
+    Left,
+    /// Right associative (e.g., `a ^ b ^ c` is `a ^ (b ^ c)`).
+    Right,
+    /// Non associative (e.g., `a < b < c` is an error).
+    NonAssoc,
+}
+
+impl Display for Associativity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Associativity::Left => write!(f, "%left"),
+            Associativity::Right => write!(f, "%right"),
+            Associativity::NonAssoc => write!(f, "%nonassoc"),
+        }
+    }
+}
+
+
+/// Transition (e.g., `%push string`, `%pop`) between lexer modes that is triggered by a token.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ModeTransition {
+    /// Enters a mode, which is left when it's popped.
+    Push(SmolStr),
+    /// Leaves the current mode, returning to the mode it's pushed from.
+    Pop,
+    /// Replaces the current mode with another mode.
+    Switch(SmolStr),
+}
+
+impl Display for ModeTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModeTransition::Push(mode) => write!(f, "%push {}", mode),
+            ModeTransition::Pop => write!(f, "%pop"),
+            ModeTransition::Switch(mode) => write!(f, "%switch {}", mode),
+        }
+    }
+}
+
+
+/// Precedence (e.g., `%left '+' '-'`) of a token in a grammar.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Precedence {
+    level: usize,
+    associativity: Associativity,
+}
+
+impl Precedence {
+    /// Creates a new precedence.
+    pub fn new(level: usize, associativity: Associativity) -> Precedence {
+        Precedence { level, associativity }
+    }
+}
+
+impl Precedence {
+    /// Gets the level of the precedence, tokens declared later have higher levels.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Gets the associativity of the precedence.
+    pub fn associativity(&self) -> Associativity {
+        self.associativity
+    }
+}
+
+
 /// Grammar of a language.
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
 #[derive(Clone, Debug)]
 pub struct Grammar {
     symbols: IndexSet<Symbol>,
     start_symbol: Symbol,
+    entry_symbols: IndexSet<Symbol>,
+    entry_symbol_spans: IndexMap<Symbol, Span>,
     constant_tokens: IndexSet<ConstantToken>,
-    #[cfg_attr(feature = "serde", serde(serialize_with = "utils::serialize_regex_map"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "utils::serialize_regex_map",
+            deserialize_with = "utils::deserialize_regex_map",
+        )
+    )]
     regular_expressions: IndexMap<RegexToken, Regex>,
+    regex_spans: IndexMap<RegexToken, Span>,
+    priorities: IndexMap<RegexToken, usize>,
+    keywords: IndexSet<ConstantToken>,
+    case_insensitive: bool,
+    case_insensitive_tokens: IndexSet<ConstantToken>,
+    modes: IndexMap<SmolStr, IndexSet<Token>>,
+    transitions: IndexMap<SmolStr, IndexMap<Token, ModeTransition>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "utils::serialize_regex_vec",
+            deserialize_with = "utils::deserialize_regex_vec",
+        )
+    )]
+    ignored_regular_expressions: Vec<Regex>,
     rules: Vec<Rule>,
+    parameterized_rules: Vec<grammar_parser::ParameterizedRule>,
+    auxiliary_symbols: IndexMap<Symbol, EbnfConstruct>,
+    instantiations: IndexMap<Symbol, Symbol>,
+    precedences: IndexMap<Token, Precedence>,
 }
 
 
+impl Grammar {
+    /// Name of the lexer mode the tokenization starts in.
+    pub const DEFAULT_MODE: &'static str = "default";
+}
+
 impl Grammar {
     /// Creates a grammar from a grammar string.
     pub fn parse(grammar_string: &str) -> Result<Grammar, GrammarError> {
-        grammar_parser::parse(grammar_string)
+        grammar_parser::parse(grammar_string, &mut grammar_parser::NoResolver)
+    }
+
+    /// Creates a grammar from a grammar string, resolving its imports with a resolver.
+    pub fn parse_with_resolver(
+        grammar_string: &str,
+        resolver: &mut impl GrammarResolver,
+    ) -> Result<Grammar, GrammarError> {
+        grammar_parser::parse(grammar_string, resolver)
+    }
+
+    /// Creates a grammar from a grammar string, reporting all of its errors instead of the first one.
+    pub fn parse_all_errors(grammar_string: &str) -> Result<Grammar, Vec<GrammarError>> {
+        grammar_parser::parse_all_errors(grammar_string, &mut grammar_parser::NoResolver)
+    }
+
+    /// Creates a grammar from a grammar string, resolving its imports with a resolver and reporting
+    /// all of its errors instead of the first one.
+    pub fn parse_all_errors_with_resolver(
+        grammar_string: &str,
+        resolver: &mut impl GrammarResolver,
+    ) -> Result<Grammar, Vec<GrammarError>> {
+        grammar_parser::parse_all_errors(grammar_string, resolver)
+    }
+
+    /// Formats a grammar string, keeping its comments unlike the display of a grammar.
+    ///
+    /// Formatting a formatted grammar string doesn't change it.
+    pub fn format(grammar_string: &str) -> Result<String, GrammarError> {
+        grammar_parser::format(grammar_string)
     }
 }
 
@@ -244,6 +466,18 @@ impl Grammar {
             Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
         }
     }
+    pub fn parse_all_errors_wasm(grammar_string: &str) -> Result<Grammar, JsValue> {
+        match Grammar::parse_all_errors(grammar_string) {
+            Ok(grammar) => Ok(grammar),
+            Err(errors) => Err(serde_wasm_bindgen::to_value(&errors)?),
+        }
+    }
+    pub fn format_wasm(grammar_string: &str) -> Result<String, JsValue> {
+        match Grammar::format(grammar_string) {
+            Ok(formatted) => Ok(formatted),
+            Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
+        }
+    }
 }
 
 
@@ -258,6 +492,16 @@ impl Grammar {
         &self.start_symbol
     }
 
+    /// Gets the entry symbols of the grammar, the first of which is the start symbol.
+    pub fn entry_symbols(&self) -> &IndexSet<Symbol> {
+        &self.entry_symbols
+    }
+
+    /// Gets the spans of the entry symbols declared with `%start` in the grammar string.
+    pub fn entry_symbol_spans(&self) -> &IndexMap<Symbol, Span> {
+        &self.entry_symbol_spans
+    }
+
     /// Gets the constant tokens of the grammar.
     pub fn constant_tokens(&self) -> &IndexSet<ConstantToken> {
         &self.constant_tokens
@@ -268,10 +512,173 @@ impl Grammar {
         &self.regular_expressions
     }
 
+    /// Gets the spans of the definitions of the regular expressions in the grammar string.
+    pub fn regex_spans(&self) -> &IndexMap<RegexToken, Span> {
+        &self.regex_spans
+    }
+
+    /// Gets the priorities of the regular expressions declared with `%prio` in the grammar.
+    pub fn priorities(&self) -> &IndexMap<RegexToken, usize> {
+        &self.priorities
+    }
+
+    /// Gets the priority of a regular expression token, which is `0` unless it's declared otherwise.
+    pub fn priority_of(&self, regex_token: &RegexToken) -> usize {
+        self.priorities.get(regex_token).copied().unwrap_or(0)
+    }
+
+    /// Gets the keywords of the grammar, which are reserved from matching regular expressions.
+    pub fn keywords(&self) -> &IndexSet<ConstantToken> {
+        &self.keywords
+    }
+
+    /// Gets whether all constant tokens of the grammar are case insensitive (i.e., `%caseless`).
+    pub fn case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    /// Gets the constant tokens that are declared case insensitive (e.g., `'select'i`).
+    pub fn case_insensitive_tokens(&self) -> &IndexSet<ConstantToken> {
+        &self.case_insensitive_tokens
+    }
+
+    /// Gets the lexer modes declared with `%mode` in the grammar, and the tokens they recognize.
+    pub fn modes(&self) -> &IndexMap<SmolStr, IndexSet<Token>> {
+        &self.modes
+    }
+
+    /// Gets the transitions between lexer modes, for the tokens that trigger them in each mode.
+    pub fn transitions(&self) -> &IndexMap<SmolStr, IndexMap<Token, ModeTransition>> {
+        &self.transitions
+    }
+
+    /// Gets the regular expressions that are skipped between tokens during tokenization.
+    ///
+    /// If it's empty, whitespace is skipped instead.
+    pub fn ignored_regular_expressions(&self) -> &[Regex] {
+        &self.ignored_regular_expressions
+    }
+
     /// Gets the rules of the grammar.
     pub fn rules(&self) -> &[Rule] {
         &self.rules
     }
+
+    /// Gets the auxiliary symbols generated for the EBNF constructs in the grammar.
+    pub fn auxiliary_symbols(&self) -> &IndexMap<Symbol, EbnfConstruct> {
+        &self.auxiliary_symbols
+    }
+
+    /// Gets the patterns and labels of the alternatives of the construct of an auxiliary symbol.
+    pub(crate) fn construct_alternatives_internal(
+        &self,
+        symbol: &Symbol,
+        construct: EbnfConstruct,
+    ) -> Vec<(&[AtomicPattern], &[Option<SmolStr>])> {
+        let rules = self.rules().iter().filter(|rule| rule.symbol() == symbol);
+        match construct {
+            // X? -> X
+            // X? -> ε
+            EbnfConstruct::Optional => {
+                rules
+                    .filter(|rule| !rule.pattern().is_empty())
+                    .map(|rule| (rule.pattern(), rule.labels()))
+                    .collect()
+            },
+            // X* -> X* X
+            // X+ -> X+ X
+            EbnfConstruct::ZeroOrMore | EbnfConstruct::OneOrMore => {
+                let recursion = AtomicPattern::Symbol(symbol.clone());
+                rules
+                    .filter(|rule| rule.pattern().first() == Some(&recursion))
+                    .map(|rule| (&rule.pattern()[1..], &rule.labels()[1..]))
+                    .collect()
+            },
+            // (X | Y) -> X
+            // (X | Y) -> Y
+            EbnfConstruct::Group => rules.map(|rule| (rule.pattern(), rule.labels())).collect(),
+        }
+    }
+
+    /// Gets the symbols instantiated from parameterized rules, mapped to their parameterized symbols.
+    pub fn instantiations(&self) -> &IndexMap<Symbol, Symbol> {
+        &self.instantiations
+    }
+
+    /// Gets the precedences of the tokens in the grammar.
+    pub fn precedences(&self) -> &IndexMap<Token, Precedence> {
+        &self.precedences
+    }
+}
+
+impl Grammar {
+    /// Checks whether a token is recognized in a lexer mode during tokenization.
+    ///
+    /// Tokens that are not declared in any mode are recognized in the default mode.
+    pub fn recognizes(&self, mode: &str, token: &Token) -> bool {
+        if self.modes.is_empty() {
+            return true;
+        }
+        if self.modes.get(mode).map(|tokens| tokens.contains(token)).unwrap_or(false) {
+            return true;
+        }
+        mode == Grammar::DEFAULT_MODE && !self.modes.values().any(|tokens| tokens.contains(token))
+    }
+
+    /// Checks whether a constant token is matched case insensitively during tokenization.
+    pub fn is_case_insensitive(&self, constant_token: &ConstantToken) -> bool {
+        self.case_insensitive || self.case_insensitive_tokens.contains(constant_token)
+    }
+
+    /// Gets the transition between lexer modes that is triggered by a token in a mode.
+    pub fn transition_of(&self, mode: &str, token: &Token) -> Option<&ModeTransition> {
+        self.transitions.get(mode).and_then(|transitions| transitions.get(token))
+    }
+}
+
+impl Grammar {
+    /// Gets the precedence of a rule, which is the precedence of its `%prec` token
+    /// if it has one, or the precedence of the last token in its pattern otherwise.
+    pub fn precedence_of(&self, rule: &Rule) -> Option<Precedence> {
+        let token = match rule.precedence() {
+            Some(token) => Some(token),
+            None => {
+                rule.pattern().iter().rev().find_map(|atomic_pattern| {
+                    match atomic_pattern {
+                        AtomicPattern::Token(token) => Some(token),
+                        AtomicPattern::Symbol(_) => None,
+                    }
+                })
+            },
+        };
+        token.and_then(|token| self.precedences.get(token)).copied()
+    }
+}
+
+impl Grammar {
+    /// Internal creation of a grammar with the same declarations but different rules.
+    pub(crate) fn with_rules_internal(
+        &self,
+        rules: Vec<Rule>,
+        entry_symbols: IndexSet<Symbol>,
+        auxiliary_symbols: IndexMap<Symbol, EbnfConstruct>,
+        instantiations: IndexMap<Symbol, Symbol>,
+    ) -> Grammar {
+        let symbols = rules.iter().map(|rule| rule.symbol().clone()).collect();
+        let start_symbol = entry_symbols.first().cloned().unwrap_or(self.start_symbol.clone());
+        let mut entry_symbol_spans = self.entry_symbol_spans.clone();
+        entry_symbol_spans.retain(|symbol, _| entry_symbols.contains(symbol));
+        Grammar {
+            symbols,
+            start_symbol,
+            entry_symbols,
+            entry_symbol_spans,
+            rules,
+            auxiliary_symbols,
+            instantiations,
+            ..self.clone()
+        }
+    }
 }
 
 #[cfg(feature = "wasm")]
@@ -283,6 +690,9 @@ impl Grammar {
     pub fn start_symbol_wasm(&self) -> Result<JsValue, JsValue> {
         Ok(serde_wasm_bindgen::to_value(&self.start_symbol)?)
     }
+    pub fn entry_symbols_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.entry_symbols)?)
+    }
     pub fn rules_wasm(&self) -> Result<JsValue, JsValue> {
         Ok(serde_wasm_bindgen::to_value(&self.rules)?)
     }
@@ -298,6 +708,41 @@ impl Grammar {
             self.regular_expressions.iter().map(|(k, v)| (k.clone(), v.to_string())).collect();
         Ok(serde_wasm_bindgen::to_value(&index_map)?)
     }
+    pub fn priorities_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.priorities)?)
+    }
+    pub fn keywords_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.keywords)?)
+    }
+    pub fn case_insensitive_wasm(&self) -> bool {
+        self.case_insensitive
+    }
+    pub fn case_insensitive_tokens_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.case_insensitive_tokens)?)
+    }
+    pub fn modes_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.modes)?)
+    }
+    pub fn transitions_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.transitions)?)
+    }
+    pub fn ignored_regular_expressions_wasm(&self) -> Result<JsValue, JsValue> {
+        let regexes: Vec<String> =
+            self.ignored_regular_expressions.iter().map(|regex| regex.to_string()).collect();
+        Ok(serde_wasm_bindgen::to_value(&regexes)?)
+    }
+    pub fn auxiliary_symbols_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.auxiliary_symbols)?)
+    }
+    pub fn instantiations_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.instantiations)?)
+    }
+    pub fn precedences_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.precedences)?)
+    }
+    pub fn lint_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.lint())?)
+    }
     pub fn clone_wasm(&self) -> Grammar {
         self.clone()
     }
@@ -306,20 +751,202 @@ impl Grammar {
 
 impl Display for Grammar {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Entry symbols are only declared if they are not just the symbol of the first rule.
+        let implicit_entry_symbol = self.rules.first().map(|rule| rule.symbol());
+        let declare_entry_symbols =
+            self.entry_symbols.len() > 1 || self.entry_symbols.first() != implicit_entry_symbol;
+        if declare_entry_symbols {
+            writeln!(f, "%start {}", self.entry_symbols.iter().join(" "))?;
+        }
+
+        let declarations = self.precedences.iter().chunk_by(|(_, precedence)| **precedence);
+        for (precedence, tokens) in declarations.into_iter() {
+            write!(f, "{}", precedence.associativity())?;
+            for (token, _) in tokens {
+                write!(f, " {}", token)?;
+            }
+            writeln!(f)?;
+        }
+        if declare_entry_symbols || !self.precedences.is_empty() {
+            writeln!(f)?;
+        }
+
+        let mut previous_symbol = None;
         for rule in self.rules.iter() {
-            writeln!(f, "{}", rule)?;
+            // Rules of auxiliary symbols are generated from the EBNF constructs in other rules,
+            // and rules of instantiated symbols are generated from the parameterized rules.
+            if self.auxiliary_symbols.contains_key(rule.symbol())
+                || self.instantiations.contains_key(rule.symbol())
+            {
+                continue;
+            }
+
+            // Alternate form groups consecutive rules of the same symbol (e.g., `E -> x\n  | y`).
+            if f.alternate() && previous_symbol == Some(rule.symbol()) {
+                let indentation = rule.symbol().chars().count() + 1;
+                let rule_string = rule.to_string();
+                let pattern = &rule_string[rule.symbol().len() + " ->".len()..];
+                writeln!(f, "{:indentation$}|{}", "", pattern)?;
+            } else {
+                writeln!(f, "{}", rule)?;
+            }
+            previous_symbol = Some(rule.symbol());
+        }
+        for parameterized_rule in self.parameterized_rules.iter() {
+            writeln!(f, "{}", parameterized_rule)?;
         }
         if !self.regular_expressions.is_empty() {
             writeln!(f)?;
         }
         for (regex_token, regex) in self.regular_expressions.iter() {
-            writeln!(f, "{} -> /{}/", regex_token, regex)?;
+            write!(f, "{} -> /{}/", regex_token, utils::escape_regex(regex.as_str()))?;
+            if let Some(priority) = self.priorities.get(regex_token) {
+                write!(f, " %prio {}", priority)?;
+            }
+            writeln!(f)?;
+        }
+        let declares_case_insensitivity =
+            self.case_insensitive || !self.case_insensitive_tokens.is_empty();
+        if !self.keywords.is_empty() || declares_case_insensitivity {
+            writeln!(f)?;
+        }
+        if !self.keywords.is_empty() {
+            writeln!(f, "%keyword {}", self.keywords.iter().join(" "))?;
+        }
+        if self.case_insensitive {
+            writeln!(f, "%caseless")?;
+        } else if !self.case_insensitive_tokens.is_empty() {
+            writeln!(f, "%caseless {}", self.case_insensitive_tokens.iter().join(" "))?;
+        }
+        if !self.modes.is_empty() || !self.transitions.is_empty() {
+            writeln!(f)?;
+        }
+        for (mode, tokens) in self.modes.iter() {
+            writeln!(f, "%mode {} {}", mode, tokens.iter().join(" "))?;
+        }
+        for (mode, transitions) in self.transitions.iter() {
+            for (token, transition) in transitions.iter() {
+                writeln!(f, "%on {} {} {}", mode, token, transition)?;
+            }
+        }
+        if !self.ignored_regular_expressions.is_empty() {
+            writeln!(f)?;
+        }
+        for regex in self.ignored_regular_expressions.iter() {
+            writeln!(f, "%ignore /{}/", utils::escape_regex(regex.as_str()))?;
         }
         Ok(())
     }
 }
 
 
+/// Builder of grammars that are constructed programmatically instead of from grammar strings.
+#[derive(Clone, Debug, Default)]
+pub struct GrammarBuilder {
+    start_symbol: Option<Symbol>,
+    symbols: IndexSet<Symbol>,
+    constant_tokens: IndexSet<ConstantToken>,
+    regular_expressions: IndexMap<RegexToken, Regex>,
+    rules: Vec<Rule>,
+}
+
+impl GrammarBuilder {
+    /// Creates an empty grammar builder.
+    pub fn new() -> GrammarBuilder {
+        GrammarBuilder::default()
+    }
+}
+
+impl GrammarBuilder {
+    /// Adds a rule (e.g., `E -> E '+' F`) to the grammar.
+    ///
+    /// Constant tokens in the pattern of the rule are added to the grammar as well.
+    pub fn rule(mut self, rule: Rule) -> GrammarBuilder {
+        self.symbols.insert(rule.symbol().clone());
+        for atomic_pattern in rule.pattern() {
+            if let AtomicPattern::Token(Token::Constant(constant_token)) = atomic_pattern {
+                self.constant_tokens.insert(constant_token.clone());
+            }
+        }
+        self.rules.push(rule);
+        self
+    }
+
+    /// Adds a constant token (e.g., `'+'`) to the grammar.
+    pub fn constant_token(mut self, constant_token: impl Into<ConstantToken>) -> GrammarBuilder {
+        self.constant_tokens.insert(constant_token.into());
+        self
+    }
+
+    /// Adds a regular expression token (e.g., `%f -> /[0-9]+/`) to the grammar.
+    pub fn regex_token(
+        mut self,
+        regex_token: impl Into<RegexToken>,
+        regex: &str,
+    ) -> Result<GrammarBuilder, GrammarBuilderError> {
+        let regex_token = regex_token.into();
+        match grammar_parser::anchored_regex(regex) {
+            Ok(compiled_regex) => {
+                self.regular_expressions.insert(regex_token, compiled_regex);
+                Ok(self)
+            },
+            Err(error) => {
+                Err(GrammarBuilderError::InvalidRegex {
+                    regex_token,
+                    regex: regex.into(),
+                    reason: format_smolstr!("{}", error),
+                })
+            },
+        }
+    }
+
+    /// Sets the start symbol of the grammar, which is the symbol of the first rule otherwise.
+    pub fn start_symbol(mut self, symbol: impl Into<Symbol>) -> GrammarBuilder {
+        self.start_symbol = Some(symbol.into());
+        self
+    }
+}
+
+impl GrammarBuilder {
+    /// Builds the grammar, checking that every symbol and regex token it uses is defined.
+    ///
+    /// All undefined symbols, regex tokens and start symbols are reported, as in `Parser::check`.
+    pub fn build(self) -> Result<Grammar, Vec<ParserError>> {
+        let start_symbol =
+            self.start_symbol.or_else(|| self.rules.first().map(|rule| rule.symbol().clone()));
+        let (start_symbol, entry_symbols) = match start_symbol {
+            Some(start_symbol) => (start_symbol.clone(), IndexSet::from([start_symbol])),
+            None => (Symbol::from(""), IndexSet::new()),
+        };
+
+        let grammar = Grammar {
+            symbols: self.symbols,
+            start_symbol,
+            entry_symbols,
+            entry_symbol_spans: IndexMap::new(),
+            constant_tokens: self.constant_tokens,
+            regular_expressions: self.regular_expressions,
+            regex_spans: IndexMap::new(),
+            priorities: IndexMap::new(),
+            keywords: IndexSet::new(),
+            case_insensitive: false,
+            case_insensitive_tokens: IndexSet::new(),
+            modes: IndexMap::new(),
+            transitions: IndexMap::new(),
+            ignored_regular_expressions: vec![],
+            rules: self.rules,
+            parameterized_rules: vec![],
+            auxiliary_symbols: IndexMap::new(),
+            instantiations: IndexMap::new(),
+            precedences: IndexMap::new(),
+        };
+
+        let errors = Parser::undefined_errors_internal(&grammar);
+        if errors.is_empty() { Ok(grammar) } else { Err(errors) }
+    }
+}
+
+
 /// Internal module to parse grammar strings.
 mod grammar_parser {
     use crate::prelude::*;
@@ -332,22 +959,134 @@ mod grammar_parser {
         #[regex("[a-zA-Z0-9]+", |lexer| Symbol::from(lexer.slice()))]
         Symbol(Symbol),
 
+        /// List(, Delimited(, ...
+        #[regex(r"[a-zA-Z0-9]+\(", |lexer| Symbol::from(lexer.slice().trim_end_matches('(')))]
+        ParameterizedSymbol(Symbol),
+
         /// ->
         #[token("->")]
         Arrow,
 
-        /// '+', '-', ',', ...
-        #[regex(r#"'([^'])*'"#, |lexer| ConstantToken::from(lexer.slice().trim_matches('\'')))]
+        /// ε, ''
+        #[token("ε")]
+        #[token("''")]
+        Empty,
+
+        /// '+', '-', ',', '\'', ...
+        #[regex(r#"'([^'\\\n]|\\[^\n])+'"#, |lexer| {
+            let slice = lexer.slice();
+            utils::unescape_constant_token(&slice[1..slice.len() - 1]).map(ConstantToken::from)
+        })]
         ConstantToken(ConstantToken),
 
+        /// 'select'i, 'from'i, ...
+        #[regex(r#"'([^'\\\n]|\\[^\n])+'i"#, |lexer| {
+            let slice = lexer.slice();
+            utils::unescape_constant_token(&slice[1..slice.len() - 2]).map(ConstantToken::from)
+        })]
+        CaseInsensitiveConstantToken(ConstantToken),
+
         /// %d, %f, %s, ...
         #[regex("%[a-zA-Z0-9]+", |lexer| RegexToken::from(&lexer.slice()[1..]))]
         RegexToken(RegexToken),
 
-        /// /\[0-9]+/, /\[a-z]+/, ...
-        #[regex(r#"/([^/])*/"#, |lexer| SmolStr::from(lexer.slice().trim_matches('/')))]
+        /// lhs:, op:, ...
+        #[regex("[a-zA-Z0-9_]+:", |lexer| SmolStr::from(lexer.slice().trim_end_matches(':')))]
+        Label(SmolStr),
+
+        /// %left, %right, %nonassoc
+        #[token("%left", |_| Associativity::Left)]
+        #[token("%right", |_| Associativity::Right)]
+        #[token("%nonassoc", |_| Associativity::NonAssoc)]
+        PrecedenceDeclaration(Associativity),
+
+        /// %prec
+        #[token("%prec")]
+        Prec,
+
+        /// %start
+        #[token("%start")]
+        Start,
+
+        /// %ignore
+        #[token("%ignore")]
+        Ignore,
+
+        /// %import
+        #[token("%import")]
+        Import,
+
+        /// %keyword
+        #[token("%keyword")]
+        Keyword,
+
+        /// %prio
+        #[token("%prio")]
+        Prio,
+
+        /// %caseless
+        #[token("%caseless")]
+        Caseless,
+
+        /// %mode
+        #[token("%mode")]
+        Mode,
+
+        /// %on
+        #[token("%on")]
+        On,
+
+        /// %push
+        #[token("%push")]
+        Push,
+
+        /// %pop
+        #[token("%pop")]
+        Pop,
+
+        /// %switch
+        #[token("%switch")]
+        Switch,
+
+        /// "expressions.lr", "../common/tokens.lr", ...
+        #[regex(r#""([^"\n])*""#, |lexer| SmolStr::from(lexer.slice().trim_matches('"')))]
+        ImportPath(SmolStr),
+
+        /// /\[0-9]+/, /\[a-z]+/, /\[^\/]+/, ...
+        #[regex(r#"/([^/\\\n]|\\[^\n])*/"#, |lexer| {
+            let slice = lexer.slice();
+            utils::unescape_regex(&slice[1..slice.len() - 1])
+        })]
         Regex(SmolStr),
 
+        /// ?
+        #[token("?")]
+        QuestionMark,
+
+        /// \*
+        #[token("*")]
+        Asterisk,
+
+        /// \+
+        #[token("+")]
+        Plus,
+
+        /// (
+        #[token("(")]
+        OpeningParenthesis,
+
+        /// )
+        #[token(")")]
+        ClosingParenthesis,
+
+        /// |
+        #[token("|")]
+        Pipe,
+
+        /// ,
+        #[token(",")]
+        Comma,
+
         /// \n
         #[token("\n")]
         NewLine,
@@ -357,32 +1096,176 @@ mod grammar_parser {
         Comment,
     }
 
+    /// Element (e.g., `E`, `'+'`, `E*`, `('+' | '-')`, `lhs:E`, `List(E, ',')`) of a pattern before it's desugared.
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+    #[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+    #[derive(Clone, Debug)]
+    enum Element {
+        Atomic(AtomicPattern, Span),
+        Construct { construct: EbnfConstruct, alternatives: Vec<Vec<Element>>, span: Span },
+        Labeled { label: SmolStr, element: Box<Element>, span: Span },
+        Application { symbol: Symbol, arguments: Vec<Element>, span: Span },
+    }
+
+    impl Element {
+        /// Labels the element if there is a label.
+        fn labeled(self, label: Option<(SmolStr, Span)>) -> Element {
+            match label {
+                Some((label, label_span)) => {
+                    let span = label_span.join(self.span());
+                    Element::Labeled { label, element: Box::new(self), span }
+                },
+                None => self,
+            }
+        }
+
+        /// Gets the span of the element in the grammar string.
+        fn span(&self) -> &Span {
+            match self {
+                Element::Atomic(_, span)
+                | Element::Construct { span, .. }
+                | Element::Labeled { span, .. }
+                | Element::Application { span, .. } => span,
+            }
+        }
+    }
+
+    impl Display for Element {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Element::Atomic(atomic_pattern, _) => write!(f, "{}", atomic_pattern),
+                Element::Construct { construct: EbnfConstruct::Group, alternatives, .. } => {
+                    write!(
+                        f,
+                        "({})",
+                        alternatives
+                            .iter()
+                            .map(|alternative| alternative.iter().join(" "))
+                            .join(" | "),
+                    )
+                },
+                Element::Construct { construct, alternatives, .. } => {
+                    write!(f, "{}{}", alternatives[0][0], construct)
+                },
+                Element::Labeled { label, element, .. } => write!(f, "{}:{}", label, element),
+                Element::Application { symbol, arguments, .. } => {
+                    write!(f, "{}({})", symbol, arguments.iter().join(", "))
+                },
+            }
+        }
+    }
+
+    /// Group (e.g., `('+' | '-')`) or arguments of an application (e.g., `List(E, ',')`) that is being parsed.
+    #[derive(Debug)]
+    struct OpenGroup {
+        enclosing_pattern: Vec<Element>,
+        label: Option<(SmolStr, Span)>,
+        alternatives: Vec<Vec<Element>>,
+        application: Option<Symbol>,
+        span: Span,
+    }
+
     #[derive(Debug)]
     #[allow(clippy::enum_variant_names)]
     enum GrammarParsingState {
         AwaitingSymbolOrRegexToken,
 
-        AwaitingArrowForRule { symbol: Symbol },
-        AwaitingAtomicPatterns { symbol: Symbol, pattern: SmallVec<[AtomicPattern; 3]> },
-
-        AwaitingArrowForRegex { regex_token: RegexToken },
-        AwaitingRegex { regex_token: RegexToken },
+        AwaitingArrowForRule {
+            symbol: Symbol,
+        },
+        AwaitingParameter {
+            symbol: Symbol,
+            parameters: Vec<Symbol>,
+        },
+        AwaitingParameterSeparator {
+            symbol: Symbol,
+            parameters: Vec<Symbol>,
+        },
+        AwaitingAtomicPatterns {
+            symbol: Symbol,
+            pattern: Vec<Element>,
+            open_groups: Vec<OpenGroup>,
+            label: Option<(SmolStr, Span)>,
+            precedence: Option<Token>,
+        },
+        AwaitingPrecedenceTokenOfRule {
+            symbol: Symbol,
+            pattern: Vec<Element>,
+        },
+        AwaitingNewLineAfterEmptyPattern {
+            symbol: Symbol,
+        },
+
+        AwaitingArrowForRegex {
+            regex_token: RegexToken,
+        },
+        AwaitingRegex {
+            regex_token: RegexToken,
+        },
+        AwaitingPriorityOfRegex {
+            regex_token: RegexToken,
+        },
+        AwaitingPriority {
+            regex_token: RegexToken,
+        },
+        AwaitingIgnoredRegex,
+
+        AwaitingImportPath,
+
+        AwaitingKeywords {
+            keywords: Vec<ConstantToken>,
+        },
+
+        AwaitingCaseInsensitiveTokens {
+            tokens: Vec<ConstantToken>,
+        },
+
+        AwaitingModeName,
+        AwaitingModeTokens {
+            mode: SmolStr,
+            tokens: Vec<Token>,
+        },
+
+        AwaitingModeOfTransition,
+        AwaitingTokenOfTransition {
+            mode: SmolStr,
+        },
+        AwaitingTransition {
+            mode: SmolStr,
+            token: Token,
+        },
+        AwaitingTargetModeOfTransition {
+            mode: SmolStr,
+            token: Token,
+            switch: bool,
+        },
+
+        AwaitingPrecedenceTokens {
+            associativity: Associativity,
+            tokens: Vec<Token>,
+        },
+
+        AwaitingEntrySymbols {
+            symbols: Vec<Symbol>,
+        },
     }
 
     impl GrammarParsingState {
-        pub fn unexpected_token(&self, lexer: Lexer<GrammarToken>) -> GrammarError {
+        pub fn unexpected_token(&self, lexer: &Lexer<GrammarToken>) -> GrammarError {
             let (line, column) = lexer.extras;
+            let span = span_of(lexer);
             let slice = lexer.slice();
             let token = if slice == "\n" { "\\n".into() } else { slice.into() };
-            let expected = match self.unexpected_eof() {
-                GrammarError::UnexpectedEof { expected } => expected,
+            let expected = match self.unexpected_eof(span.clone()) {
+                GrammarError::UnexpectedEof { expected, .. } => expected,
                 _ => unreachable!(),
             };
 
-            GrammarError::UnexpectedToken { line, column, token, expected }
+            GrammarError::UnexpectedToken { line, column, span, token, expected }
         }
 
-        pub fn unexpected_eof(&self) -> GrammarError {
+        pub fn unexpected_eof(&self, span: Span) -> GrammarError {
             let mut expected = SmallVec::new();
             match self {
                 GrammarParsingState::AwaitingSymbolOrRegexToken => {
@@ -394,44 +1277,721 @@ mod grammar_parser {
                 | GrammarParsingState::AwaitingArrowForRegex { .. } => {
                     expected.push("'->'".into());
                 },
+                GrammarParsingState::AwaitingParameter { .. } => {
+                    expected.push("symbol".into());
+                },
+                GrammarParsingState::AwaitingParameterSeparator { .. } => {
+                    expected.push("','".into());
+                    expected.push("')'".into());
+                },
 
-                GrammarParsingState::AwaitingAtomicPatterns { .. } => {
+                GrammarParsingState::AwaitingAtomicPatterns { precedence: Some(_), .. } => {
+                    expected.push("new line".into());
+                },
+                GrammarParsingState::AwaitingAtomicPatterns { label: Some(_), .. } => {
                     expected.push("symbol".into());
                     expected.push("constant token".into());
                     expected.push("regular expression token".into());
                 },
-                GrammarParsingState::AwaitingRegex { .. } => {
+                GrammarParsingState::AwaitingAtomicPatterns { open_groups, .. } => {
+                    expected.push("symbol".into());
+                    expected.push("constant token".into());
+                    expected.push("regular expression token".into());
+                    if !open_groups.is_empty() {
+                        expected.push("')'".into());
+                    }
+                },
+                GrammarParsingState::AwaitingNewLineAfterEmptyPattern { .. } => {
+                    expected.push("new line".into());
+                },
+                GrammarParsingState::AwaitingPrecedenceTokenOfRule { .. }
+                | GrammarParsingState::AwaitingPrecedenceTokens { .. } => {
+                    expected.push("constant token".into());
+                    expected.push("regular expression token".into());
+                },
+                GrammarParsingState::AwaitingRegex { .. }
+                | GrammarParsingState::AwaitingIgnoredRegex => {
                     expected.push("regular expression".into());
                 },
+                GrammarParsingState::AwaitingEntrySymbols { .. } => {
+                    expected.push("symbol".into());
+                },
+                GrammarParsingState::AwaitingPriorityOfRegex { .. } => {
+                    expected.push("'%prio'".into());
+                    expected.push("new line".into());
+                },
+                GrammarParsingState::AwaitingPriority { .. } => {
+                    expected.push("priority".into());
+                },
+                GrammarParsingState::AwaitingImportPath => {
+                    expected.push("import path".into());
+                },
+                GrammarParsingState::AwaitingKeywords { .. } => {
+                    expected.push("constant token".into());
+                },
+                GrammarParsingState::AwaitingCaseInsensitiveTokens { .. } => {
+                    expected.push("constant token".into());
+                    expected.push("new line".into());
+                },
+                GrammarParsingState::AwaitingModeName
+                | GrammarParsingState::AwaitingModeOfTransition
+                | GrammarParsingState::AwaitingTargetModeOfTransition { .. } => {
+                    expected.push("mode".into());
+                },
+                GrammarParsingState::AwaitingModeTokens { .. }
+                | GrammarParsingState::AwaitingTokenOfTransition { .. } => {
+                    expected.push("constant token".into());
+                    expected.push("regular expression token".into());
+                },
+                GrammarParsingState::AwaitingTransition { .. } => {
+                    expected.push("'%push'".into());
+                    expected.push("'%pop'".into());
+                    expected.push("'%switch'".into());
+                },
             };
 
-            GrammarError::UnexpectedEof { expected }
+            GrammarError::UnexpectedEof { span, expected }
         }
 
-        pub fn unexpected_regex(&self, lexer: Lexer<GrammarToken>) -> GrammarError {
+        pub fn unexpected_regex(&self, lexer: &Lexer<GrammarToken>) -> GrammarError {
             let (line, column) = lexer.extras;
+            let span = span_of(lexer);
             let slice = lexer.slice();
             let regex = slice.into();
 
-            GrammarError::InvalidRegex { line, column, regex }
+            GrammarError::InvalidRegex { line, column, span, regex }
         }
     }
 
-    pub fn parse(grammar_string: &str) -> Result<Grammar, GrammarError> {
+    /// Gets the span of the current token of the lexer.
+    fn span_of(lexer: &Lexer<GrammarToken>) -> Span {
+        let (line, column) = lexer.extras;
+        Span { offset: lexer.span().start, len: lexer.span().len(), line, column, source: None }
+    }
+
+    /// Extends the span of the definition that is being parsed until the end of a token.
+    fn extend_span(definition_span: &mut Option<Span>, token_span: &Span) {
+        *definition_span = Some(match definition_span.take() {
+            Some(span) => span.join(token_span),
+            None => token_span.clone(),
+        });
+    }
+
+    /// Records the imported grammar a span is in, unless it's in a grammar imported by it.
+    fn record_source(span: &mut Span, source: &SmolStr) {
+        if span.source.is_none() {
+            span.source = Some(source.clone());
+        }
+    }
+
+    /// Records the imported grammar the spans of an element are in.
+    fn record_element_source(element: &mut Element, source: &SmolStr) {
+        match element {
+            Element::Atomic(_, span) => {
+                record_source(span, source);
+            },
+            Element::Construct { alternatives, span, .. } => {
+                for element in alternatives.iter_mut().flatten() {
+                    record_element_source(element, source);
+                }
+                record_source(span, source);
+            },
+            Element::Labeled { element, span, .. } => {
+                record_element_source(element, source);
+                record_source(span, source);
+            },
+            Element::Application { arguments, span, .. } => {
+                for argument in arguments.iter_mut() {
+                    record_element_source(argument, source);
+                }
+                record_source(span, source);
+            },
+        }
+    }
+
+    /// Records the imported grammar the spans of the definitions of an imported grammar are in.
+    fn record_grammar_source(grammar: &mut Grammar, source: &SmolStr) {
+        for rule in grammar.rules.iter_mut() {
+            for span in rule.span.iter_mut().chain(rule.pattern_spans.iter_mut().flatten()) {
+                record_source(span, source);
+            }
+        }
+        for span in grammar.regex_spans.values_mut() {
+            record_source(span, source);
+        }
+        for parameterized_rule in grammar.parameterized_rules.iter_mut() {
+            for element in parameterized_rule.pattern.iter_mut() {
+                record_element_source(element, source);
+            }
+            if let Some(span) = &mut parameterized_rule.span {
+                record_source(span, source);
+            }
+        }
+    }
+
+    /// Reports an error in the imported grammar its span is in, if it's in one.
+    fn in_source(error: GrammarError, span: &Span) -> GrammarError {
+        match &span.source {
+            Some(source) => {
+                GrammarError::InImportedGrammar { grammar: source.clone(), error: Box::new(error) }
+            },
+            None => error,
+        }
+    }
+
+    /// Rule (e.g., `List(X, Sep) -> X (Sep X)*`) with parameters that is instantiated for its arguments.
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+    #[derive(Clone, Debug)]
+    pub struct ParameterizedRule {
+        symbol: Symbol,
+        parameters: Vec<Symbol>,
+        pattern: Vec<Element>,
+        precedence: Option<Token>,
+        span: Option<Span>,
+    }
+
+    impl Display for ParameterizedRule {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}({}) ->", self.symbol, self.parameters.iter().join(", "))?;
+            if self.pattern.is_empty() {
+                write!(f, " ε")?;
+            }
+            for element in self.pattern.iter() {
+                write!(f, " {}", element)?;
+            }
+            if let Some(precedence) = &self.precedence {
+                write!(f, " %prec {}", precedence)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Definition of rules in a grammar, in the order they are defined.
+    #[derive(Debug)]
+    enum Definition {
+        Rule {
+            symbol: Symbol,
+            pattern: Vec<Element>,
+            precedence: Option<Token>,
+            span: Option<Span>,
+        },
+        Imported {
+            rules: Vec<Rule>,
+        },
+    }
+
+    /// Defines a rule, or a parameterized rule if the rule is defined with parameters.
+    fn define_rule(
+        symbol: Symbol,
+        pattern: Vec<Element>,
+        precedence: Option<Token>,
+        span: Option<Span>,
+        parameters: &Option<Vec<Symbol>>,
+        definitions: &mut Vec<Definition>,
+        parameterized_rules: &mut Vec<ParameterizedRule>,
+    ) {
+        match parameters {
+            Some(parameters) => {
+                let parameters = parameters.clone();
+                parameterized_rules.push(ParameterizedRule {
+                    symbol,
+                    parameters,
+                    pattern,
+                    precedence,
+                    span,
+                });
+            },
+            None => {
+                definitions.push(Definition::Rule { symbol, pattern, precedence, span });
+            },
+        }
+    }
+
+    /// Substitutes the parameters of a parameterized rule in an element with their arguments.
+    fn substitute(element: Element, parameters: &[Symbol], arguments: &[Element]) -> Element {
+        match element {
+            Element::Atomic(AtomicPattern::Symbol(symbol), span) => {
+                match parameters.iter().position(|parameter| *parameter == symbol) {
+                    Some(index) => arguments[index].clone(),
+                    None => Element::Atomic(AtomicPattern::Symbol(symbol), span),
+                }
+            },
+            Element::Atomic(..) => element,
+            Element::Construct { construct, alternatives, span } => {
+                Element::Construct {
+                    construct,
+                    alternatives: alternatives
+                        .into_iter()
+                        .map(|alternative| {
+                            alternative
+                                .into_iter()
+                                .map(|element| substitute(element, parameters, arguments))
+                                .collect()
+                        })
+                        .collect(),
+                    span,
+                }
+            },
+            Element::Labeled { label, element, span } => {
+                Element::Labeled {
+                    label,
+                    element: Box::new(substitute(*element, parameters, arguments)),
+                    span,
+                }
+            },
+            Element::Application { symbol, arguments: application_arguments, span } => {
+                Element::Application {
+                    symbol,
+                    arguments: application_arguments
+                        .into_iter()
+                        .map(|element| substitute(element, parameters, arguments))
+                        .collect(),
+                    span,
+                }
+            },
+        }
+    }
+
+    /// Atomic patterns of a desugared pattern, alongside their labels and spans.
+    type DesugaredPattern =
+        (SmallVec<[AtomicPattern; 3]>, SmallVec<[Option<SmolStr>; 3]>, SmallVec<[Option<Span>; 3]>);
+
+    /// Desugarer of the patterns of rules into atomic patterns.
+    struct Desugarer<'g> {
+        symbols: &'g mut IndexSet<Symbol>,
+        auxiliary_symbols: &'g mut IndexMap<Symbol, EbnfConstruct>,
+        parameterized_rules: &'g [ParameterizedRule],
+        instantiations: &'g mut IndexMap<Symbol, Symbol>,
+        instantiating: Vec<Symbol>,
+    }
+
+    impl Desugarer<'_> {
+        /// Maximum number of nested instantiations of the same parameterized symbol.
+        const MAXIMUM_INSTANTIATION_DEPTH: usize = 32;
+
+        /// Desugars an element into an atomic pattern, generating auxiliary rules when necessary.
+        fn desugar(
+            &mut self,
+            element: Element,
+            auxiliary_rules: &mut Vec<Rule>,
+        ) -> Result<AtomicPattern, GrammarError> {
+            let span = element.span().clone();
+            let (construct, alternatives) = match element {
+                Element::Atomic(atomic_pattern, _) => return Ok(atomic_pattern),
+                Element::Labeled { element, .. } => {
+                    return self.desugar(*element, auxiliary_rules);
+                },
+                Element::Application { .. } => {
+                    return self.instantiate(element, auxiliary_rules);
+                },
+                Element::Construct { construct, ref alternatives, .. } => {
+                    (construct, alternatives.clone())
+                },
+            };
+            let alternatives = match alternatives.as_slice() {
+                // Repeated groups don't need a separate auxiliary symbol for the group.
+                [alternative] if construct != EbnfConstruct::Group => {
+                    match alternative.as_slice() {
+                        [
+                            Element::Construct {
+                                construct: EbnfConstruct::Group,
+                                alternatives,
+                                ..
+                            },
+                        ] => alternatives.clone(),
+                        _ => alternatives,
+                    }
+                },
+                _ => alternatives,
+            };
+
+            let symbol = Symbol::from(format_smolstr!("{}", element));
+            if self.auxiliary_symbols.contains_key(&symbol) {
+                return Ok(AtomicPattern::Symbol(symbol));
+            }
+
+            let alternatives = alternatives
+                .into_iter()
+                .map(|alternative| self.desugar_pattern(alternative, auxiliary_rules))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            self.symbols.insert(symbol.clone());
+            self.auxiliary_symbols.insert(symbol.clone(), construct);
+
+            let recursive_alternatives = || {
+                alternatives.iter().map(|(pattern, labels, pattern_spans)| {
+                    let pattern = std::iter::once(AtomicPattern::Symbol(symbol.clone()))
+                        .chain(pattern.iter().cloned())
+                        .collect::<SmallVec<[AtomicPattern; 3]>>();
+                    let labels = std::iter::once(None)
+                        .chain(labels.iter().cloned())
+                        .collect::<SmallVec<[Option<SmolStr>; 3]>>();
+                    let pattern_spans = std::iter::once(Some(span.clone()))
+                        .chain(pattern_spans.iter().cloned())
+                        .collect::<SmallVec<[Option<Span>; 3]>>();
+                    (pattern, labels, pattern_spans)
+                })
+            };
+            let empty = || (smallvec![], smallvec![], smallvec![]);
+            let patterns: Vec<_> = match construct {
+                // X? -> X
+                // X? -> ε
+                EbnfConstruct::Optional => {
+                    alternatives.iter().cloned().chain(std::iter::once(empty())).collect()
+                },
+                // X* -> X* X
+                // X* -> ε
+                EbnfConstruct::ZeroOrMore => {
+                    recursive_alternatives().chain(std::iter::once(empty())).collect()
+                },
+                // X+ -> X+ X
+                // X+ -> X
+                EbnfConstruct::OneOrMore => {
+                    recursive_alternatives().chain(alternatives.iter().cloned()).collect()
+                },
+                // (X | Y) -> X
+                // (X | Y) -> Y
+                EbnfConstruct::Group => alternatives.clone(),
+            };
+            for (pattern, labels, pattern_spans) in patterns {
+                auxiliary_rules.push(Rule {
+                    symbol: symbol.clone(),
+                    pattern,
+                    labels,
+                    precedence: None,
+                    span: Some(span.clone()),
+                    pattern_spans,
+                });
+            }
+
+            Ok(AtomicPattern::Symbol(symbol))
+        }
+
+        /// Instantiates a parameterized rule for the arguments of an application into a symbol.
+        fn instantiate(
+            &mut self,
+            application: Element,
+            auxiliary_rules: &mut Vec<Rule>,
+        ) -> Result<AtomicPattern, GrammarError> {
+            let symbol = Symbol::from(format_smolstr!("{}", application));
+            if self.instantiations.contains_key(&symbol) {
+                return Ok(AtomicPattern::Symbol(symbol));
+            }
+
+            let (parameterized_symbol, arguments, span) = match application {
+                Element::Application { symbol, arguments, span } => (symbol, arguments, span),
+                _ => unreachable!(),
+            };
+
+            let parameterized_rules: &[ParameterizedRule] = self.parameterized_rules;
+            let parameterized_rules = parameterized_rules
+                .iter()
+                .filter(|parameterized_rule| parameterized_rule.symbol == parameterized_symbol)
+                .collect::<Vec<_>>();
+            if parameterized_rules.is_empty() {
+                let error = GrammarError::UndefinedParameterizedSymbol {
+                    line: span.line,
+                    column: span.column,
+                    span: span.clone(),
+                    symbol: parameterized_symbol,
+                };
+                return Err(in_source(error, &span));
+            }
+            let expected = parameterized_rules[0].parameters.len();
+            if arguments.len() != expected {
+                let error = GrammarError::ArgumentCountMismatch {
+                    line: span.line,
+                    column: span.column,
+                    span: span.clone(),
+                    symbol: parameterized_symbol,
+                    expected,
+                    got: arguments.len(),
+                };
+                return Err(in_source(error, &span));
+            }
+
+            // Applications with growing arguments (e.g., `Nested(X) -> Nested('-' X)`) never terminate.
+            let depth = self
+                .instantiating
+                .iter()
+                .filter(|instantiating| **instantiating == parameterized_symbol)
+                .count();
+            if depth == Self::MAXIMUM_INSTANTIATION_DEPTH {
+                let error = GrammarError::UnboundedInstantiation {
+                    line: span.line,
+                    column: span.column,
+                    span: span.clone(),
+                    symbol: parameterized_symbol,
+                };
+                return Err(in_source(error, &span));
+            }
+
+            // Instantiation is registered before its rules, so recursive applications refer to it.
+            self.symbols.insert(symbol.clone());
+            self.instantiations.insert(symbol.clone(), parameterized_symbol.clone());
+            self.instantiating.push(parameterized_symbol);
+
+            for parameterized_rule in parameterized_rules {
+                let pattern = parameterized_rule
+                    .pattern
+                    .iter()
+                    .cloned()
+                    .map(|element| substitute(element, &parameterized_rule.parameters, &arguments))
+                    .collect();
+
+                let mut nested_auxiliary_rules = Vec::new();
+                let (pattern, labels, pattern_spans) =
+                    self.desugar_pattern(pattern, &mut nested_auxiliary_rules)?;
+
+                auxiliary_rules.push(Rule {
+                    symbol: symbol.clone(),
+                    pattern,
+                    labels,
+                    precedence: parameterized_rule.precedence.clone(),
+                    span: parameterized_rule.span.clone(),
+                    pattern_spans,
+                });
+                auxiliary_rules.extend(nested_auxiliary_rules);
+            }
+            self.instantiating.pop();
+
+            Ok(AtomicPattern::Symbol(symbol))
+        }
+
+        /// Desugars the elements of a pattern into atomic patterns, and their labels and spans.
+        fn desugar_pattern(
+            &mut self,
+            pattern: Vec<Element>,
+            auxiliary_rules: &mut Vec<Rule>,
+        ) -> Result<DesugaredPattern, GrammarError> {
+            let mut atomic_patterns = SmallVec::new();
+            let mut labels = SmallVec::new();
+            let mut pattern_spans = SmallVec::new();
+            for element in pattern {
+                labels.push(match &element {
+                    Element::Labeled { label, .. } => Some(label.clone()),
+                    _ => None,
+                });
+                pattern_spans.push(Some(element.span().clone()));
+                atomic_patterns.push(self.desugar(element, auxiliary_rules)?);
+            }
+            Ok((atomic_patterns, labels, pattern_spans))
+        }
+
+        /// Desugars the pattern of a rule and adds it to the rules alongside its auxiliary rules.
+        fn add_rule(
+            &mut self,
+            symbol: Symbol,
+            pattern: Vec<Element>,
+            precedence: Option<Token>,
+            span: Option<Span>,
+            rules: &mut Vec<Rule>,
+        ) -> Result<(), GrammarError> {
+            let mut auxiliary_rules = Vec::new();
+            let (pattern, labels, pattern_spans) =
+                self.desugar_pattern(pattern, &mut auxiliary_rules)?;
+
+            rules.push(Rule { symbol, pattern, labels, precedence, span, pattern_spans });
+            rules.extend(auxiliary_rules);
+
+            Ok(())
+        }
+    }
+
+    /// Creates a regex that only matches at the start of the input.
+    ///
+    /// Regexes that are already anchored (e.g., displayed regexes) are not anchored again.
+    pub fn anchored_regex(regex_string: &str) -> Result<Regex, regex::Error> {
+        if regex_string.starts_with('^') {
+            Regex::new(regex_string)
+        } else {
+            Regex::new(format_smolstr!("^{}", regex_string).as_str())
+        }
+    }
+
+    /// Declares the precedence of tokens, overriding their previous declarations.
+    fn declare_precedences(
+        tokens: Vec<Token>,
+        precedence: Precedence,
+        precedences: &mut IndexMap<Token, Precedence>,
+    ) {
+        for token in tokens {
+            precedences.shift_remove(&token);
+            precedences.insert(token, precedence);
+        }
+    }
+
+    /// Resolver of the grammars parsed without a resolver.
+    pub struct NoResolver;
+
+    impl GrammarResolver for NoResolver {
+        fn resolve(&mut self, _: &str, _: Option<&str>) -> Result<(SmolStr, String), SmolStr> {
+            Err("imports can only be resolved with a resolver".into())
+        }
+    }
+
+    /// Imports of the grammar that is being parsed.
+    #[derive(Debug, Default)]
+    struct Imports {
+        stack: Vec<SmolStr>,
+        imported: IndexSet<SmolStr>,
+    }
+
+    pub fn parse(
+        grammar_string: &str,
+        resolver: &mut dyn GrammarResolver,
+    ) -> Result<Grammar, GrammarError> {
+        parse_all_errors(grammar_string, resolver)
+            .map_err(|errors| errors.into_iter().next().unwrap())
+    }
+
+    /// Parses a grammar string, collecting the errors instead of stopping at the first one.
+    ///
+    /// After an error, the rest of the line is skipped and parsing continues from the next line.
+    pub fn parse_all_errors(
+        grammar_string: &str,
+        resolver: &mut dyn GrammarResolver,
+    ) -> Result<Grammar, Vec<GrammarError>> {
+        let mut errors = Vec::new();
+        let grammar =
+            parse_internal(grammar_string, resolver, &mut Imports::default(), &mut errors);
+        if errors.is_empty() { Ok(grammar) } else { Err(errors) }
+    }
+
+    /// Formats a grammar string, keeping its comments and the blank lines between its definitions.
+    ///
+    /// Tokens on a line are separated by a single space, apart from the ones inside parentheses
+    /// and the ones attached to the previous token (e.g., `*`, `,`), and the `|` of a rule that
+    /// continues on a new line is aligned with the `->` of the rule.
+    pub fn format(grammar_string: &str) -> Result<String, GrammarError> {
+        let mut lexer = GrammarToken::lexer(grammar_string);
+        let mut lines = vec![Vec::new()];
+
+        let mut line = 1;
+        let mut column = 1;
+        let mut column_start_position = 0;
+        while let Some(token) = lexer.next() {
+            let span = lexer.span();
+            column += grammar_string[column_start_position..span.start].chars().count();
+            column_start_position = span.start;
+            lexer.extras = (line, column);
+
+            match token {
+                Ok(GrammarToken::NewLine) => {
+                    lines.push(Vec::new());
+                    line += 1;
+                    column = 1;
+                    column_start_position = span.end;
+                },
+                Ok(token) => {
+                    lines.last_mut().unwrap().push((token, lexer.slice().trim_end()));
+                },
+                Err(_) => {
+                    return Err(
+                        GrammarParsingState::AwaitingSymbolOrRegexToken.unexpected_token(&lexer)
+                    );
+                },
+            }
+        }
+
+        let mut formatted = String::new();
+        let mut arrow_column = 0;
+        let mut separated = false;
+        for (index, tokens) in lines.iter().enumerate() {
+            if tokens.is_empty() {
+                // Consecutive blank lines are merged, and leading and trailing ones are dropped.
+                separated = !formatted.is_empty();
+                continue;
+            }
+            if separated {
+                formatted.push('\n');
+                separated = false;
+            }
+
+            let is_continuation = |tokens: &[(GrammarToken, &str)]| {
+                matches!(tokens.first(), Some((GrammarToken::Pipe, _)))
+            };
+            let is_comment = matches!(tokens.as_slice(), [(GrammarToken::Comment, _)]);
+            let next_is_continuation =
+                lines.get(index + 1).is_some_and(|next_tokens| is_continuation(next_tokens));
+            if is_continuation(tokens) || (is_comment && next_is_continuation) {
+                formatted.push_str(&" ".repeat(arrow_column));
+            }
+
+            let line_start = formatted.len();
+            for (position, (token, slice)) in tokens.iter().enumerate() {
+                if position > 0 {
+                    let is_attached = matches!(
+                        tokens[position - 1].0,
+                        GrammarToken::ParameterizedSymbol(_)
+                            | GrammarToken::Label(_)
+                            | GrammarToken::OpeningParenthesis,
+                    ) || matches!(
+                        token,
+                        GrammarToken::ClosingParenthesis
+                            | GrammarToken::Comma
+                            | GrammarToken::QuestionMark
+                            | GrammarToken::Asterisk
+                            | GrammarToken::Plus,
+                    );
+                    if !is_attached {
+                        formatted.push(' ');
+                    }
+                }
+                if *token == GrammarToken::Arrow {
+                    arrow_column = formatted[line_start..].chars().count();
+                }
+                formatted.push_str(slice);
+            }
+            formatted.push('\n');
+        }
+
+        Ok(formatted)
+    }
+
+    fn parse_internal(
+        grammar_string: &str,
+        resolver: &mut dyn GrammarResolver,
+        imports: &mut Imports,
+        errors: &mut Vec<GrammarError>,
+    ) -> Grammar {
         let mut lexer = GrammarToken::lexer(grammar_string);
         let mut state = GrammarParsingState::AwaitingSymbolOrRegexToken;
 
         let mut symbols = IndexSet::new();
         let mut start_symbol = None;
+        let mut imported_start_symbol = None;
+        let mut entry_symbols = IndexSet::new();
+        let mut entry_symbol_spans = IndexMap::new();
         let mut constant_tokens = IndexSet::new();
         let mut regular_expressions = IndexMap::new();
-        let mut rules = Vec::new();
+        let mut regex_spans = IndexMap::new();
+        let mut priorities = IndexMap::new();
+        let mut keywords = IndexSet::new();
+        let mut case_insensitive = false;
+        let mut case_insensitive_tokens = IndexSet::new();
+        let mut modes: IndexMap<SmolStr, IndexSet<Token>> = IndexMap::new();
+        let mut transitions: IndexMap<SmolStr, IndexMap<Token, ModeTransition>> = IndexMap::new();
+        let mut referenced_modes = Vec::new();
+        let mut ignored_regular_expressions = Vec::new();
+        let mut definitions = Vec::new();
+        let mut parameterized_rules: Vec<ParameterizedRule> = Vec::new();
+        let mut auxiliary_symbols = IndexMap::new();
+        let mut instantiations = IndexMap::new();
+
+        let mut last_rule_symbol = None;
+        let mut parameters = None;
+        let mut definition_span = None;
+        let mut precedences = IndexMap::new();
+        let mut precedence_level = 0;
 
         let mut line = 1;
         let mut column = 1;
         lexer.extras = (line, column);
 
         let mut column_start_position = 0;
+        let mut recovering = false;
         while let Some(token) = lexer.next() {
             let span = lexer.span();
 
@@ -440,142 +2000,1088 @@ mod grammar_parser {
 
             column += remaining_line_slice.chars().count();
             lexer.extras = (line, column);
+            let token_span = span_of(&lexer);
 
             let token = match token {
                 Ok(token) => token,
-                Err(_) => return Err(state.unexpected_token(lexer)),
-            };
-
-            let newline = token == GrammarToken::NewLine;
-            match &mut state {
-                GrammarParsingState::AwaitingSymbolOrRegexToken => {
-                    match token {
-                        GrammarToken::NewLine | GrammarToken::Comment => {},
-                        GrammarToken::Symbol(symbol) => {
-                            if start_symbol.is_none() {
-                                start_symbol = Some(symbol.clone());
-                            }
-                            symbols.insert(symbol.clone());
-                            state = GrammarParsingState::AwaitingArrowForRule { symbol };
-                        },
-                        GrammarToken::RegexToken(regex_token) => {
-                            state = GrammarParsingState::AwaitingArrowForRegex { regex_token };
-                        },
-                        _ => {
-                            return Err(state.unexpected_token(lexer));
-                        },
-                    }
-                },
-
-                GrammarParsingState::AwaitingArrowForRule { symbol } => {
-                    match token {
-                        GrammarToken::Arrow => {
-                            state = GrammarParsingState::AwaitingAtomicPatterns {
-                                symbol: symbol.clone(),
-                                pattern: smallvec![],
-                            };
-                        },
-                        _ => {
-                            return Err(state.unexpected_token(lexer));
-                        },
-                    }
-                },
-                GrammarParsingState::AwaitingArrowForRegex { regex_token } => {
-                    match token {
-                        GrammarToken::Arrow => {
-                            state = GrammarParsingState::AwaitingRegex {
-                                regex_token: regex_token.clone(),
-                            };
-                        },
-                        _ => {
-                            return Err(state.unexpected_token(lexer));
-                        },
+                Err(_) => {
+                    if !recovering {
+                        errors.push(state.unexpected_token(&lexer));
+                        state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                        definition_span = None;
+                        recovering = true;
                     }
+                    continue;
                 },
+            };
 
-                GrammarParsingState::AwaitingAtomicPatterns { symbol, pattern } => {
-                    match token {
-                        GrammarToken::Comment => {},
-                        GrammarToken::Symbol(symbol) => {
-                            pattern.push(AtomicPattern::Symbol(symbol));
-                        },
-                        GrammarToken::ConstantToken(constant_token) => {
-                            constant_tokens.insert(constant_token.clone());
-                            pattern.push(AtomicPattern::Token(Token::Constant(constant_token)));
-                        },
-                        GrammarToken::RegexToken(regex_token) => {
-                            pattern.push(AtomicPattern::Token(Token::Regex(regex_token)));
-                        },
-                        GrammarToken::NewLine => {
-                            if pattern.is_empty() {
-                                return Err(state.unexpected_token(lexer));
-                            }
-
-                            let rule = Rule {
-                                symbol: std::mem::replace(symbol, Symbol::from("")),
-                                pattern: std::mem::take(pattern),
-                            };
-                            rules.push(rule);
-
-                            state = GrammarParsingState::AwaitingSymbolOrRegexToken;
-                        },
-
-                        _ => {
-                            return Err(state.unexpected_token(lexer));
-                        },
-                    }
-                },
-                GrammarParsingState::AwaitingRegex { regex_token } => {
-                    match token {
-                        GrammarToken::Regex(regex_string) => {
-                            let regex =
-                                match Regex::new(format_smolstr!("^{}", regex_string).as_str()) {
+            let newline = token == GrammarToken::NewLine;
+            if recovering && !newline {
+                continue;
+            }
+            recovering = false;
+
+            let result = 'token: {
+                match &mut state {
+                    GrammarParsingState::AwaitingSymbolOrRegexToken => {
+                        match token {
+                            GrammarToken::NewLine | GrammarToken::Comment => {},
+                            GrammarToken::Symbol(symbol) => {
+                                if start_symbol.is_none() {
+                                    start_symbol = Some(symbol.clone());
+                                }
+                                symbols.insert(symbol.clone());
+                                last_rule_symbol = Some(symbol.clone());
+                                parameters = None;
+                                definition_span = Some(token_span);
+                                state = GrammarParsingState::AwaitingArrowForRule { symbol };
+                            },
+                            GrammarToken::ParameterizedSymbol(symbol) => {
+                                last_rule_symbol = None;
+                                definition_span = Some(token_span);
+                                state = GrammarParsingState::AwaitingParameter {
+                                    symbol,
+                                    parameters: vec![],
+                                };
+                            },
+                            GrammarToken::RegexToken(regex_token) => {
+                                last_rule_symbol = None;
+                                definition_span = Some(token_span);
+                                state = GrammarParsingState::AwaitingArrowForRegex { regex_token };
+                            },
+                            GrammarToken::Start => {
+                                last_rule_symbol = None;
+                                state =
+                                    GrammarParsingState::AwaitingEntrySymbols { symbols: vec![] };
+                            },
+                            GrammarToken::Ignore => {
+                                last_rule_symbol = None;
+                                state = GrammarParsingState::AwaitingIgnoredRegex;
+                            },
+                            GrammarToken::Import => {
+                                last_rule_symbol = None;
+                                state = GrammarParsingState::AwaitingImportPath;
+                            },
+                            GrammarToken::Keyword => {
+                                last_rule_symbol = None;
+                                state = GrammarParsingState::AwaitingKeywords { keywords: vec![] };
+                            },
+                            GrammarToken::Caseless => {
+                                last_rule_symbol = None;
+                                state = GrammarParsingState::AwaitingCaseInsensitiveTokens {
+                                    tokens: vec![],
+                                };
+                            },
+                            GrammarToken::Mode => {
+                                last_rule_symbol = None;
+                                state = GrammarParsingState::AwaitingModeName;
+                            },
+                            GrammarToken::On => {
+                                last_rule_symbol = None;
+                                state = GrammarParsingState::AwaitingModeOfTransition;
+                            },
+                            GrammarToken::PrecedenceDeclaration(associativity) => {
+                                last_rule_symbol = None;
+                                state = GrammarParsingState::AwaitingPrecedenceTokens {
+                                    associativity,
+                                    tokens: vec![],
+                                };
+                            },
+                            GrammarToken::Pipe if last_rule_symbol.is_some() => {
+                                // Alternatives can be continued on the following lines.
+                                definition_span = None;
+                                state = GrammarParsingState::AwaitingAtomicPatterns {
+                                    symbol: last_rule_symbol.clone().unwrap(),
+                                    pattern: vec![],
+                                    open_groups: vec![],
+                                    label: None,
+                                    precedence: None,
+                                };
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+
+                    GrammarParsingState::AwaitingArrowForRule { symbol } => {
+                        match token {
+                            GrammarToken::Arrow => {
+                                state = GrammarParsingState::AwaitingAtomicPatterns {
+                                    symbol: symbol.clone(),
+                                    pattern: vec![],
+                                    open_groups: vec![],
+                                    label: None,
+                                    precedence: None,
+                                };
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingParameter {
+                        symbol,
+                        parameters: symbol_parameters,
+                    } => {
+                        match token {
+                            GrammarToken::Symbol(parameter) => {
+                                symbol_parameters.push(parameter);
+                                state = GrammarParsingState::AwaitingParameterSeparator {
+                                    symbol: std::mem::replace(symbol, Symbol::from("")),
+                                    parameters: std::mem::take(symbol_parameters),
+                                };
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingParameterSeparator {
+                        symbol,
+                        parameters: symbol_parameters,
+                    } => {
+                        match token {
+                            GrammarToken::Comma => {
+                                state = GrammarParsingState::AwaitingParameter {
+                                    symbol: std::mem::replace(symbol, Symbol::from("")),
+                                    parameters: std::mem::take(symbol_parameters),
+                                };
+                            },
+                            GrammarToken::ClosingParenthesis => {
+                                // All rules of a parameterized symbol need to have the same number of parameters.
+                                let previous_definition =
+                                    parameterized_rules.iter().find(|parameterized_rule| {
+                                        parameterized_rule.symbol == *symbol
+                                    });
+                                if let Some(previous_definition) = previous_definition {
+                                    let expected = previous_definition.parameters.len();
+                                    if symbol_parameters.len() != expected {
+                                        let (line, column) = lexer.extras;
+                                        break 'token Err(GrammarError::ArgumentCountMismatch {
+                                            line,
+                                            column,
+                                            span: token_span,
+                                            symbol: std::mem::replace(symbol, Symbol::from("")),
+                                            expected,
+                                            got: symbol_parameters.len(),
+                                        });
+                                    }
+                                }
+
+                                last_rule_symbol = Some(symbol.clone());
+                                parameters = Some(std::mem::take(symbol_parameters));
+                                state = GrammarParsingState::AwaitingArrowForRule {
+                                    symbol: std::mem::replace(symbol, Symbol::from("")),
+                                };
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingArrowForRegex { regex_token } => {
+                        match token {
+                            GrammarToken::Arrow => {
+                                state = GrammarParsingState::AwaitingRegex {
+                                    regex_token: regex_token.clone(),
+                                };
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+
+                    GrammarParsingState::AwaitingAtomicPatterns {
+                        symbol,
+                        pattern,
+                        open_groups,
+                        label,
+                        precedence,
+                    } => {
+                        // Span of a rule ends with the last token of its pattern.
+                        let ends_rule = token == GrammarToken::NewLine
+                            || (token == GrammarToken::Pipe && open_groups.is_empty());
+                        if token != GrammarToken::Comment && !ends_rule {
+                            extend_span(&mut definition_span, &token_span);
+                        }
+
+                        match token {
+                            GrammarToken::Comment => {},
+                            // Precedence of the rule can only be followed by the end of the rule.
+                            _ if precedence.is_some()
+                                && !matches!(token, GrammarToken::NewLine | GrammarToken::Pipe) =>
+                            {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                            // Label can only be followed by the element it labels.
+                            _ if label.is_some()
+                                && !matches!(
+                                    token,
+                                    GrammarToken::Symbol(_)
+                                        | GrammarToken::ParameterizedSymbol(_)
+                                        | GrammarToken::ConstantToken(_)
+                                        | GrammarToken::CaseInsensitiveConstantToken(_)
+                                        | GrammarToken::RegexToken(_)
+                                        | GrammarToken::OpeningParenthesis
+                                ) =>
+                            {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                            GrammarToken::Label(new_label) => {
+                                *label = Some((new_label, token_span));
+                            },
+                            GrammarToken::Symbol(symbol) => {
+                                pattern.push(
+                                    Element::Atomic(AtomicPattern::Symbol(symbol), token_span)
+                                        .labeled(label.take()),
+                                );
+                            },
+                            GrammarToken::ConstantToken(constant_token) => {
+                                constant_tokens.insert(constant_token.clone());
+                                pattern.push(
+                                    Element::Atomic(
+                                        AtomicPattern::Token(Token::Constant(constant_token)),
+                                        token_span,
+                                    )
+                                    .labeled(label.take()),
+                                );
+                            },
+                            GrammarToken::CaseInsensitiveConstantToken(constant_token) => {
+                                constant_tokens.insert(constant_token.clone());
+                                case_insensitive_tokens.insert(constant_token.clone());
+                                pattern.push(
+                                    Element::Atomic(
+                                        AtomicPattern::Token(Token::Constant(constant_token)),
+                                        token_span,
+                                    )
+                                    .labeled(label.take()),
+                                );
+                            },
+                            GrammarToken::RegexToken(regex_token) => {
+                                pattern.push(
+                                    Element::Atomic(
+                                        AtomicPattern::Token(Token::Regex(regex_token)),
+                                        token_span,
+                                    )
+                                    .labeled(label.take()),
+                                );
+                            },
+                            GrammarToken::QuestionMark
+                            | GrammarToken::Asterisk
+                            | GrammarToken::Plus => {
+                                let construct = match token {
+                                    GrammarToken::QuestionMark => EbnfConstruct::Optional,
+                                    GrammarToken::Asterisk => EbnfConstruct::ZeroOrMore,
+                                    GrammarToken::Plus => EbnfConstruct::OneOrMore,
+                                    _ => unreachable!(),
+                                };
+                                match pattern.pop() {
+                                    // Label of an element applies to the repetition of the element.
+                                    Some(Element::Labeled { label, element, span }) => {
+                                        pattern.push(Element::Labeled {
+                                            label,
+                                            element: Box::new(Element::Construct {
+                                                construct,
+                                                span: element.span().join(&token_span),
+                                                alternatives: vec![vec![*element]],
+                                            }),
+                                            span: span.join(&token_span),
+                                        });
+                                    },
+                                    Some(element) => {
+                                        pattern.push(Element::Construct {
+                                            construct,
+                                            span: element.span().join(&token_span),
+                                            alternatives: vec![vec![element]],
+                                        });
+                                    },
+                                    None => {
+                                        break 'token Err(state.unexpected_token(&lexer));
+                                    },
+                                }
+                            },
+                            GrammarToken::OpeningParenthesis => {
+                                open_groups.push(OpenGroup {
+                                    enclosing_pattern: std::mem::take(pattern),
+                                    label: label.take(),
+                                    alternatives: vec![],
+                                    application: None,
+                                    span: token_span,
+                                });
+                            },
+                            GrammarToken::ParameterizedSymbol(symbol) => {
+                                open_groups.push(OpenGroup {
+                                    enclosing_pattern: std::mem::take(pattern),
+                                    label: label.take(),
+                                    alternatives: vec![],
+                                    application: Some(symbol),
+                                    span: token_span,
+                                });
+                            },
+                            GrammarToken::Comma => {
+                                match open_groups.last_mut() {
+                                    Some(open_group)
+                                        if open_group.application.is_some()
+                                            && !pattern.is_empty() =>
+                                    {
+                                        open_group.alternatives.push(std::mem::take(pattern));
+                                    },
+                                    _ => {
+                                        break 'token Err(state.unexpected_token(&lexer));
+                                    },
+                                }
+                            },
+                            GrammarToken::ClosingParenthesis => {
+                                if pattern.is_empty() {
+                                    break 'token Err(state.unexpected_token(&lexer));
+                                }
+                                let OpenGroup {
+                                    enclosing_pattern,
+                                    label,
+                                    mut alternatives,
+                                    application,
+                                    span,
+                                } = match open_groups.pop() {
+                                    Some(open_group) => open_group,
+                                    None => {
+                                        break 'token Err(state.unexpected_token(&lexer));
+                                    },
+                                };
+                                let span = span.join(&token_span);
+                                alternatives.push(std::mem::replace(pattern, enclosing_pattern));
+                                if let Some(symbol) = application {
+                                    // Arguments with multiple elements are grouped (e.g., `List(A B)`).
+                                    let arguments = alternatives
+                                        .into_iter()
+                                        .map(|mut argument| {
+                                            if argument.len() == 1 {
+                                                argument.pop().unwrap()
+                                            } else {
+                                                let first = argument.first().unwrap().span();
+                                                let last = argument.last().unwrap().span();
+                                                Element::Construct {
+                                                    construct: EbnfConstruct::Group,
+                                                    span: first.join(last),
+                                                    alternatives: vec![argument],
+                                                }
+                                            }
+                                        })
+                                        .collect();
+                                    pattern.push(
+                                        Element::Application { symbol, arguments, span }
+                                            .labeled(label),
+                                    );
+                                } else if alternatives.len() == 1 && alternatives[0].len() == 1 {
+                                    // (X) is the same as X.
+                                    let element = alternatives.pop().unwrap().pop().unwrap();
+                                    pattern.push(element.labeled(label));
+                                } else {
+                                    pattern.push(
+                                        Element::Construct {
+                                            construct: EbnfConstruct::Group,
+                                            alternatives,
+                                            span,
+                                        }
+                                        .labeled(label),
+                                    );
+                                }
+                            },
+                            GrammarToken::Pipe => {
+                                if pattern.is_empty() {
+                                    break 'token Err(state.unexpected_token(&lexer));
+                                }
+                                match open_groups.last_mut() {
+                                    Some(open_group) if open_group.application.is_some() => {
+                                        break 'token Err(state.unexpected_token(&lexer));
+                                    },
+                                    Some(open_group) => {
+                                        open_group.alternatives.push(std::mem::take(pattern));
+                                    },
+                                    None => {
+                                        define_rule(
+                                            symbol.clone(),
+                                            std::mem::take(pattern),
+                                            precedence.take(),
+                                            definition_span.take(),
+                                            &parameters,
+                                            &mut definitions,
+                                            &mut parameterized_rules,
+                                        );
+                                    },
+                                }
+                            },
+                            GrammarToken::Prec => {
+                                if pattern.is_empty() || !open_groups.is_empty() {
+                                    break 'token Err(state.unexpected_token(&lexer));
+                                }
+                                state = GrammarParsingState::AwaitingPrecedenceTokenOfRule {
+                                    symbol: std::mem::replace(symbol, Symbol::from("")),
+                                    pattern: std::mem::take(pattern),
+                                };
+                            },
+                            GrammarToken::Empty => {
+                                if !pattern.is_empty() || !open_groups.is_empty() {
+                                    break 'token Err(state.unexpected_token(&lexer));
+                                }
+                                state = GrammarParsingState::AwaitingNewLineAfterEmptyPattern {
+                                    symbol: std::mem::replace(symbol, Symbol::from("")),
+                                };
+                            },
+                            GrammarToken::NewLine => {
+                                if pattern.is_empty() || !open_groups.is_empty() {
+                                    break 'token Err(state.unexpected_token(&lexer));
+                                }
+
+                                define_rule(
+                                    std::mem::replace(symbol, Symbol::from("")),
+                                    std::mem::take(pattern),
+                                    precedence.take(),
+                                    definition_span.take(),
+                                    &parameters,
+                                    &mut definitions,
+                                    &mut parameterized_rules,
+                                );
+
+                                state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                            },
+
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingPrecedenceTokenOfRule { symbol, pattern } => {
+                        extend_span(&mut definition_span, &token_span);
+                        let token = match token {
+                            GrammarToken::ConstantToken(constant_token) => {
+                                Token::Constant(constant_token)
+                            },
+                            GrammarToken::RegexToken(regex_token) => Token::Regex(regex_token),
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        };
+                        state = GrammarParsingState::AwaitingAtomicPatterns {
+                            symbol: std::mem::replace(symbol, Symbol::from("")),
+                            pattern: std::mem::take(pattern),
+                            open_groups: vec![],
+                            label: None,
+                            precedence: Some(token),
+                        };
+                    },
+                    GrammarParsingState::AwaitingNewLineAfterEmptyPattern { symbol } => {
+                        match token {
+                            GrammarToken::Comment => {},
+                            GrammarToken::NewLine => {
+                                define_rule(
+                                    std::mem::replace(symbol, Symbol::from("")),
+                                    vec![],
+                                    None,
+                                    definition_span.take(),
+                                    &parameters,
+                                    &mut definitions,
+                                    &mut parameterized_rules,
+                                );
+
+                                state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                            },
+                            GrammarToken::Pipe => {
+                                define_rule(
+                                    symbol.clone(),
+                                    vec![],
+                                    None,
+                                    definition_span.take(),
+                                    &parameters,
+                                    &mut definitions,
+                                    &mut parameterized_rules,
+                                );
+
+                                state = GrammarParsingState::AwaitingAtomicPatterns {
+                                    symbol: std::mem::replace(symbol, Symbol::from("")),
+                                    pattern: vec![],
+                                    open_groups: vec![],
+                                    label: None,
+                                    precedence: None,
+                                };
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingPrecedenceTokens { associativity, tokens } => {
+                        match token {
+                            GrammarToken::Comment => {},
+                            GrammarToken::ConstantToken(constant_token) => {
+                                tokens.push(Token::Constant(constant_token));
+                            },
+                            GrammarToken::RegexToken(regex_token) => {
+                                tokens.push(Token::Regex(regex_token));
+                            },
+                            GrammarToken::NewLine => {
+                                if tokens.is_empty() {
+                                    break 'token Err(state.unexpected_token(&lexer));
+                                }
+
+                                precedence_level += 1;
+                                declare_precedences(
+                                    std::mem::take(tokens),
+                                    Precedence::new(precedence_level, *associativity),
+                                    &mut precedences,
+                                );
+
+                                state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingEntrySymbols { symbols } => {
+                        match token {
+                            GrammarToken::Comment => {},
+                            GrammarToken::Symbol(symbol) => {
+                                entry_symbol_spans.entry(symbol.clone()).or_insert(token_span);
+                                symbols.push(symbol);
+                            },
+                            GrammarToken::NewLine => {
+                                if symbols.is_empty() {
+                                    break 'token Err(state.unexpected_token(&lexer));
+                                }
+                                entry_symbols.extend(std::mem::take(symbols));
+                                state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingRegex { regex_token } => {
+                        match token {
+                            GrammarToken::Regex(regex_string) => {
+                                let regex = match anchored_regex(&regex_string) {
                                     Ok(regex) => regex,
                                     Err(_) => {
-                                        return Err(state.unexpected_regex(lexer));
+                                        break 'token Err(state.unexpected_regex(&lexer));
                                     },
                                 };
-                            regular_expressions.insert(regex_token.clone(), regex);
-                            state = GrammarParsingState::AwaitingSymbolOrRegexToken;
-                        },
-                        _ => {
-                            return Err(state.unexpected_token(lexer));
-                        },
-                    }
-                },
+                                regular_expressions.insert(regex_token.clone(), regex);
+                                extend_span(&mut definition_span, &token_span);
+                                if let Some(regex_span) = definition_span.take() {
+                                    regex_spans.insert(regex_token.clone(), regex_span);
+                                }
+                                state = GrammarParsingState::AwaitingPriorityOfRegex {
+                                    regex_token: regex_token.clone(),
+                                };
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingPriorityOfRegex { regex_token } => {
+                        match token {
+                            GrammarToken::NewLine | GrammarToken::Comment => {
+                                state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                            },
+                            GrammarToken::Prio => {
+                                state = GrammarParsingState::AwaitingPriority {
+                                    regex_token: regex_token.clone(),
+                                };
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingPriority { regex_token } => {
+                        match token {
+                            GrammarToken::Symbol(priority) => {
+                                let priority = match priority.parse::<usize>() {
+                                    Ok(priority) => priority,
+                                    Err(_) => {
+                                        break 'token Err(state.unexpected_token(&lexer));
+                                    },
+                                };
+                                priorities.insert(regex_token.clone(), priority);
+                                state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingCaseInsensitiveTokens { tokens } => {
+                        match token {
+                            GrammarToken::Comment => {},
+                            GrammarToken::ConstantToken(constant_token)
+                            | GrammarToken::CaseInsensitiveConstantToken(constant_token) => {
+                                tokens.push(constant_token);
+                            },
+                            GrammarToken::NewLine => {
+                                // Without any tokens, all constant tokens are case insensitive.
+                                if tokens.is_empty() {
+                                    case_insensitive = true;
+                                }
+                                for constant_token in std::mem::take(tokens) {
+                                    constant_tokens.insert(constant_token.clone());
+                                    case_insensitive_tokens.insert(constant_token);
+                                }
+                                state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingModeName => {
+                        match token {
+                            GrammarToken::Symbol(mode) => {
+                                state = GrammarParsingState::AwaitingModeTokens {
+                                    mode: SmolStr::from(mode.as_str()),
+                                    tokens: vec![],
+                                };
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingModeTokens { mode, tokens } => {
+                        match token {
+                            GrammarToken::Comment => {},
+                            GrammarToken::ConstantToken(constant_token) => {
+                                constant_tokens.insert(constant_token.clone());
+                                tokens.push(Token::Constant(constant_token));
+                            },
+                            GrammarToken::RegexToken(regex_token) => {
+                                tokens.push(Token::Regex(regex_token));
+                            },
+                            GrammarToken::NewLine => {
+                                if tokens.is_empty() {
+                                    break 'token Err(state.unexpected_token(&lexer));
+                                }
+                                modes
+                                    .entry(std::mem::take(mode))
+                                    .or_default()
+                                    .extend(std::mem::take(tokens));
+                                state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingModeOfTransition => {
+                        match token {
+                            GrammarToken::Symbol(mode) => {
+                                referenced_modes.push((mode.clone(), token_span));
+                                state = GrammarParsingState::AwaitingTokenOfTransition {
+                                    mode: SmolStr::from(mode.as_str()),
+                                };
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingTokenOfTransition { mode } => {
+                        let token = match token {
+                            GrammarToken::ConstantToken(constant_token) => {
+                                constant_tokens.insert(constant_token.clone());
+                                Token::Constant(constant_token)
+                            },
+                            GrammarToken::RegexToken(regex_token) => Token::Regex(regex_token),
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        };
+                        state = GrammarParsingState::AwaitingTransition {
+                            mode: std::mem::take(mode),
+                            token,
+                        };
+                    },
+                    GrammarParsingState::AwaitingTransition { mode, token: trigger } => {
+                        match token {
+                            GrammarToken::Push | GrammarToken::Switch => {
+                                state = GrammarParsingState::AwaitingTargetModeOfTransition {
+                                    mode: std::mem::take(mode),
+                                    token: trigger.clone(),
+                                    switch: token == GrammarToken::Switch,
+                                };
+                            },
+                            GrammarToken::Pop => {
+                                transitions
+                                    .entry(std::mem::take(mode))
+                                    .or_default()
+                                    .insert(trigger.clone(), ModeTransition::Pop);
+                                state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingTargetModeOfTransition {
+                        mode,
+                        token: trigger,
+                        switch,
+                    } => {
+                        match token {
+                            GrammarToken::Symbol(target) => {
+                                referenced_modes.push((target.clone(), token_span));
+
+                                let target = SmolStr::from(target.as_str());
+                                let transition = if *switch {
+                                    ModeTransition::Switch(target)
+                                } else {
+                                    ModeTransition::Push(target)
+                                };
+                                transitions
+                                    .entry(std::mem::take(mode))
+                                    .or_default()
+                                    .insert(trigger.clone(), transition);
+                                state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingKeywords { keywords: declared_keywords } => {
+                        match token {
+                            GrammarToken::Comment => {},
+                            GrammarToken::ConstantToken(constant_token) => {
+                                declared_keywords.push(constant_token);
+                            },
+                            GrammarToken::CaseInsensitiveConstantToken(constant_token) => {
+                                case_insensitive_tokens.insert(constant_token.clone());
+                                declared_keywords.push(constant_token);
+                            },
+                            GrammarToken::NewLine => {
+                                if declared_keywords.is_empty() {
+                                    break 'token Err(state.unexpected_token(&lexer));
+                                }
+                                for keyword in std::mem::take(declared_keywords) {
+                                    constant_tokens.insert(keyword.clone());
+                                    keywords.insert(keyword);
+                                }
+                                state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingImportPath => {
+                        match token {
+                            GrammarToken::ImportPath(path) => {
+                                let (line, column) = lexer.extras;
+                                let importer =
+                                    imports.stack.last().map(|importer| importer.as_str());
+                                let (name, content) = match resolver.resolve(&path, importer) {
+                                    Ok(resolved) => resolved,
+                                    Err(reason) => {
+                                        break 'token Err(GrammarError::UnresolvedImport {
+                                            line,
+                                            column,
+                                            span: token_span,
+                                            path,
+                                            reason,
+                                        });
+                                    },
+                                };
+                                if imports.stack.contains(&name) {
+                                    break 'token Err(GrammarError::CyclicImport {
+                                        line,
+                                        column,
+                                        span: token_span,
+                                        path,
+                                    });
+                                }
+
+                                // Grammars are imported only once, even if they are imported many times.
+                                if imports.imported.insert(name.clone()) {
+                                    imports.stack.push(name.clone());
+                                    let mut imported_errors = Vec::new();
+                                    let mut imported = parse_internal(
+                                        &content,
+                                        resolver,
+                                        imports,
+                                        &mut imported_errors,
+                                    );
+                                    imports.stack.pop();
+
+                                    // Spans of the imported grammar are positions in the imported grammar.
+                                    record_grammar_source(&mut imported, &name);
+
+                                    // Errors are reported in the grammar they occur in.
+                                    for error in imported_errors {
+                                        errors.push(match error {
+                                            GrammarError::InImportedGrammar { .. } => error,
+                                            _ => {
+                                                GrammarError::InImportedGrammar {
+                                                    grammar: name.clone(),
+                                                    error: Box::new(error),
+                                                }
+                                            },
+                                        });
+                                    }
+
+                                    // Entry symbols are only declared by the importing grammar.
+                                    if imported_start_symbol.is_none() {
+                                        imported_start_symbol = imported
+                                            .rules
+                                            .first()
+                                            .map(|rule| rule.symbol().clone());
+                                    }
+
+                                    symbols.extend(imported.symbols);
+                                    constant_tokens.extend(imported.constant_tokens);
+                                    regular_expressions.extend(imported.regular_expressions);
+                                    regex_spans.extend(imported.regex_spans);
+                                    priorities.extend(imported.priorities);
+                                    keywords.extend(imported.keywords);
+                                    case_insensitive |= imported.case_insensitive;
+                                    case_insensitive_tokens
+                                        .extend(imported.case_insensitive_tokens);
+                                    for (mode, tokens) in imported.modes {
+                                        modes.entry(mode).or_default().extend(tokens);
+                                    }
+                                    for (mode, mode_transitions) in imported.transitions {
+                                        transitions
+                                            .entry(mode)
+                                            .or_default()
+                                            .extend(mode_transitions);
+                                    }
+                                    for regex in imported.ignored_regular_expressions {
+                                        let declared = ignored_regular_expressions.iter().any(
+                                            |ignored: &Regex| ignored.as_str() == regex.as_str(),
+                                        );
+                                        if !declared {
+                                            ignored_regular_expressions.push(regex);
+                                        }
+                                    }
+                                    definitions
+                                        .push(Definition::Imported { rules: imported.rules });
+                                    auxiliary_symbols.extend(imported.auxiliary_symbols);
+                                    parameterized_rules.extend(imported.parameterized_rules);
+                                    instantiations.extend(imported.instantiations);
+
+                                    // Precedences of the imported grammar are higher than the ones above the import.
+                                    let mut highest_imported_level = 0;
+                                    for (token, precedence) in imported.precedences {
+                                        highest_imported_level =
+                                            highest_imported_level.max(precedence.level());
+                                        declare_precedences(
+                                            vec![token],
+                                            Precedence::new(
+                                                precedence_level + precedence.level(),
+                                                precedence.associativity(),
+                                            ),
+                                            &mut precedences,
+                                        );
+                                    }
+                                    precedence_level += highest_imported_level;
+                                }
+
+                                state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                    GrammarParsingState::AwaitingIgnoredRegex => {
+                        match token {
+                            GrammarToken::Regex(regex_string) => {
+                                let regex = match anchored_regex(&regex_string) {
+                                    Ok(regex) => regex,
+                                    Err(_) => {
+                                        break 'token Err(state.unexpected_regex(&lexer));
+                                    },
+                                };
+                                ignored_regular_expressions.push(regex);
+                                state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                            },
+                            _ => {
+                                break 'token Err(state.unexpected_token(&lexer));
+                            },
+                        }
+                    },
+                }
+                Ok(())
+            };
+            if let Err(error) = result {
+                // Rest of the line is skipped, and parsing continues from the next definition.
+                errors.push(error);
+                state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                definition_span = None;
+                recovering = !newline;
             }
 
             if newline {
                 line += 1;
                 column = 1;
+                column_start_position = span.end;
                 lexer.extras = (line, column);
             }
         }
 
+        let eof_span = Span {
+            offset: grammar_string.len(),
+            len: 0,
+            line,
+            column: column + grammar_string[column_start_position..].chars().count(),
+            source: None,
+        };
         match &mut state {
             GrammarParsingState::AwaitingSymbolOrRegexToken => {},
-            GrammarParsingState::AwaitingAtomicPatterns { symbol, pattern } => {
-                if pattern.is_empty() {
-                    return Err(state.unexpected_eof());
+            GrammarParsingState::AwaitingAtomicPatterns {
+                symbol,
+                pattern,
+                open_groups,
+                label,
+                precedence,
+            } => {
+                if pattern.is_empty() || !open_groups.is_empty() || label.is_some() {
+                    errors.push(state.unexpected_eof(eof_span));
+                } else {
+                    define_rule(
+                        std::mem::replace(symbol, Symbol::from("")),
+                        std::mem::take(pattern),
+                        precedence.take(),
+                        definition_span.take(),
+                        &parameters,
+                        &mut definitions,
+                        &mut parameterized_rules,
+                    );
+                }
+            },
+            GrammarParsingState::AwaitingNewLineAfterEmptyPattern { symbol } => {
+                define_rule(
+                    std::mem::replace(symbol, Symbol::from("")),
+                    vec![],
+                    None,
+                    definition_span.take(),
+                    &parameters,
+                    &mut definitions,
+                    &mut parameterized_rules,
+                );
+            },
+            GrammarParsingState::AwaitingPrecedenceTokens { associativity, tokens }
+                if !tokens.is_empty() =>
+            {
+                precedence_level += 1;
+                declare_precedences(
+                    std::mem::take(tokens),
+                    Precedence::new(precedence_level, *associativity),
+                    &mut precedences,
+                );
+            },
+            GrammarParsingState::AwaitingEntrySymbols { symbols } if !symbols.is_empty() => {
+                entry_symbols.extend(std::mem::take(symbols));
+            },
+            GrammarParsingState::AwaitingPriorityOfRegex { .. } => {},
+            GrammarParsingState::AwaitingCaseInsensitiveTokens { tokens } => {
+                if tokens.is_empty() {
+                    case_insensitive = true;
+                }
+                for constant_token in std::mem::take(tokens) {
+                    constant_tokens.insert(constant_token.clone());
+                    case_insensitive_tokens.insert(constant_token);
+                }
+            },
+            GrammarParsingState::AwaitingModeTokens { mode, tokens } if !tokens.is_empty() => {
+                modes.entry(std::mem::take(mode)).or_default().extend(std::mem::take(tokens));
+            },
+            GrammarParsingState::AwaitingKeywords { keywords: declared_keywords }
+                if !declared_keywords.is_empty() =>
+            {
+                for keyword in std::mem::take(declared_keywords) {
+                    constant_tokens.insert(keyword.clone());
+                    keywords.insert(keyword);
                 }
-
-                let rule = Rule {
-                    symbol: std::mem::replace(symbol, Symbol::from("")),
-                    pattern: std::mem::take(pattern),
-                };
-                rules.push(rule);
             },
             _ => {
-                return Err(state.unexpected_eof());
+                errors.push(state.unexpected_eof(eof_span));
             },
         }
 
-        Ok(Grammar {
+        // Modes can be used in transitions before they are declared.
+        for (mode, span) in referenced_modes {
+            if mode.as_str() != Grammar::DEFAULT_MODE && !modes.contains_key(mode.as_str()) {
+                errors.push(GrammarError::UndefinedMode {
+                    line: span.line,
+                    column: span.column,
+                    span,
+                    mode: SmolStr::from(mode.as_str()),
+                });
+            }
+        }
+
+        // Patterns are desugared once all parameterized rules are known.
+        let mut rules = Vec::new();
+        let mut desugarer = Desugarer {
+            symbols: &mut symbols,
+            auxiliary_symbols: &mut auxiliary_symbols,
+            parameterized_rules: &parameterized_rules,
+            instantiations: &mut instantiations,
+            instantiating: vec![],
+        };
+        for definition in definitions {
+            match definition {
+                Definition::Rule { symbol, pattern, precedence, span } => {
+                    if let Err(error) =
+                        desugarer.add_rule(symbol, pattern, precedence, span, &mut rules)
+                    {
+                        errors.push(error);
+                    }
+                },
+                Definition::Imported { rules: imported_rules } => {
+                    rules.extend(imported_rules);
+                },
+            }
+        }
+
+        // Start symbol is the symbol of the first rule, unless entry symbols are declared.
+        // Rules of imported grammars are only considered if the grammar has no rules of its own.
+        match entry_symbols.first() {
+            Some(entry_symbol) => {
+                start_symbol = Some(entry_symbol.clone());
+            },
+            None => {
+                if start_symbol.is_none() {
+                    start_symbol = imported_start_symbol;
+                }
+                if let Some(start_symbol) = &start_symbol {
+                    entry_symbols.insert(start_symbol.clone());
+                }
+            },
+        }
+
+        Grammar {
             symbols,
             start_symbol: start_symbol.unwrap_or(Symbol::from("")),
+            entry_symbols,
+            entry_symbol_spans,
             constant_tokens,
             regular_expressions,
+            regex_spans,
+            priorities,
+            keywords,
+            case_insensitive,
+            case_insensitive_tokens,
+            modes,
+            transitions,
+            ignored_regular_expressions,
             rules,
-        })
+            parameterized_rules,
+            auxiliary_symbols,
+            instantiations,
+            precedences,
+        }
     }
 }
@@ -1,12 +1,20 @@
 #![doc = include_str!("../README.md")]
 
 mod automaton;
+mod bison;
+mod comparison;
 mod errors;
+mod exporters;
 mod grammar;
+mod lints;
+mod normal_forms;
 mod parser;
+mod railroad;
+mod resolver;
 mod span;
 mod tables;
 mod trace;
+mod transformations;
 mod tree;
 mod utils;
 
@@ -16,21 +24,38 @@ pub use {
         Item,
         State,
     },
+    bison::BisonWarning,
+    comparison::Comparison,
     errors::{
+        GrammarBuilderError,
         GrammarError,
         ParserError,
         ParsingError,
     },
+    exporters::{
+        ExportFormat,
+        ExportWarning,
+    },
     grammar::{
+        Associativity,
         AtomicPattern,
         ConstantToken,
+        EbnfConstruct,
         Grammar,
+        GrammarBuilder,
+        ModeTransition,
+        Precedence,
         RegexToken,
         Rule,
         Symbol,
         Token,
     },
+    lints::GrammarWarning,
     parser::Parser,
+    resolver::{
+        FileSystemResolver,
+        GrammarResolver,
+    },
     span::{
         Span,
         Spanned,
@@ -39,18 +64,24 @@ pub use {
         Action,
         FirstTable,
         FollowTable,
+        NullableTable,
         ParsingTables,
+        ResolvedConflict,
     },
     trace::{
         Step,
         Trace,
     },
+    transformations::Rewrite,
     tree::Tree,
 };
 
 mod prelude {
     #[cfg(feature = "serde")]
-    pub use serde_renamed::Serialize;
+    pub use serde_renamed::{
+        Deserialize,
+        Serialize,
+    };
 
     #[cfg(feature = "wasm")]
     pub use {
@@ -108,7 +139,10 @@ mod prelude {
     };
     #[cfg(feature = "serde")]
     pub use {
+        serde_renamed::Deserializer,
         serde_renamed::Serializer,
+        serde_renamed::de::Error as DeserializationError,
         serde_renamed::ser::SerializeMap,
+        serde_renamed::ser::SerializeSeq,
     };
 }
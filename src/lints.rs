@@ -0,0 +1,225 @@
+use crate::prelude::*;
+
+
+/// Grammar warning of a definition that is likely to be a mistake.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum GrammarWarning {
+    /// A symbol cannot be reached from any of the entry symbols.
+    #[error(
+        "symbol {}{} is not reachable from the entry symbols",
+        format_smolstr!("{}", symbol).green(),
+        location(span),
+    )]
+    UnreachableSymbol { symbol: Symbol, span: Option<Span> },
+
+    /// A symbol cannot derive any sequence of tokens.
+    #[error(
+        "symbol {}{} cannot derive any sequence of tokens",
+        format_smolstr!("{}", symbol).green(),
+        location(span),
+    )]
+    UnproductiveSymbol { symbol: Symbol, span: Option<Span> },
+
+    /// A regex token is defined without being used in any rule.
+    #[error(
+        "regex token {}{} is not used in any rule",
+        format_smolstr!("{}", regex_token).green(),
+        location(span),
+    )]
+    UnusedRegexToken { regex_token: RegexToken, span: Option<Span> },
+
+    /// A rule is defined more than once.
+    #[error(
+        "rule {}{} is defined more than once",
+        format_smolstr!("{}", rule).green(),
+        location(span),
+    )]
+    DuplicateRule { rule: Rule, span: Option<Span> },
+
+    /// A rule derives its own symbol without consuming any tokens (e.g., `A -> A`).
+    #[error(
+        "rule {}{} forms a cycle",
+        format_smolstr!("{}", rule).green(),
+        location(span),
+    )]
+    TrivialCycle { rule: Rule, span: Option<Span> },
+}
+
+impl GrammarWarning {
+    /// Gets the span of the definition the warning is about, if it's known.
+    pub fn span(&self) -> Option<&Span> {
+        match self {
+            GrammarWarning::UnreachableSymbol { span, .. }
+            | GrammarWarning::UnproductiveSymbol { span, .. }
+            | GrammarWarning::UnusedRegexToken { span, .. }
+            | GrammarWarning::DuplicateRule { span, .. }
+            | GrammarWarning::TrivialCycle { span, .. } => span.as_ref(),
+        }
+    }
+}
+
+
+impl Grammar {
+    /// Lints the grammar for unreachable, unproductive, unused and duplicate definitions.
+    pub fn lint(&self) -> Vec<GrammarWarning> {
+        let mut warnings = Vec::new();
+
+        // Symbols are located at their first rule.
+        let mut symbol_spans = IndexMap::new();
+        for rule in self.rules() {
+            if let Some(span) = rule.span() {
+                symbol_spans.entry(rule.symbol()).or_insert(span);
+            }
+        }
+
+        let reachable_symbols = self.reachable_symbols_internal();
+        for symbol in self.defined_symbols_internal() {
+            if !reachable_symbols.contains(symbol) {
+                warnings.push(GrammarWarning::UnreachableSymbol {
+                    symbol: symbol.clone(),
+                    span: symbol_spans.get(symbol).copied().cloned(),
+                });
+            }
+        }
+
+        let productive_symbols = self.productive_symbols_internal();
+        for symbol in self.defined_symbols_internal() {
+            if !productive_symbols.contains(symbol) {
+                warnings.push(GrammarWarning::UnproductiveSymbol {
+                    symbol: symbol.clone(),
+                    span: symbol_spans.get(symbol).copied().cloned(),
+                });
+            }
+        }
+
+        for regex_token in self.regular_expressions().keys() {
+            let used = self.rules().iter().any(|rule| {
+                rule.pattern().iter().any(|atomic_pattern| {
+                    matches!(atomic_pattern, AtomicPattern::Token(Token::Regex(used)) if used == regex_token)
+                })
+            });
+            if !used {
+                warnings.push(GrammarWarning::UnusedRegexToken {
+                    regex_token: regex_token.clone(),
+                    span: self.regex_spans().get(regex_token).cloned(),
+                });
+            }
+        }
+
+        let mut defined_rules = IndexSet::new();
+        for rule in self.rules() {
+            if !defined_rules.insert((rule.symbol(), rule.pattern())) {
+                warnings.push(GrammarWarning::DuplicateRule {
+                    rule: rule.clone(),
+                    span: rule.span().cloned(),
+                });
+            }
+        }
+
+        for rule in self.rules() {
+            if let [AtomicPattern::Symbol(symbol)] = rule.pattern() {
+                if self.unit_derivable_symbols_internal(symbol).contains(rule.symbol()) {
+                    warnings.push(GrammarWarning::TrivialCycle {
+                        rule: rule.clone(),
+                        span: rule.span().cloned(),
+                    });
+                }
+            }
+        }
+
+        warnings
+    }
+}
+
+impl Grammar {
+    /// Internal computation of the symbols with at least one rule, in the order they are defined.
+    fn defined_symbols_internal(&self) -> IndexSet<&Symbol> {
+        self.rules().iter().map(|rule| rule.symbol()).collect()
+    }
+
+    /// Internal computation of the symbols reachable from the entry symbols.
+    pub(crate) fn reachable_symbols_internal(&self) -> IndexSet<&Symbol> {
+        let mut reachable_symbols = self.entry_symbols().iter().collect::<IndexSet<_>>();
+
+        let mut index = 0;
+        while let Some(symbol) = reachable_symbols.get_index(index).copied() {
+            for rule in self.rules().iter().filter(|rule| rule.symbol() == symbol) {
+                for atomic_pattern in rule.pattern() {
+                    if let AtomicPattern::Symbol(symbol) = atomic_pattern {
+                        reachable_symbols.insert(symbol);
+                    }
+                }
+            }
+            index += 1;
+        }
+
+        reachable_symbols
+    }
+
+    /// Internal computation of the symbols that can derive a sequence of tokens.
+    pub(crate) fn productive_symbols_internal(&self) -> IndexSet<&Symbol> {
+        let mut productive_symbols = IndexSet::new();
+
+        let mut done = false;
+        while !done {
+            done = true;
+            for rule in self.rules().iter() {
+                if productive_symbols.contains(rule.symbol()) {
+                    continue;
+                }
+
+                let pattern_is_productive = rule.pattern().iter().all(|atomic_pattern| {
+                    match atomic_pattern {
+                        AtomicPattern::Symbol(symbol) => productive_symbols.contains(symbol),
+                        AtomicPattern::Token(_) => true,
+                    }
+                });
+                if pattern_is_productive {
+                    productive_symbols.insert(rule.symbol());
+                    done = false;
+                }
+            }
+        }
+
+        productive_symbols
+    }
+
+    /// Internal computation of the symbols derivable from a symbol using only unit rules (e.g., `A -> B`).
+    pub(crate) fn unit_derivable_symbols_internal<'s>(
+        &'s self,
+        symbol: &'s Symbol,
+    ) -> IndexSet<&'s Symbol> {
+        let mut derivable_symbols = IndexSet::from([symbol]);
+
+        let mut index = 0;
+        while let Some(symbol) = derivable_symbols.get_index(index).copied() {
+            for rule in self.rules().iter().filter(|rule| rule.symbol() == symbol) {
+                if let [AtomicPattern::Symbol(derived_symbol)] = rule.pattern() {
+                    derivable_symbols.insert(derived_symbol);
+                }
+            }
+            index += 1;
+        }
+
+        derivable_symbols
+    }
+}
+
+
+/// Formats the location of a span for a warning message.
+fn location(span: &Option<Span>) -> String {
+    match span {
+        Some(span) => {
+            format!(
+                " at line {} column {}{}",
+                format_smolstr!("{}", span.line).cyan(),
+                format_smolstr!("{}", span.column).cyan(),
+                errors::source(Some(span)),
+            )
+        },
+        None => String::new(),
+    }
+}
@@ -1,53 +1,222 @@
 use {
-    clap::Parser as Clap,
+    clap::{
+        Parser as Clap,
+        Subcommand,
+        ValueEnum,
+    },
     colored::Colorize,
     dotlr::{
+        ExportFormat,
+        FileSystemResolver,
         Grammar,
         Parser,
         ParserError,
+        Symbol,
+        Token,
     },
     rustyline::{
         DefaultEditor,
         error::ReadlineError,
     },
     std::{
-        path::PathBuf,
+        path::{
+            Path,
+            PathBuf,
+        },
         process::ExitCode,
     },
 };
 
 #[derive(Clap)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Create an LALR(1) parser instead of an LR(1) parser.
     #[arg(long)]
     lalr: bool,
 
+    /// Entry symbol to start parsing from (defaults to the start symbol of the grammar).
+    #[arg(long)]
+    entry: Option<String>,
+
     /// Grammar to parse.
-    grammar: PathBuf,
+    #[arg(required = true)]
+    grammar: Option<PathBuf>,
 
     /// Input to parse.
     input: Option<String>,
 }
 
+#[derive(Subcommand)]
+enum Command {
+    /// Compare the languages of two grammars on the sentences up to a length.
+    Compare {
+        /// Create LALR(1) parsers instead of LR(1) parsers.
+        #[arg(long)]
+        lalr: bool,
+
+        /// Maximum number of tokens in the compared sentences.
+        #[arg(long, default_value_t = 6)]
+        max_length: usize,
+
+        /// Maximum number of sentences to enumerate for each symbol.
+        #[arg(long, default_value_t = 10000)]
+        max_sentences: usize,
+
+        /// First grammar to compare.
+        grammar: PathBuf,
+
+        /// Second grammar to compare.
+        other_grammar: PathBuf,
+    },
+
+    /// Format grammars in place, keeping their comments.
+    Fmt {
+        /// Check whether the grammars are formatted instead of formatting them.
+        #[arg(long)]
+        check: bool,
+
+        /// Grammars to format.
+        #[arg(required = true)]
+        grammars: Vec<PathBuf>,
+    },
+
+    /// Export a grammar to another format, printing it to the standard output.
+    Export {
+        /// Format to export the grammar to.
+        #[arg(value_enum)]
+        format: Format,
+
+        /// Grammar to export.
+        grammar: PathBuf,
+    },
+
+    /// Translate a yacc/bison grammar into a grammar, printing it to the standard output.
+    Import {
+        /// Yacc/bison grammar to translate.
+        grammar: PathBuf,
+    },
+
+    /// Render railroad diagrams of a grammar to an SVG image, printing it to the standard output.
+    Railroad {
+        /// Symbol to render the railroad diagram of (defaults to all symbols).
+        #[arg(long)]
+        symbol: Option<String>,
+
+        /// Grammar to render.
+        grammar: PathBuf,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// Bison grammar skeleton.
+    Bison,
+    /// Lark grammar.
+    Lark,
+    /// Tree-sitter grammar, named after the grammar file.
+    TreeSitter,
+}
+
+impl From<Format> for ExportFormat {
+    fn from(format: Format) -> ExportFormat {
+        match format {
+            Format::Bison => ExportFormat::Bison,
+            Format::Lark => ExportFormat::Lark,
+            Format::TreeSitter => ExportFormat::TreeSitter,
+        }
+    }
+}
+
 fn main() -> ExitCode {
     let args = Args::parse();
 
-    let grammar = match std::fs::read_to_string(args.grammar) {
+    match args.command {
+        Some(Command::Compare { lalr, max_length, max_sentences, grammar, other_grammar }) => {
+            return compare(&grammar, &other_grammar, lalr, max_length, max_sentences);
+        },
+        Some(Command::Fmt { check, grammars }) => {
+            return fmt(&grammars, check);
+        },
+        Some(Command::Export { format, grammar }) => {
+            return export(&grammar, format.into());
+        },
+        Some(Command::Import { grammar }) => {
+            return import(&grammar);
+        },
+        Some(Command::Railroad { symbol, grammar }) => {
+            return railroad(&grammar, symbol.map(Symbol::from));
+        },
+        None => {},
+    }
+
+    let parser = match load(&args.grammar.unwrap_or_default(), args.lalr) {
+        Some(parser) => parser,
+        None => return ExitCode::FAILURE,
+    };
+
+    let entry_symbol = match args.entry {
+        Some(entry_symbol) => Symbol::from(entry_symbol),
+        None => parser.grammar().start_symbol().clone(),
+    };
+    if !parser.grammar().entry_symbols().contains(&entry_symbol) {
+        eprintln!(
+            "{} symbol {} is not an entry symbol",
+            "argument error:".red().bold(),
+            entry_symbol.green(),
+        );
+        return ExitCode::FAILURE;
+    }
+
+    println!();
+    parser.dump();
+    println!();
+
+    match args.input {
+        Some(input) => {
+            println!("{} {}", ">".cyan().bold(), input);
+            parse(&parser, &entry_symbol, &input)
+        },
+        None => repl(&parser, &entry_symbol),
+    }
+}
+
+fn read(grammar_path: &Path) -> Option<Grammar> {
+    let grammar_directory = grammar_path.parent().map(|parent| parent.to_path_buf());
+    let grammar = match std::fs::read_to_string(grammar_path) {
         Ok(content) => content,
         Err(error) => {
             eprintln!("{} grammar file cannot be read ({})", "io error:".red().bold(), error);
-            return ExitCode::FAILURE;
+            return None;
         },
     };
-    let grammar = match Grammar::parse(&grammar) {
-        Ok(grammar) => grammar,
-        Err(error) => {
-            eprintln!("{} {}", "grammar error:".red().bold(), error);
-            return ExitCode::FAILURE;
+    let mut resolver = FileSystemResolver::new(grammar_directory.unwrap_or_default());
+    match Grammar::parse_all_errors_with_resolver(&grammar, &mut resolver) {
+        Ok(grammar) => Some(grammar),
+        Err(errors) => {
+            for error in errors {
+                eprintln!("{} {}", "grammar error:".red().bold(), error);
+            }
+            None
         },
-    };
+    }
+}
+
+fn load(grammar_path: &Path, lalr: bool) -> Option<Parser> {
+    let grammar = read(grammar_path)?;
+    if let Err(errors) = Parser::check(&grammar) {
+        for error in errors {
+            eprintln!("{} {}", "parser error:".red().bold(), error);
+        }
+        return None;
+    }
+    for warning in grammar.lint() {
+        eprintln!("{} {}", "grammar warning:".yellow().bold(), warning);
+    }
     let parser = {
-        if args.lalr {
+        if lalr {
             match Parser::lalr(grammar) {
                 Ok(parser) => parser,
                 Err(error) => {
@@ -55,7 +224,7 @@ fn main() -> ExitCode {
                     if let ParserError::Conflict { parser, .. } = error {
                         parser.dump();
                     }
-                    return ExitCode::FAILURE;
+                    return None;
                 },
             }
         } else {
@@ -66,26 +235,105 @@ fn main() -> ExitCode {
                     if let ParserError::Conflict { parser, .. } = error {
                         parser.dump();
                     }
-                    return ExitCode::FAILURE;
+                    return None;
                 },
             }
         }
     };
 
-    println!();
-    parser.dump();
-    println!();
+    Some(parser)
+}
 
-    match args.input {
-        Some(input) => {
-            println!("{} {}", ">".cyan().bold(), input);
-            parse(&parser, &input)
+fn compare(
+    grammar_path: &Path,
+    other_grammar_path: &Path,
+    lalr: bool,
+    max_length: usize,
+    max_sentences: usize,
+) -> ExitCode {
+    let (parser, other_parser) = match (load(grammar_path, lalr), load(other_grammar_path, lalr)) {
+        (Some(parser), Some(other_parser)) => (parser, other_parser),
+        _ => return ExitCode::FAILURE,
+    };
+
+    let comparison = parser.compare(&other_parser, max_length, max_sentences);
+    let differences = [
+        (comparison.only_accepted_by_first(), grammar_path, other_grammar_path),
+        (comparison.only_accepted_by_second(), other_grammar_path, grammar_path),
+    ];
+    for (sentences, accepting_grammar_path, rejecting_grammar_path) in differences {
+        for sentence in sentences {
+            println!(
+                "{} {} is accepted by {} but not by {}",
+                "difference:".red().bold(),
+                sentence.iter().map(Token::to_string).collect::<Vec<_>>().join(" ").green(),
+                accepting_grammar_path.display(),
+                rejecting_grammar_path.display(),
+            );
+        }
+    }
+
+    let summary = format!(
+        "{} sentences of up to {} tokens are compared{}",
+        comparison.compared_sentences(),
+        comparison.max_length(),
+        if comparison.is_exhaustive() {
+            ""
+        } else {
+            " (limited by the maximum number of sentences)"
         },
-        None => repl(&parser),
+    );
+    if comparison.is_equivalent() {
+        println!("{} no difference is found, {}", "comparison:".cyan().bold(), summary);
+        ExitCode::SUCCESS
+    } else {
+        println!("{} {}", "comparison:".cyan().bold(), summary);
+        ExitCode::FAILURE
+    }
+}
+
+fn fmt(grammar_paths: &[PathBuf], check: bool) -> ExitCode {
+    let mut exit_code = ExitCode::SUCCESS;
+    for grammar_path in grammar_paths {
+        let grammar = match std::fs::read_to_string(grammar_path) {
+            Ok(content) => content,
+            Err(error) => {
+                eprintln!("{} grammar file cannot be read ({})", "io error:".red().bold(), error);
+                exit_code = ExitCode::FAILURE;
+                continue;
+            },
+        };
+        let formatted_grammar = match Grammar::format(&grammar) {
+            Ok(formatted_grammar) => formatted_grammar,
+            Err(error) => {
+                eprintln!(
+                    "{} {} ({})",
+                    "grammar error:".red().bold(),
+                    error,
+                    grammar_path.display()
+                );
+                exit_code = ExitCode::FAILURE;
+                continue;
+            },
+        };
+        if formatted_grammar == grammar {
+            continue;
+        }
+
+        if check {
+            println!("{} {} is not formatted", "fmt:".cyan().bold(), grammar_path.display());
+            exit_code = ExitCode::FAILURE;
+        } else if let Err(error) = std::fs::write(grammar_path, formatted_grammar) {
+            eprintln!("{} grammar file cannot be written ({})", "io error:".red().bold(), error);
+            exit_code = ExitCode::FAILURE;
+        } else {
+            println!("{} {} is formatted", "fmt:".cyan().bold(), grammar_path.display());
+        }
     }
+    exit_code
 }
 
-fn repl(parser: &Parser) -> ExitCode {
+fn repl(parser: &Parser, entry_symbol: &Symbol) -> ExitCode {
     let mut editor = match DefaultEditor::new() {
         Ok(editor) => editor,
         Err(error) => {
@@ -114,7 +362,7 @@ fn repl(parser: &Parser) -> ExitCode {
                     history_file.as_ref().inspect(|history_file| {
                         editor.save_history(&history_file).ok();
                     });
-                    parse(parser, &line);
+                    parse(parser, entry_symbol, &line);
                 }
             },
             Err(ReadlineError::Interrupted | ReadlineError::Eof) => {
@@ -131,7 +379,7 @@ fn repl(parser: &Parser) -> ExitCode {
     }
 }
 
-fn parse(parser: &Parser, input: &str) -> ExitCode {
+fn parse(parser: &Parser, entry_symbol: &Symbol, input: &str) -> ExitCode {
     let tokens = match parser.tokenize(input) {
         Ok(tokens) => tokens,
         Err(error) => {
@@ -141,7 +389,7 @@ fn parse(parser: &Parser, input: &str) -> ExitCode {
             return ExitCode::FAILURE;
         },
     };
-    match parser.trace(tokens) {
+    match parser.trace_from(entry_symbol, tokens) {
         Ok((parse_trace, parse_tree)) => {
             println!();
             parse_tree.dump();
@@ -158,3 +406,73 @@ fn parse(parser: &Parser, input: &str) -> ExitCode {
         },
     }
 }
+
+fn export(grammar_path: &Path, format: ExportFormat) -> ExitCode {
+    let grammar = match read(grammar_path) {
+        Some(grammar) => grammar,
+        None => return ExitCode::FAILURE,
+    };
+
+    let (exported_grammar, warnings) = match format {
+        ExportFormat::Bison => grammar.to_bison(),
+        ExportFormat::Lark => grammar.to_lark(),
+        ExportFormat::TreeSitter => {
+            let name = grammar_path.file_stem().unwrap_or_default().to_string_lossy();
+            // Names of grammar files can have multiple extensions (e.g., `json.ebnf.lr`).
+            grammar.to_tree_sitter(name.split('.').next().unwrap_or_default())
+        },
+    };
+    for warning in warnings {
+        eprintln!("{} {}", "export warning:".yellow().bold(), warning);
+    }
+    print!("{}", exported_grammar);
+    ExitCode::SUCCESS
+}
+
+fn import(bison_grammar_path: &Path) -> ExitCode {
+    let bison_grammar = match std::fs::read_to_string(bison_grammar_path) {
+        Ok(content) => content,
+        Err(error) => {
+            eprintln!("{} grammar file cannot be read ({})", "io error:".red().bold(), error);
+            return ExitCode::FAILURE;
+        },
+    };
+    let (grammar, warnings) = match Grammar::translate_bison(&bison_grammar) {
+        Ok(grammar_and_warnings) => grammar_and_warnings,
+        Err(error) => {
+            eprintln!("{} {}", "grammar error:".red().bold(), error);
+            return ExitCode::FAILURE;
+        },
+    };
+    for warning in warnings {
+        eprintln!("{} {}", "bison warning:".yellow().bold(), warning);
+    }
+    print!("{}", grammar);
+    ExitCode::SUCCESS
+}
+
+fn railroad(grammar_path: &Path, symbol: Option<Symbol>) -> ExitCode {
+    let grammar = match read(grammar_path) {
+        Some(grammar) => grammar,
+        None => return ExitCode::FAILURE,
+    };
+
+    let svg = match symbol {
+        Some(symbol) => {
+            match grammar.to_railroad_svg_of(&symbol) {
+                Some(svg) => svg,
+                None => {
+                    eprintln!(
+                        "{} symbol {} is not in the grammar",
+                        "argument error:".red().bold(),
+                        symbol.green(),
+                    );
+                    return ExitCode::FAILURE;
+                },
+            }
+        },
+        None => grammar.to_railroad_svg(),
+    };
+    print!("{}", svg);
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,356 @@
+use crate::{
+    prelude::*,
+    transformations::Transformation,
+};
+
+
+impl Grammar {
+    /// Converts the grammar to Chomsky normal form.
+    ///
+    /// Rules of the resulting grammar are either `A -> B C` or `A -> 'a'`,
+    /// apart from the empty rules of the entry symbols, which are not used in any rule.
+    pub fn to_chomsky_normal_form(&self) -> (Grammar, Vec<Rewrite>) {
+        let mut rewrites = Vec::new();
+        let mut record = |(grammar, step_rewrites): (Grammar, Vec<Rewrite>)| {
+            rewrites.extend(step_rewrites);
+            grammar
+        };
+
+        let grammar = record(self.add_start_symbols());
+        let grammar = record(grammar.isolate_tokens());
+        let grammar = record(grammar.binarize_rules());
+        let grammar = record(grammar.remove_empty_rules());
+        let grammar = record(grammar.remove_unit_rules());
+        let grammar = record(grammar.remove_useless_symbols());
+
+        (grammar, rewrites)
+    }
+
+    /// Converts the grammar to Greibach normal form.
+    ///
+    /// Rules of the resulting grammar are `A -> 'a' B C ...`,
+    /// apart from the empty rules of the entry symbols, which are not used in any rule.
+    pub fn to_greibach_normal_form(&self) -> (Grammar, Vec<Rewrite>) {
+        let (chomsky_grammar, mut rewrites) = self.to_chomsky_normal_form();
+        let mut transformation = Transformation::new(&chomsky_grammar);
+
+        // Rules of each symbol are made to start with a token or a later symbol.
+        let symbols = transformation.defined_symbols();
+        for (i, symbol) in symbols.iter().enumerate() {
+            while let Some((index, leading_symbol)) =
+                transformation.rules.iter().enumerate().find_map(|(index, rule)| {
+                    match rule.pattern.first() {
+                        Some(AtomicPattern::Symbol(leading_symbol))
+                            if rule.symbol == *symbol && symbols[..i].contains(leading_symbol) =>
+                        {
+                            Some((index, leading_symbol.clone()))
+                        },
+                        _ => None,
+                    }
+                })
+            {
+                transformation.substitute_leading_symbol(index, &leading_symbol);
+            }
+            transformation.eliminate_direct_left_recursion(symbol, true);
+        }
+
+        // Rules of the last symbol start with a token, so they can be substituted backwards,
+        // and then into the rules of the symbols introduced to eliminate the left recursion.
+        let tails = transformation
+            .defined_symbols()
+            .into_iter()
+            .filter(|symbol| !symbols.contains(symbol))
+            .collect::<Vec<_>>();
+        for symbol in symbols.iter().rev().chain(tails.iter()) {
+            while let Some((index, leading_symbol)) =
+                transformation.rules.iter().enumerate().find_map(|(index, rule)| {
+                    match rule.pattern.first() {
+                        Some(AtomicPattern::Symbol(leading_symbol)) if rule.symbol == *symbol => {
+                            Some((index, leading_symbol.clone()))
+                        },
+                        _ => None,
+                    }
+                })
+            {
+                transformation.substitute_leading_symbol(index, &leading_symbol);
+            }
+        }
+
+        // Substitutions can produce the same rule more than once.
+        let mut defined_rules = IndexSet::new();
+        let rules = std::mem::take(&mut transformation.rules);
+        for rule in rules {
+            if defined_rules.insert((rule.symbol.clone(), rule.pattern.clone())) {
+                transformation.rules.push(rule);
+            } else {
+                transformation.rewrites.push(Rewrite::RuleRemoved { rule });
+            }
+        }
+
+        let (greibach_grammar, greibach_rewrites) = transformation.finish();
+        rewrites.extend(greibach_rewrites);
+
+        let (useful_grammar, useful_rewrites) = greibach_grammar.remove_useless_symbols();
+        rewrites.extend(useful_rewrites);
+
+        (useful_grammar, rewrites)
+    }
+}
+
+impl Grammar {
+    /// Adds a new entry symbol in place of each entry symbol that is used in the rules (START).
+    pub fn add_start_symbols(&self) -> (Grammar, Vec<Rewrite>) {
+        let mut transformation = Transformation::new(self);
+
+        let mut start_rules = Vec::new();
+        let mut entry_symbols = IndexSet::new();
+        for symbol in self.entry_symbols() {
+            let is_used = transformation.rules.iter().any(|rule| {
+                rule.pattern.iter().any(|atomic_pattern| {
+                    matches!(atomic_pattern, AtomicPattern::Symbol(used) if used == symbol)
+                })
+            });
+            if !is_used {
+                entry_symbols.insert(symbol.clone());
+                continue;
+            }
+
+            let start = transformation.fresh_symbol(symbol, "Start");
+            start_rules.push(Rule::new(start.clone(), [AtomicPattern::Symbol(symbol.clone())]));
+            transformation
+                .rewrites
+                .push(Rewrite::StartSymbolAdded { symbol: symbol.clone(), start: start.clone() });
+            entry_symbols.insert(start);
+        }
+        transformation.rules.splice(0..0, start_rules);
+        transformation.entry_symbols = entry_symbols;
+
+        transformation.finish()
+    }
+
+    /// Replaces the tokens in the rules with multiple atomic patterns by new symbols (TERM).
+    pub fn isolate_tokens(&self) -> (Grammar, Vec<Rewrite>) {
+        let mut transformation = Transformation::new(self);
+
+        let mut token_symbols = IndexMap::<Token, Symbol>::new();
+        let mut token_rules = Vec::new();
+        for index in 0..transformation.rules.len() {
+            if transformation.rules[index].pattern.len() < 2 {
+                continue;
+            }
+            for position in 0..transformation.rules[index].pattern.len() {
+                let token = match &transformation.rules[index].pattern[position] {
+                    AtomicPattern::Token(token) => token.clone(),
+                    AtomicPattern::Symbol(_) => continue,
+                };
+                let symbol = match token_symbols.get(&token) {
+                    Some(symbol) => symbol.clone(),
+                    None => {
+                        let symbol = transformation.fresh_symbol_named(&token_symbol_name(&token));
+                        token_rules
+                            .push(Rule::new(symbol.clone(), [AtomicPattern::Token(token.clone())]));
+                        transformation.rewrites.push(Rewrite::TokenReplaced {
+                            token: token.clone(),
+                            symbol: symbol.clone(),
+                        });
+                        token_symbols.insert(token, symbol.clone());
+                        symbol
+                    },
+                };
+                transformation.rules[index].pattern[position] = AtomicPattern::Symbol(symbol);
+            }
+        }
+        transformation.rules.extend(token_rules);
+
+        transformation.finish()
+    }
+
+    /// Splits the rules with more than two atomic patterns into rules with two (BIN).
+    pub fn binarize_rules(&self) -> (Grammar, Vec<Rewrite>) {
+        let mut transformation = Transformation::new(self);
+
+        let mut index = 0;
+        while index < transformation.rules.len() {
+            let rule = transformation.rules[index].clone();
+            if rule.pattern.len() <= 2 {
+                index += 1;
+                continue;
+            }
+
+            let symbols = (0..rule.pattern.len() - 2)
+                .map(|_| transformation.fresh_symbol(&rule.symbol, "Part"))
+                .collect::<Vec<_>>();
+
+            // A -> X1 X2 X3 X4 is split into A -> X1 APart, APart -> X2 APart2, APart2 -> X3 X4.
+            let mut split_rules = Vec::new();
+            for position in 0..symbols.len() {
+                let symbol = if position == 0 { &rule.symbol } else { &symbols[position - 1] };
+                let mut split_rule = Rule::new(symbol.clone(), []);
+                split_rule.push_from_internal(&rule, position);
+                split_rule.push_internal(AtomicPattern::Symbol(symbols[position].clone()));
+                split_rule.precedence = rule.precedence.clone();
+                split_rule.span = rule.span.clone();
+                split_rules.push(split_rule);
+            }
+            let mut last_rule = Rule::new(symbols[symbols.len() - 1].clone(), []);
+            last_rule.extend_internal(&rule, rule.pattern.len() - 2);
+            last_rule.precedence = rule.precedence.clone();
+            last_rule.span = rule.span.clone();
+            split_rules.push(last_rule);
+
+            let split_rules_len = split_rules.len();
+            transformation.rules.splice(index..index + 1, split_rules);
+            transformation.rewrites.push(Rewrite::RuleSplit { rule, symbols });
+            index += split_rules_len;
+        }
+
+        transformation.finish()
+    }
+
+    /// Removes the empty rules of the symbols other than the entry symbols (DEL).
+    ///
+    /// Each rule using nullable symbols is complemented by the rules without them.
+    pub fn remove_empty_rules(&self) -> (Grammar, Vec<Rewrite>) {
+        let mut transformation = Transformation::new(self);
+        let nullable_table = NullableTable::construct(self);
+
+        let mut defined_rules = IndexSet::new();
+        let rules = std::mem::take(&mut transformation.rules);
+        for rule in rules.iter() {
+            // Each nullable symbol can be either kept or omitted.
+            let mut generated_rules = vec![Rule::new(rule.symbol.clone(), [])];
+            for (position, atomic_pattern) in rule.pattern.iter().enumerate() {
+                let is_nullable = matches!(
+                    atomic_pattern,
+                    AtomicPattern::Symbol(symbol) if nullable_table.contains(symbol),
+                );
+                let mut next_generated_rules = Vec::new();
+                for generated_rule in generated_rules {
+                    if is_nullable {
+                        next_generated_rules.push(generated_rule.clone());
+                    }
+                    let mut generated_rule = generated_rule;
+                    generated_rule.push_from_internal(rule, position);
+                    next_generated_rules.push(generated_rule);
+                }
+                generated_rules = next_generated_rules;
+            }
+
+            for mut generated_rule in generated_rules.into_iter().rev() {
+                let is_original = generated_rule.pattern == rule.pattern;
+                let is_allowed = !generated_rule.pattern.is_empty()
+                    || transformation.entry_symbols.contains(&generated_rule.symbol);
+
+                if !is_allowed
+                    || !defined_rules
+                        .insert((generated_rule.symbol.clone(), generated_rule.pattern.clone()))
+                {
+                    if is_original {
+                        transformation.rewrites.push(Rewrite::RuleRemoved { rule: rule.clone() });
+                    }
+                    continue;
+                }
+
+                if is_original {
+                    transformation.rules.push(rule.clone());
+                } else {
+                    generated_rule.precedence = rule.precedence.clone();
+                    generated_rule.span = rule.span.clone();
+                    transformation.rules.push(generated_rule.clone());
+                    transformation.rewrites.push(Rewrite::RuleAdded { rule: generated_rule });
+                }
+            }
+        }
+
+        transformation.remove_rules_using_undefined_symbols();
+        transformation.finish()
+    }
+
+    /// Replaces the rules with a single symbol by the rules of the symbol (UNIT).
+    pub fn remove_unit_rules(&self) -> (Grammar, Vec<Rewrite>) {
+        let mut transformation = Transformation::new(self);
+
+        for symbol in transformation.defined_symbols() {
+            for rule in self.rules().iter().filter(|rule| rule.symbol == symbol) {
+                if let [AtomicPattern::Symbol(unit_symbol)] = rule.pattern.as_slice() {
+                    let rewrite = if *unit_symbol == symbol {
+                        Rewrite::RuleRemoved { rule: rule.clone() }
+                    } else {
+                        Rewrite::RulesSubstituted {
+                            rule: rule.clone(),
+                            symbol: unit_symbol.clone(),
+                        }
+                    };
+                    transformation.rewrites.push(rewrite);
+                }
+            }
+
+            let mut defined_patterns = IndexSet::new();
+            let mut rules = Vec::new();
+            for unit_symbol in self.unit_derivable_symbols_internal(&symbol) {
+                for rule in self.rules().iter().filter(|rule| rule.symbol == *unit_symbol) {
+                    if matches!(rule.pattern.as_slice(), [AtomicPattern::Symbol(_)]) {
+                        continue;
+                    }
+                    if defined_patterns.insert(rule.pattern.clone()) {
+                        let mut rule = rule.clone();
+                        rule.symbol = symbol.clone();
+                        rules.push(rule);
+                    }
+                }
+            }
+            transformation.replace_rules_of(&symbol, rules);
+        }
+
+        transformation.remove_rules_using_undefined_symbols();
+        transformation.finish()
+    }
+}
+
+
+impl Transformation<'_> {
+    /// Removes the rules using symbols without any rules left, until there are none.
+    fn remove_rules_using_undefined_symbols(&mut self) {
+        let mut done = false;
+        while !done {
+            done = true;
+            let defined_symbols = self.defined_symbols();
+            let rules = std::mem::take(&mut self.rules);
+            for rule in rules {
+                let uses_undefined_symbol = rule.pattern.iter().any(|atomic_pattern| {
+                    matches!(
+                        atomic_pattern,
+                        AtomicPattern::Symbol(symbol) if !defined_symbols.contains(symbol),
+                    )
+                });
+                if uses_undefined_symbol {
+                    self.rewrites.push(Rewrite::RuleRemoved { rule });
+                    done = false;
+                } else {
+                    self.rules.push(rule);
+                }
+            }
+        }
+    }
+}
+
+
+/// Creates the name of the symbol a token is replaced by (e.g., `'if'` to `IfToken`).
+fn token_symbol_name(token: &Token) -> SmolStr {
+    let name = match token {
+        Token::Constant(constant_token) => constant_token.as_str(),
+        Token::Regex(regex_token) => regex_token.as_str(),
+        Token::Eof => "",
+    };
+    let mut characters = name.chars().filter(|character| character.is_ascii_alphanumeric());
+    match characters.next() {
+        Some(first_character) => {
+            format_smolstr!(
+                "{}{}Token",
+                first_character.to_ascii_uppercase(),
+                characters.collect::<String>(),
+            )
+        },
+        None => SmolStr::new("Token"),
+    }
+}
@@ -7,6 +7,7 @@ use crate::prelude::*;
 #[derive(Debug)]
 pub struct Parser {
     grammar: Grammar,
+    nullable_table: NullableTable,
     first_table: FirstTable,
     follow_table: FollowTable,
     automaton: Automaton,
@@ -19,12 +20,20 @@ impl Parser {
     pub fn lr(grammar: Grammar) -> Result<Parser, ParserError> {
         Parser::check_grammar_internal(&grammar)?;
 
-        let first_table = FirstTable::construct(&grammar);
-        let follow_table = FollowTable::construct(&grammar, &first_table);
-        let automaton = Automaton::construct(&grammar, &first_table);
+        let nullable_table = NullableTable::construct(&grammar);
+        let first_table = FirstTable::construct(&grammar, &nullable_table);
+        let follow_table = FollowTable::construct(&grammar, &nullable_table, &first_table);
+        let automaton = Automaton::construct(&grammar, &nullable_table, &first_table);
         let parsing_tables = ParsingTables::construct(&grammar, &follow_table, &automaton)?;
 
-        let parser = Parser { grammar, first_table, follow_table, automaton, parsing_tables };
+        let parser = Parser {
+            grammar,
+            nullable_table,
+            first_table,
+            follow_table,
+            automaton,
+            parsing_tables,
+        };
         parser.check_conflicts_internal()
     }
 
@@ -32,14 +41,30 @@ impl Parser {
     pub fn lalr(grammar: Grammar) -> Result<Parser, ParserError> {
         Parser::check_grammar_internal(&grammar)?;
 
-        let first_table = FirstTable::construct(&grammar);
-        let follow_table = FollowTable::construct(&grammar, &first_table);
-        let automaton = Automaton::construct(&grammar, &first_table).to_lalr();
+        let nullable_table = NullableTable::construct(&grammar);
+        let first_table = FirstTable::construct(&grammar, &nullable_table);
+        let follow_table = FollowTable::construct(&grammar, &nullable_table, &first_table);
+        let automaton = Automaton::construct(&grammar, &nullable_table, &first_table).to_lalr();
         let parsing_tables = ParsingTables::construct(&grammar, &follow_table, &automaton)?;
 
-        let parser = Parser { grammar, first_table, follow_table, automaton, parsing_tables };
+        let parser = Parser {
+            grammar,
+            nullable_table,
+            first_table,
+            follow_table,
+            automaton,
+            parsing_tables,
+        };
         parser.check_conflicts_internal()
     }
+
+    /// Checks a grammar for all the errors that prevent constructing a parser of it.
+    ///
+    /// Conflicts are not detected, as they depend on the kind of the parser.
+    pub fn check(grammar: &Grammar) -> Result<(), Vec<ParserError>> {
+        let errors = Parser::grammar_errors_internal(grammar);
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
 }
 #[cfg(feature = "wasm")]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
@@ -50,6 +75,54 @@ impl Parser {
     pub fn new_lalr_wasm(grammar: Grammar) -> Result<Parser, WasmParserError> {
         Parser::lalr(grammar).map_err(WasmParserError::new)
     }
+    pub fn check_wasm(grammar: &Grammar) -> Result<(), JsValue> {
+        match Parser::check(grammar) {
+            Ok(()) => Ok(()),
+            Err(errors) => Err(serde_wasm_bindgen::to_value(&errors)?),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Parser {
+    fn deserialize<D>(deserializer: D) -> Result<Parser, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        /// Parser that is not yet checked against its grammar.
+        #[derive(Deserialize)]
+        #[serde(crate = "serde_renamed")]
+        struct UncheckedParser {
+            grammar: Grammar,
+            nullable_table: NullableTable,
+            first_table: FirstTable,
+            follow_table: FollowTable,
+            automaton: Automaton,
+            parsing_tables: ParsingTables,
+        }
+
+        let UncheckedParser {
+            grammar,
+            nullable_table,
+            first_table,
+            follow_table,
+            automaton,
+            parsing_tables,
+        } = UncheckedParser::deserialize(deserializer)?;
+
+        Parser::check_grammar_internal(&grammar).map_err(D::Error::custom)?;
+        let parser = Parser {
+            grammar,
+            nullable_table,
+            first_table,
+            follow_table,
+            automaton,
+            parsing_tables,
+        };
+        parser.check_tables_internal().map_err(D::Error::custom)?;
+
+        Ok(parser)
+    }
 }
 
 
@@ -59,6 +132,11 @@ impl Parser {
         &self.grammar
     }
 
+    /// Gets the nullable table of the symbols in the grammar of the parser.
+    pub fn nullable_table(&self) -> &NullableTable {
+        &self.nullable_table
+    }
+
     /// Gets the first table of the symbols in the grammar of the parser.
     pub fn first_table(&self) -> &FirstTable {
         &self.first_table
@@ -93,6 +171,9 @@ impl Parser {
 #[cfg(feature = "wasm")]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Parser {
+    pub fn nullable_table_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.nullable_table)?)
+    }
     pub fn first_table_wasm(&self) -> Result<JsValue, JsValue> {
         Ok(serde_wasm_bindgen::to_value(&self.first_table)?)
     }
@@ -115,44 +196,122 @@ impl Parser {
 
 impl Parser {
     /// Tokenizes an input into a stream of tokens and their corresponding input slices.
+    ///
+    /// The longest match wins. Among matches of the same length, the token with the highest
+    /// priority wins, where constant tokens have priority `0` and win over regular expressions
+    /// of the same priority, and regular expressions of the same priority win in declaration order.
+    /// Lastly, matches of regular expressions that are keywords are tokenized as the keywords.
+    ///
+    /// Only the tokens of the current lexer mode are matched, and ignored input is only skipped
+    /// in the default mode. Tokens that trigger transitions push, pop or switch the current mode.
     pub fn tokenize<'i>(
         &self,
         input: &'i str,
     ) -> Result<Vec<(Spanned<Token>, &'i str)>, ParsingError> {
+        self.tokenize_with_trivia(input).map(|(tokens, _)| tokens)
+    }
+
+    /// Tokenizes an input into a stream of tokens and a stream of ignored input slices.
+    #[allow(clippy::type_complexity)]
+    pub fn tokenize_with_trivia<'i>(
+        &self,
+        input: &'i str,
+    ) -> Result<(Vec<(Spanned<Token>, &'i str)>, Vec<Spanned<&'i str>>), ParsingError> {
         let mut tokens: Vec<(Spanned<Token>, &'i str)> = Vec::new();
+        let mut trivia: Vec<Spanned<&'i str>> = Vec::new();
 
         let mut ordered_constant_tokens = self.grammar.constant_tokens().iter().collect::<Vec<_>>();
         ordered_constant_tokens.sort_by_key(|token| token.len());
 
-        let mut remaining_input = input.trim_start();
-        let mut offset = input.len() - remaining_input.len();
-        let (initial_new_lines, initial_newline_offset) = utils::count_new_lines(&input[..offset]);
-        let mut line = initial_new_lines + 1;
-        let mut last_newline_offset = initial_newline_offset.unwrap_or(0);
-        let mut column = utils::count_col_position(&input[last_newline_offset..offset]);
-        while !remaining_input.is_empty() {
+        let mut modes = vec![SmolStr::from(Grammar::DEFAULT_MODE)];
+
+        let mut offset = 0;
+        let mut line = 1;
+        let mut last_newline_offset = 0;
+        loop {
+            let mode = modes.last().unwrap().clone();
+
+            let mut remaining_input = &input[offset..];
+            let mut column = utils::count_col_position(&input[last_newline_offset..offset]);
+
+            loop {
+                let ignored_len = if mode == Grammar::DEFAULT_MODE {
+                    self.ignored_prefix_len(remaining_input)
+                } else {
+                    0
+                };
+                if ignored_len == 0 {
+                    break;
+                }
+
+                let ignored_slice = &remaining_input[..ignored_len];
+                let span = Span { offset, len: ignored_len, line, column, source: None };
+                trivia.push(Spanned::new(ignored_slice, span));
+
+                let (ignored_lines, ignored_newline_offset) = utils::count_new_lines(ignored_slice);
+                line += ignored_lines;
+                if let Some(ignored_newline_offset) = ignored_newline_offset {
+                    last_newline_offset = offset + ignored_newline_offset;
+                }
+
+                offset += ignored_len;
+                remaining_input = &input[offset..];
+                column = utils::count_col_position(&input[last_newline_offset..offset]);
+            }
+
+            if remaining_input.is_empty() {
+                let eof =
+                    Spanned::new(Token::Eof, Span { offset, len: 0, line, column, source: None });
+                tokens.push((eof, "\0"));
+                break;
+            }
+
             let mut matching_token = None;
             let mut matching_slice = "";
+            let mut matching_priority = 0;
 
             for token in ordered_constant_tokens.iter().rev() {
-                if remaining_input.starts_with(token.as_str()) {
+                if !self.grammar.recognizes(&mode, &Token::Constant((*token).clone())) {
+                    continue;
+                }
+                if let Some(slice) = self.constant_token_prefix(remaining_input, token) {
                     matching_token = Some(Token::Constant((*token).clone()));
-                    matching_slice = &remaining_input[..token.len()];
+                    matching_slice = slice;
                     break;
                 }
             }
 
             for (regex_token, regex) in self.grammar.regular_expressions() {
+                if !self.grammar.recognizes(&mode, &Token::Regex(regex_token.clone())) {
+                    continue;
+                }
                 if let Some(match_info) = regex.find(remaining_input) {
-                    if match_info.len() > matching_slice.len() {
+                    let priority = self.grammar.priority_of(regex_token);
+                    let is_better_match = match_info.len() > matching_slice.len()
+                        || (match_info.len() == matching_slice.len()
+                            && priority > matching_priority);
+                    if is_better_match && !match_info.is_empty() {
                         matching_token = Some(Token::Regex(regex_token.clone()));
                         matching_slice = &remaining_input[..match_info.end()];
+                        matching_priority = priority;
+                    }
+                }
+            }
+
+            if let Some(Token::Regex(_)) = &matching_token {
+                let keyword = self.grammar.keywords().iter().find(|keyword| {
+                    self.constant_token_prefix(matching_slice, keyword) == Some(matching_slice)
+                });
+                if let Some(keyword) = keyword {
+                    let keyword = Token::Constant(keyword.clone());
+                    if self.grammar.recognizes(&mode, &keyword) {
+                        matching_token = Some(keyword);
                     }
                 }
             }
 
             if matching_token.is_none() {
-                let span = Span { offset, len: 1, line, column };
+                let span = Span { offset, len: 1, line, column, source: None };
                 return Err(ParsingError::UnknownToken {
                     token: format_smolstr!("{}", remaining_input.chars().next().unwrap()),
                     span,
@@ -164,6 +323,7 @@ impl Parser {
                 len: matching_slice.len(),
                 line,
                 column,
+                source: None,
             });
 
             let (slice_lines, slice_newline_offset) = utils::count_new_lines(matching_slice);
@@ -173,26 +333,25 @@ impl Parser {
                 last_newline_offset = offset + slice_newline_offset
             }
 
-            tokens.push((token, matching_slice));
-            remaining_input = remaining_input[matching_slice.len()..].trim_start();
-
-            // add back to the offset the whitespace that was trimmed
-            let old_offset = offset;
-            offset = input.len() - remaining_input.len();
-            let whitespace = &input[old_offset..offset];
-            let (whitespace_lines, whitespace_newline_offset) = utils::count_new_lines(whitespace);
-            line += whitespace_lines;
-
-            if let Some(whitespace_newline_offset) = whitespace_newline_offset {
-                last_newline_offset = old_offset + whitespace_newline_offset;
+            match self.grammar.transition_of(&mode, &token) {
+                Some(ModeTransition::Push(target)) => {
+                    modes.push(target.clone());
+                },
+                Some(ModeTransition::Pop) if modes.len() > 1 => {
+                    modes.pop();
+                },
+                Some(ModeTransition::Switch(target)) => {
+                    *modes.last_mut().unwrap() = target.clone();
+                },
+                // Popping the initial mode is ignored, so tokens can be unbalanced.
+                _ => {},
             }
-            // skip the newline character
-            column = utils::count_col_position(&input[last_newline_offset..offset]);
+
+            tokens.push((token, matching_slice));
+            offset += matching_slice.len();
         }
-        let eof = Spanned::new(Token::Eof, Span { offset, len: 0, line, column });
-        tokens.push((eof, "\0"));
 
-        Ok(tokens)
+        Ok((tokens, trivia))
     }
 
     /// Parses a tokenized input.
@@ -200,7 +359,16 @@ impl Parser {
         &self,
         tokens: Vec<(Spanned<Token>, &'i str)>,
     ) -> Result<Tree<'i>, ParsingError> {
-        self.parse_and_trace_internal(tokens, false).map(|(_, tree)| tree)
+        self.parse_from(self.grammar.start_symbol(), tokens)
+    }
+
+    /// Parses a tokenized input starting from an entry symbol.
+    pub fn parse_from<'i>(
+        &self,
+        entry_symbol: &Symbol,
+        tokens: Vec<(Spanned<Token>, &'i str)>,
+    ) -> Result<Tree<'i>, ParsingError> {
+        self.parse_and_trace_internal(entry_symbol, tokens, false).map(|(_, tree)| tree)
     }
 
     /// Traces the parsing of a tokenized input.
@@ -208,7 +376,16 @@ impl Parser {
         &self,
         tokens: Vec<(Spanned<Token>, &'i str)>,
     ) -> Result<(Trace<'i>, Tree<'i>), ParsingError> {
-        self.parse_and_trace_internal(tokens, true)
+        self.trace_from(self.grammar.start_symbol(), tokens)
+    }
+
+    /// Traces the parsing of a tokenized input starting from an entry symbol.
+    pub fn trace_from<'i>(
+        &self,
+        entry_symbol: &Symbol,
+        tokens: Vec<(Spanned<Token>, &'i str)>,
+    ) -> Result<(Trace<'i>, Tree<'i>), ParsingError> {
+        self.parse_and_trace_internal(entry_symbol, tokens, true)
     }
 }
 
@@ -221,24 +398,46 @@ impl Parser {
             Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
         }
     }
-    pub fn parse_wasm(&self, input: &str) -> Result<JsValue, JsValue> {
+    pub fn tokenize_with_trivia_wasm(&self, input: &str) -> Result<JsValue, JsValue> {
+        match self.tokenize_with_trivia(input) {
+            Ok(tokens_and_trivia) => Ok(serde_wasm_bindgen::to_value(&tokens_and_trivia)?),
+            Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
+        }
+    }
+    pub fn parse_wasm(
+        &self,
+        input: &str,
+        entry_symbol: Option<String>,
+    ) -> Result<JsValue, JsValue> {
         let tokens = self.tokenize(input);
         let tokens = match tokens {
             Ok(tokens) => tokens,
             Err(error) => return Err(serde_wasm_bindgen::to_value(&error)?),
         };
-        match self.parse(tokens) {
+        let entry_symbol = match entry_symbol {
+            Some(entry_symbol) => Symbol::from(entry_symbol),
+            None => self.grammar.start_symbol().clone(),
+        };
+        match self.parse_from(&entry_symbol, tokens) {
             Ok(tree) => Ok(serde_wasm_bindgen::to_value(&tree)?),
             Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
         }
     }
-    pub fn trace_wasm(&self, input: &str) -> Result<Vec<JsValue>, JsValue> {
+    pub fn trace_wasm(
+        &self,
+        input: &str,
+        entry_symbol: Option<String>,
+    ) -> Result<Vec<JsValue>, JsValue> {
         let tokens = self.tokenize(input);
         let tokens = match tokens {
             Ok(tokens) => tokens,
             Err(error) => return Err(serde_wasm_bindgen::to_value(&error)?),
         };
-        match self.trace(tokens) {
+        let entry_symbol = match entry_symbol {
+            Some(entry_symbol) => Symbol::from(entry_symbol),
+            None => self.grammar.start_symbol().clone(),
+        };
+        match self.trace_from(&entry_symbol, tokens) {
             Ok((trace, tree)) => {
                 let trace = serde_wasm_bindgen::to_value(&trace)?;
                 let tree = serde_wasm_bindgen::to_value(&tree)?;
@@ -253,26 +452,50 @@ impl Parser {
 impl Parser {
     /// Internal grammar checks.
     fn check_grammar_internal(grammar: &Grammar) -> Result<(), ParserError> {
+        match Parser::grammar_errors_internal(grammar).into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Internal collection of the errors of a grammar.
+    fn grammar_errors_internal(grammar: &Grammar) -> Vec<ParserError> {
         if grammar.rules().is_empty() {
-            return Err(ParserError::EmptyGrammar);
+            return vec![ParserError::EmptyGrammar];
+        }
+        Parser::undefined_errors_internal(grammar)
+    }
+
+    /// Internal collection of the undefined symbols, regex tokens and entry symbols of a grammar.
+    pub(crate) fn undefined_errors_internal(grammar: &Grammar) -> Vec<ParserError> {
+        let mut errors = Vec::new();
+        for entry_symbol in grammar.entry_symbols() {
+            if !grammar.symbols().contains(entry_symbol) {
+                errors.push(ParserError::UndefinedEntrySymbol {
+                    symbol: entry_symbol.clone(),
+                    span: grammar.entry_symbol_spans().get(entry_symbol).cloned(),
+                });
+            }
         }
         for rule in grammar.rules() {
-            for atomic_pattern in rule.pattern() {
+            for (atomic_pattern, span) in rule.pattern().iter().zip(rule.pattern_spans()) {
                 match atomic_pattern {
                     AtomicPattern::Symbol(symbol) => {
                         if !grammar.symbols().contains(symbol) {
-                            return Err(ParserError::UndefinedSymbol {
+                            errors.push(ParserError::UndefinedSymbol {
                                 symbol: symbol.clone(),
                                 rule: rule.clone(),
+                                span: span.clone(),
                             });
                         }
                     },
                     AtomicPattern::Token(token) => {
                         if let Token::Regex(regex_token) = token {
                             if !grammar.regular_expressions().contains_key(regex_token) {
-                                return Err(ParserError::UndefinedRegexToken {
+                                errors.push(ParserError::UndefinedRegexToken {
                                     regex_token: regex_token.clone(),
                                     rule: rule.clone(),
+                                    span: span.clone(),
                                 });
                             }
                         }
@@ -280,7 +503,46 @@ impl Parser {
                 }
             }
         }
-        Ok(())
+        errors
+    }
+
+    /// Internal matching of a constant token at the start of an input.
+    fn constant_token_prefix<'i>(
+        &self,
+        input: &'i str,
+        constant_token: &ConstantToken,
+    ) -> Option<&'i str> {
+        if !self.grammar.is_case_insensitive(constant_token) {
+            return input
+                .starts_with(constant_token.as_str())
+                .then(|| &input[..constant_token.len()]);
+        }
+
+        // Case insensitive tokens are compared char by char, since cases can differ in length.
+        let mut len = 0;
+        let mut input_chars = input.chars();
+        for token_char in constant_token.chars() {
+            let input_char = input_chars.next()?;
+            if !input_char.to_lowercase().eq(token_char.to_lowercase()) {
+                return None;
+            }
+            len += input_char.len_utf8();
+        }
+        Some(&input[..len])
+    }
+
+    /// Internal computation of the length of the ignored prefix of an input.
+    fn ignored_prefix_len(&self, input: &str) -> usize {
+        let ignored_regular_expressions = self.grammar.ignored_regular_expressions();
+        if ignored_regular_expressions.is_empty() {
+            return input.len() - input.trim_start().len();
+        }
+        ignored_regular_expressions
+            .iter()
+            .filter_map(|regex| regex.find(input))
+            .map(|match_info| match_info.len())
+            .max()
+            .unwrap_or(0)
     }
 
     /// Internal conflict checks.
@@ -289,21 +551,96 @@ impl Parser {
             for (token, actions) in action_map.iter() {
                 if actions.len() > 1 {
                     let token = token.clone();
+                    let spans = self.conflict_spans_internal(state, &token);
                     let parser = Box::new(self);
-                    return Err(ParserError::Conflict { parser, state, token });
+                    return Err(ParserError::Conflict { parser, state, token, spans });
                 }
             }
         }
         Ok(self)
     }
 
+    /// Internal computation of the spans of the rules to reduce and the tokens to shift in a conflict.
+    fn conflict_spans_internal(&self, state: usize, token: &Token) -> Vec<Span> {
+        let mut spans = Vec::new();
+        for item in self.automaton.states()[state].items() {
+            let rule = item.rule();
+            let span = if item.dot() == rule.pattern().len() {
+                if !item.lookahead().contains(token) {
+                    continue;
+                }
+                rule.span()
+            } else {
+                if rule.pattern()[item.dot()] != AtomicPattern::Token(token.clone()) {
+                    continue;
+                }
+                rule.pattern_spans()[item.dot()].as_ref()
+            };
+            if let Some(span) = span {
+                if !spans.contains(span) {
+                    spans.push(span.clone());
+                }
+            }
+        }
+        spans
+    }
+
+    /// Internal checks of the tables against the grammar (e.g., for deserialized parsers).
+    #[cfg(feature = "serde")]
+    fn check_tables_internal(&self) -> Result<(), SmolStr> {
+        let grammar = &self.grammar;
+
+        let nullable_table = NullableTable::construct(grammar);
+        if self.nullable_table != nullable_table {
+            return Err("nullable table does not match the grammar".into());
+        }
+        let first_table = FirstTable::construct(grammar, &nullable_table);
+        if self.first_table != first_table {
+            return Err("first table does not match the grammar".into());
+        }
+        let follow_table = FollowTable::construct(grammar, &nullable_table, &first_table);
+        if self.follow_table != follow_table {
+            return Err("follow table does not match the grammar".into());
+        }
+
+        // Parser can be an LR(1) or an LALR(1) parser, so its automaton is compared with both.
+        let same_automaton = |automaton: &Automaton| {
+            self.automaton.start_states() == automaton.start_states()
+                && self.automaton.states().len() == automaton.states().len()
+                && self.automaton.states().iter().zip(automaton.states()).all(
+                    |(state, expected)| {
+                        state.id() == expected.id()
+                            && state.items() == expected.items()
+                            && state.transitions() == expected.transitions()
+                    },
+                )
+        };
+        let automaton = Automaton::construct(grammar, &nullable_table, &first_table);
+        if !same_automaton(&automaton) && !same_automaton(&automaton.to_lalr()) {
+            return Err("automaton does not match the grammar".into());
+        }
+
+        match ParsingTables::construct(grammar, &follow_table, &self.automaton) {
+            Ok(parsing_tables) if self.parsing_tables == parsing_tables => Ok(()),
+            _ => Err("parsing tables do not match the grammar".into()),
+        }
+    }
+
     /// Internal parsing logic.
     fn parse_and_trace_internal<'i>(
         &self,
+        entry_symbol: &Symbol,
         mut tokens: Vec<(Spanned<Token>, &'i str)>,
         traced: bool,
     ) -> Result<(Trace<'i>, Tree<'i>), ParsingError> {
-        let mut state_stack = vec![0];
+        let start_state = match self.automaton.start_states().get(entry_symbol) {
+            Some(start_state) => *start_state,
+            None => {
+                return Err(ParsingError::UnknownEntrySymbol { symbol: entry_symbol.clone() });
+            },
+        };
+
+        let mut state_stack = vec![start_state];
         let mut tree_stack = vec![];
         let mut remaining_tokens = {
             tokens.reverse();
@@ -353,11 +690,8 @@ impl Parser {
             }
 
             match action_to_take {
-                Action::Accept { .. } => {
-                    let parse_tree = Tree::NonTerminal {
-                        symbol: self.grammar.start_symbol().clone(),
-                        pattern: tree_stack,
-                    };
+                Action::Accept => {
+                    let parse_tree = tree_stack.pop().unwrap();
                     return Ok((trace, parse_tree));
                 },
                 Action::Shift { next_state } => {
@@ -373,8 +707,9 @@ impl Parser {
                     let symbol = rule.symbol().clone();
                     let pattern =
                         tree_stack.split_off(tree_stack.len().saturating_sub(pattern_length));
+                    let labels = rule.labels().to_vec();
 
-                    tree_stack.push(Tree::NonTerminal { symbol, pattern });
+                    tree_stack.push(Tree::NonTerminal { symbol, pattern, labels });
 
                     let new_state_stack_len = state_stack.len().saturating_sub(pattern_length);
                     state_stack.truncate(new_state_stack_len);
@@ -406,6 +741,17 @@ impl Parser {
                 let mut pretty_rules = Table::new();
                 pretty_rules.set_format(*prettytable::format::consts::FORMAT_CLEAN);
 
+                let declarations =
+                    self.grammar.precedences().iter().chunk_by(|(_, precedence)| **precedence);
+                for (precedence, tokens) in declarations.into_iter() {
+                    let tokens = tokens.map(|(token, _)| token).join(" ");
+                    pretty_rules
+                        .add_row(row![r->"", format!("{} {}", precedence.associativity(), tokens)]);
+                }
+                if !self.grammar.precedences().is_empty() {
+                    pretty_rules.add_row(row![r->"", ""]);
+                }
+
                 for (rule_index, rule) in self.grammar.rules().iter().enumerate() {
                     pretty_rules.add_row(row![r->format!("{})", rule_index + 1), rule]);
                 }
@@ -413,7 +759,46 @@ impl Parser {
                     pretty_rules.add_row(row![r->"", ""]);
                 }
                 for (regex_token, regex) in self.grammar.regular_expressions().iter() {
-                    pretty_rules.add_row(row![r->"", format!("{} -> /{}/", regex_token, regex)]);
+                    let mut regex_string =
+                        format!("{} -> /{}/", regex_token, utils::escape_regex(regex.as_str()));
+                    if let Some(priority) = self.grammar.priorities().get(regex_token) {
+                        regex_string += &format!(" %prio {}", priority);
+                    }
+                    pretty_rules.add_row(row![r->"", regex_string]);
+                }
+                if !self.grammar.keywords().is_empty() {
+                    pretty_rules.add_row(row![r->"", ""]);
+                    pretty_rules.add_row(
+                        row![r->"", format!("%keyword {}", self.grammar.keywords().iter().join(" "))],
+                    );
+                }
+                if self.grammar.case_insensitive() {
+                    pretty_rules.add_row(row![r->"", "%caseless"]);
+                } else if !self.grammar.case_insensitive_tokens().is_empty() {
+                    let tokens = self.grammar.case_insensitive_tokens().iter().join(" ");
+                    pretty_rules.add_row(row![r->"", format!("%caseless {}", tokens)]);
+                }
+                if !self.grammar.modes().is_empty() || !self.grammar.transitions().is_empty() {
+                    pretty_rules.add_row(row![r->"", ""]);
+                }
+                for (mode, tokens) in self.grammar.modes().iter() {
+                    pretty_rules.add_row(
+                        row![r->"", format!("%mode {} {}", mode, tokens.iter().join(" "))],
+                    );
+                }
+                for (mode, transitions) in self.grammar.transitions().iter() {
+                    for (token, transition) in transitions.iter() {
+                        pretty_rules
+                            .add_row(row![r->"", format!("%on {} {} {}", mode, token, transition)]);
+                    }
+                }
+                if !self.grammar.ignored_regular_expressions().is_empty() {
+                    pretty_rules.add_row(row![r->"", ""]);
+                }
+                for regex in self.grammar.ignored_regular_expressions().iter() {
+                    pretty_rules.add_row(
+                        row![r->"", format!("%ignore /{}/", utils::escape_regex(regex.as_str()))],
+                    );
                 }
 
                 pretty_grammar.add_row(row![pretty_rules]);
@@ -421,13 +806,41 @@ impl Parser {
 
             pretty_grammar.printstd();
         }
+        {
+            let warnings = self.grammar.lint();
+            if !warnings.is_empty() {
+                let mut pretty_warnings = Table::new();
+
+                pretty_warnings.add_row(row![cbFy->"Warnings"]);
+                for warning in warnings {
+                    pretty_warnings.add_row(row![warning]);
+                }
+
+                pretty_warnings.printstd();
+            }
+        }
         {
             let mut pretty_first_and_follow_tables = Table::new();
 
             pretty_first_and_follow_tables
                 .add_row(row![cbFy->"Symbol", cbFy->"First Set", cbFy->"Follow Set"]);
-            for (symbol, first_set) in self.first_table.iter() {
+            let symbols = self
+                .first_table
+                .keys()
+                .chain(self.nullable_table.iter())
+                .unique()
+                .collect::<Vec<_>>();
+            for symbol in symbols {
                 let first_set_formatted = {
+                    let mut first_set = match self.first_table.get(symbol) {
+                        Some(first_set) => {
+                            first_set.iter().map(|token| token.to_string()).collect()
+                        },
+                        None => vec![],
+                    };
+                    if self.nullable_table.contains(symbol) {
+                        first_set.insert(0, "ε".to_owned());
+                    }
                     if first_set.is_empty() {
                         "{}".to_owned()
                     } else {
@@ -657,5 +1070,34 @@ impl Parser {
 
             pretty_parsing_tables.printstd()
         }
+        if !self.parsing_tables.resolved_conflicts().is_empty() {
+            let mut pretty_resolved_conflicts = Table::new();
+
+            pretty_resolved_conflicts.add_row(row![cbFyH5->"Resolved Conflicts"]);
+            pretty_resolved_conflicts.add_row(row![
+                cbFy->"State",
+                cbFy->"Token",
+                cbFy->"Conflict",
+                cbFy->"Resolution",
+                cbFy->"Reason",
+            ]);
+            for resolved_conflict in self.parsing_tables.resolved_conflicts() {
+                let conflict =
+                    format!("{}, {}", resolved_conflict.shift(), resolved_conflict.reduce());
+                let resolution = match resolved_conflict.resolution() {
+                    Some(action) => action.to_string(),
+                    None => "error".to_owned(),
+                };
+                pretty_resolved_conflicts.add_row(row![
+                    resolved_conflict.state(),
+                    resolved_conflict.token(),
+                    conflict,
+                    c->resolution,
+                    resolved_conflict.reason(),
+                ]);
+            }
+
+            pretty_resolved_conflicts.printstd();
+        }
     }
 }
@@ -0,0 +1,521 @@
+use crate::prelude::*;
+
+
+/// Radius of the arcs connecting the tracks of railroad diagrams.
+const ARC_RADIUS: i32 = 10;
+
+/// Minimum vertical separation between the tracks of railroad diagrams.
+const VERTICAL_SEPARATION: i32 = 8;
+
+/// Horizontal separation between the elements of sequences in railroad diagrams.
+const HORIZONTAL_SEPARATION: i32 = 10;
+
+/// Width of a character in the boxes of railroad diagrams.
+const CHARACTER_WIDTH: i32 = 9;
+
+/// Horizontal padding around the text in the boxes of railroad diagrams.
+const BOX_PADDING: i32 = 10;
+
+/// Height of the boxes of railroad diagrams.
+const BOX_HEIGHT: i32 = 24;
+
+/// Width of the markers at the start and the end of railroad diagrams.
+const MARKER_WIDTH: i32 = 20;
+
+/// Height of the titles of railroad diagrams.
+const TITLE_HEIGHT: i32 = 30;
+
+/// Padding around railroad diagrams.
+const PADDING: i32 = 20;
+
+/// Style of the rendered railroad diagrams.
+const STYLE: &str = "
+svg { background-color: white; }
+path { fill: none; stroke: black; stroke-width: 2; }
+rect { stroke: black; stroke-width: 2; }
+rect.symbol { fill: #e3f2fd; }
+rect.constant-token { fill: #fff3e0; }
+rect.regex-token { fill: #f3e5f5; }
+text { font: 14px monospace; text-anchor: middle; dominant-baseline: central; }
+text.regex-token { font-style: italic; }
+text.title { font-weight: bold; text-anchor: start; }
+";
+
+
+impl Grammar {
+    /// Renders railroad diagrams of the symbols of the grammar to an SVG image.
+    ///
+    /// All rules of a symbol are drawn in a single diagram, auxiliary symbols are drawn
+    /// as the EBNF constructs they are generated for, and lists (e.g., `X -> X ',' Y | Y`)
+    /// are drawn as loops.
+    pub fn to_railroad_svg(&self) -> String {
+        let diagrams = self
+            .symbols()
+            .iter()
+            .filter(|symbol| !self.auxiliary_symbols().contains_key(*symbol))
+            .map(|symbol| (symbol, self.symbol_diagram_internal(symbol)))
+            .collect_vec();
+        render_svg(&diagrams)
+    }
+
+    /// Renders the railroad diagram of a symbol of the grammar to an SVG image,
+    /// unless the symbol is not in the grammar or is an auxiliary symbol.
+    pub fn to_railroad_svg_of(&self, symbol: &Symbol) -> Option<String> {
+        if !self.symbols().contains(symbol) || self.auxiliary_symbols().contains_key(symbol) {
+            return None;
+        }
+        Some(render_svg(&[(symbol, self.symbol_diagram_internal(symbol))]))
+    }
+}
+
+impl Grammar {
+    /// Internal creation of the railroad diagram of a symbol from its rules,
+    /// drawing left and right recursive lists as loops.
+    fn symbol_diagram_internal(&self, symbol: &Symbol) -> Diagram {
+        let recursion = AtomicPattern::Symbol(symbol.clone());
+        let patterns = self
+            .rules()
+            .iter()
+            .filter(|rule| rule.symbol() == symbol)
+            .map(|rule| rule.pattern())
+            .collect_vec();
+
+        let (left_recursive, others): (Vec<&[AtomicPattern]>, Vec<&[AtomicPattern]>) = patterns
+            .iter()
+            .partition(|pattern| pattern.len() > 1 && pattern.first() == Some(&recursion));
+        if !left_recursive.is_empty() && !others.is_empty() {
+            let tails = left_recursive.iter().map(|pattern| &pattern[1..]).collect_vec();
+            // X -> X ',' Y
+            // X -> Y
+            if let [item] = others.as_slice() {
+                if !item.is_empty() && tails.iter().all(|tail| tail.ends_with(item)) {
+                    let separators = tails
+                        .iter()
+                        .map(|tail| self.pattern_diagram_internal(&tail[..tail.len() - item.len()]))
+                        .collect();
+                    return Diagram::Loop(
+                        Box::new(self.pattern_diagram_internal(item)),
+                        Box::new(Diagram::choice(separators)),
+                    );
+                }
+            }
+            // X -> X Z
+            // X -> Y
+            let items = others.iter().map(|pattern| self.pattern_diagram_internal(pattern));
+            let repetitions = tails.iter().map(|tail| self.pattern_diagram_internal(tail));
+            return Diagram::sequence(vec![
+                Diagram::choice(items.collect()),
+                Diagram::zero_or_more(Diagram::choice(repetitions.collect())),
+            ]);
+        }
+
+        let (right_recursive, others): (Vec<&[AtomicPattern]>, Vec<&[AtomicPattern]>) = patterns
+            .iter()
+            .partition(|pattern| pattern.len() > 1 && pattern.last() == Some(&recursion));
+        if !right_recursive.is_empty() && !others.is_empty() {
+            let heads =
+                right_recursive.iter().map(|pattern| &pattern[..pattern.len() - 1]).collect_vec();
+            // X -> Y ',' X
+            // X -> Y
+            if let [item] = others.as_slice() {
+                if !item.is_empty() && heads.iter().all(|head| head.starts_with(item)) {
+                    let separators = heads
+                        .iter()
+                        .map(|head| self.pattern_diagram_internal(&head[item.len()..]))
+                        .collect();
+                    return Diagram::Loop(
+                        Box::new(self.pattern_diagram_internal(item)),
+                        Box::new(Diagram::choice(separators)),
+                    );
+                }
+            }
+            // X -> Z X
+            // X -> Y
+            let repetitions = heads.iter().map(|head| self.pattern_diagram_internal(head));
+            let items = others.iter().map(|pattern| self.pattern_diagram_internal(pattern));
+            return Diagram::sequence(vec![
+                Diagram::zero_or_more(Diagram::choice(repetitions.collect())),
+                Diagram::choice(items.collect()),
+            ]);
+        }
+
+        Diagram::choice(
+            patterns.iter().map(|pattern| self.pattern_diagram_internal(pattern)).collect(),
+        )
+    }
+
+    /// Internal creation of the railroad diagram of a pattern,
+    /// drawing the auxiliary symbols as the EBNF constructs they are generated for.
+    fn pattern_diagram_internal(&self, pattern: &[AtomicPattern]) -> Diagram {
+        let elements = pattern
+            .iter()
+            .map(|atomic_pattern| {
+                match atomic_pattern {
+                    AtomicPattern::Symbol(symbol) => {
+                        match self.auxiliary_symbols().get(symbol) {
+                            Some(construct) => self.construct_diagram_internal(symbol, *construct),
+                            None => Diagram::Symbol(symbol.clone()),
+                        }
+                    },
+                    AtomicPattern::Token(token) => Diagram::Token(token.clone()),
+                }
+            })
+            .collect();
+        Diagram::sequence(elements)
+    }
+
+    /// Internal creation of the railroad diagram of an auxiliary symbol from its rules.
+    fn construct_diagram_internal(&self, symbol: &Symbol, construct: EbnfConstruct) -> Diagram {
+        let alternatives = self
+            .construct_alternatives_internal(symbol, construct)
+            .into_iter()
+            .map(|(pattern, _)| self.pattern_diagram_internal(pattern))
+            .collect();
+
+        let alternatives = Diagram::choice(alternatives);
+        match construct {
+            EbnfConstruct::Optional => Diagram::optional(alternatives),
+            EbnfConstruct::ZeroOrMore => Diagram::zero_or_more(alternatives),
+            EbnfConstruct::OneOrMore => {
+                Diagram::Loop(Box::new(alternatives), Box::new(Diagram::Skip))
+            },
+            EbnfConstruct::Group => alternatives,
+        }
+    }
+}
+
+
+/// Railroad diagram (e.g., a box, a choice between tracks, a loop) of a part of a grammar.
+#[derive(Clone, Debug, PartialEq)]
+enum Diagram {
+    /// Empty track.
+    Skip,
+    /// Box of a symbol.
+    Symbol(Symbol),
+    /// Box of a token.
+    Token(Token),
+    /// Diagrams one after another.
+    Sequence(Vec<Diagram>),
+    /// Choice between the tracks of diagrams, with the track at the index on the main track.
+    Choice(usize, Vec<Diagram>),
+    /// Diagram that can be repeated, going back through the separator diagram.
+    Loop(Box<Diagram>, Box<Diagram>),
+}
+
+impl Diagram {
+    /// Creates a sequence of diagrams, flattening the nested sequences.
+    fn sequence(diagrams: Vec<Diagram>) -> Diagram {
+        let mut flattened = Vec::with_capacity(diagrams.len());
+        for diagram in diagrams {
+            match diagram {
+                Diagram::Skip => {},
+                Diagram::Sequence(diagrams) => flattened.extend(diagrams),
+                diagram => flattened.push(diagram),
+            }
+        }
+        match flattened.len() {
+            0 => Diagram::Skip,
+            1 => flattened.pop().unwrap(),
+            _ => Diagram::Sequence(flattened),
+        }
+    }
+
+    /// Creates a choice between diagrams, drawing the empty alternatives as a bypass.
+    fn choice(mut diagrams: Vec<Diagram>) -> Diagram {
+        if diagrams.contains(&Diagram::Skip) {
+            diagrams.retain(|diagram| *diagram != Diagram::Skip);
+            return Diagram::optional(Diagram::choice(diagrams));
+        }
+        match diagrams.len() {
+            0 => Diagram::Skip,
+            1 => diagrams.pop().unwrap(),
+            _ => Diagram::Choice(0, diagrams),
+        }
+    }
+
+    /// Creates an optional diagram, bypassing it above the main track.
+    fn optional(diagram: Diagram) -> Diagram {
+        match diagram {
+            Diagram::Skip => Diagram::Skip,
+            diagram => Diagram::Choice(1, vec![Diagram::Skip, diagram]),
+        }
+    }
+
+    /// Creates a diagram that can be repeated zero or more times.
+    fn zero_or_more(diagram: Diagram) -> Diagram {
+        Diagram::optional(Diagram::Loop(Box::new(diagram), Box::new(Diagram::Skip)))
+    }
+}
+
+impl Diagram {
+    /// Gets the width, the height above the track and the height below the track of the diagram.
+    fn size(&self) -> (i32, i32, i32) {
+        match self {
+            Diagram::Skip => (0, 0, 0),
+            Diagram::Symbol(_) | Diagram::Token(_) => {
+                let width = self.text().chars().count() as i32 * CHARACTER_WIDTH;
+                (width + 2 * BOX_PADDING, BOX_HEIGHT / 2, BOX_HEIGHT / 2)
+            },
+            Diagram::Sequence(diagrams) => {
+                let sizes = diagrams.iter().map(Diagram::size).collect_vec();
+                let width = sizes.iter().map(|(width, _, _)| width).sum::<i32>()
+                    + HORIZONTAL_SEPARATION * (sizes.len() as i32 - 1);
+                let up = sizes.iter().map(|(_, up, _)| *up).max().unwrap_or(0);
+                let down = sizes.iter().map(|(_, _, down)| *down).max().unwrap_or(0);
+                (width, up, down)
+            },
+            Diagram::Choice(normal, diagrams) => {
+                let sizes = diagrams.iter().map(Diagram::size).collect_vec();
+                let offsets = Diagram::choice_offsets(*normal, diagrams);
+                let width = sizes.iter().map(|(width, _, _)| *width).max().unwrap_or(0);
+                let up = sizes.iter().zip(&offsets).map(|((_, up, _), offset)| up - offset).max();
+                let down =
+                    sizes.iter().zip(&offsets).map(|((_, _, down), offset)| down + offset).max();
+                (width + 4 * ARC_RADIUS, up.unwrap_or(0), down.unwrap_or(0))
+            },
+            Diagram::Loop(diagram, separator) => {
+                let (width, up, _) = diagram.size();
+                let (separator_width, _, separator_down) = separator.size();
+                let offset = Diagram::loop_offset(diagram, separator);
+                (width.max(separator_width) + 2 * ARC_RADIUS, up, offset + separator_down)
+            },
+        }
+    }
+
+    /// Gets the text of the box of the diagram.
+    fn text(&self) -> String {
+        match self {
+            Diagram::Symbol(symbol) => symbol.to_string(),
+            Diagram::Token(Token::Constant(constant_token)) => constant_token.as_str().to_owned(),
+            Diagram::Token(Token::Regex(regex_token)) => regex_token.to_string(),
+            Diagram::Token(Token::Eof) => Token::Eof.to_string(),
+            _ => String::new(),
+        }
+    }
+
+    /// Gets the vertical offsets of the tracks of the diagrams of a choice from the main track.
+    fn choice_offsets(normal: usize, diagrams: &[Diagram]) -> Vec<i32> {
+        let sizes = diagrams.iter().map(Diagram::size).collect_vec();
+        let mut offsets = vec![0; diagrams.len()];
+        for index in (0..normal).rev() {
+            let (_, _, down) = sizes[index];
+            let (_, next_up, _) = sizes[index + 1];
+            let offset = offsets[index + 1] - (next_up + VERTICAL_SEPARATION + down);
+            offsets[index] = offset.min(-2 * ARC_RADIUS);
+        }
+        for index in (normal + 1)..diagrams.len() {
+            let (_, up, _) = sizes[index];
+            let (_, _, previous_down) = sizes[index - 1];
+            let offset = offsets[index - 1] + (previous_down + VERTICAL_SEPARATION + up);
+            offsets[index] = offset.max(2 * ARC_RADIUS);
+        }
+        offsets
+    }
+
+    /// Gets the vertical offset of the track going back through the separator of a loop.
+    fn loop_offset(diagram: &Diagram, separator: &Diagram) -> i32 {
+        let (_, _, down) = diagram.size();
+        let (_, separator_up, _) = separator.size();
+        (down + VERTICAL_SEPARATION + separator_up).max(2 * ARC_RADIUS)
+    }
+}
+
+impl Diagram {
+    /// Renders the diagram to SVG elements, starting from a point on its main track.
+    fn render(&self, x: i32, y: i32, output: &mut String) {
+        let radius = ARC_RADIUS;
+        match self {
+            Diagram::Skip => {},
+            Diagram::Symbol(_) | Diagram::Token(_) => {
+                let (width, up, _) = self.size();
+                let (class, corner_radius) = match self {
+                    Diagram::Symbol(_) => ("symbol", 0),
+                    Diagram::Token(Token::Regex(_)) => ("regex-token", BOX_HEIGHT / 2),
+                    _ => ("constant-token", BOX_HEIGHT / 2),
+                };
+                output.push_str(&format!(
+                    "<rect class=\"{}\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\"/>\n",
+                    class,
+                    x,
+                    y - up,
+                    width,
+                    BOX_HEIGHT,
+                    corner_radius,
+                ));
+                output.push_str(&format!(
+                    "<text class=\"{}\" x=\"{}\" y=\"{}\">{}</text>\n",
+                    class,
+                    x + width / 2,
+                    y,
+                    escape_xml(&self.text()),
+                ));
+            },
+            Diagram::Sequence(diagrams) => {
+                let mut x = x;
+                for (index, diagram) in diagrams.iter().enumerate() {
+                    if index > 0 {
+                        render_path(output, format!("M{} {}h{}", x, y, HORIZONTAL_SEPARATION));
+                        x += HORIZONTAL_SEPARATION;
+                    }
+                    diagram.render(x, y, output);
+                    x += diagram.size().0;
+                }
+            },
+            Diagram::Choice(normal, diagrams) => {
+                let (width, _, _) = self.size();
+                let inner_width = width - 4 * radius;
+                let offsets = Diagram::choice_offsets(*normal, diagrams);
+                for (diagram, offset) in diagrams.iter().zip(offsets) {
+                    let (diagram_width, _, _) = diagram.size();
+                    let left = x + 2 * radius + (inner_width - diagram_width) / 2;
+                    let right = left + diagram_width;
+                    if offset == 0 {
+                        render_path(output, format!("M{} {}h{}", x, y, left - x));
+                        render_path(output, format!("M{} {}h{}", right, y, x + width - right));
+                    } else {
+                        let direction = offset.signum();
+                        let (down, up) = if direction > 0 { (1, 0) } else { (0, 1) };
+                        render_path(
+                            output,
+                            format!(
+                                "M{} {}a{r} {r} 0 0 {} {r} {}v{}a{r} {r} 0 0 {} {r} {}h{}",
+                                x,
+                                y,
+                                down,
+                                direction * radius,
+                                offset - 2 * direction * radius,
+                                up,
+                                direction * radius,
+                                left - (x + 2 * radius),
+                                r = radius,
+                            ),
+                        );
+                        render_path(
+                            output,
+                            format!(
+                                "M{} {}h{}a{r} {r} 0 0 {} {r} {}v{}a{r} {r} 0 0 {} {r} {}",
+                                right,
+                                y + offset,
+                                x + width - 2 * radius - right,
+                                up,
+                                -direction * radius,
+                                -(offset - 2 * direction * radius),
+                                down,
+                                -direction * radius,
+                                r = radius,
+                            ),
+                        );
+                    }
+                    diagram.render(left, y + offset, output);
+                }
+            },
+            Diagram::Loop(diagram, separator) => {
+                let (width, _, _) = self.size();
+                let inner_width = width - 2 * radius;
+                let offset = Diagram::loop_offset(diagram, separator);
+
+                let (diagram_width, _, _) = diagram.size();
+                let left = x + radius + (inner_width - diagram_width) / 2;
+                let right = left + diagram_width;
+                render_path(output, format!("M{} {}h{}", x, y, left - x));
+                render_path(output, format!("M{} {}h{}", right, y, x + width - right));
+                diagram.render(left, y, output);
+
+                let (separator_width, _, _) = separator.size();
+                let left = x + radius + (inner_width - separator_width) / 2;
+                let right = left + separator_width;
+                render_path(
+                    output,
+                    format!(
+                        "M{} {}a{r} {r} 0 0 1 {r} {r}v{}a{r} {r} 0 0 1 {} {r}h{}",
+                        x + width - radius,
+                        y,
+                        offset - 2 * radius,
+                        -radius,
+                        right - (x + width - radius),
+                        r = radius,
+                    ),
+                );
+                render_path(
+                    output,
+                    format!(
+                        "M{} {}h{}a{r} {r} 0 0 1 {} {}v{}a{r} {r} 0 0 1 {r} {}",
+                        left,
+                        y + offset,
+                        x + radius - left,
+                        -radius,
+                        -radius,
+                        -(offset - 2 * radius),
+                        -radius,
+                        r = radius,
+                    ),
+                );
+                separator.render(left, y + offset, output);
+            },
+        }
+    }
+}
+
+
+/// Renders railroad diagrams of symbols to an SVG image, one under another.
+fn render_svg(diagrams: &[(&Symbol, Diagram)]) -> String {
+    let mut body = String::new();
+    let mut width = 0;
+    let mut y = PADDING;
+    for (symbol, diagram) in diagrams {
+        let (diagram_width, up, down) = diagram.size();
+        let title = symbol.to_string();
+        let title_width = title.chars().count() as i32 * CHARACTER_WIDTH;
+        width = width.max(diagram_width + 2 * MARKER_WIDTH).max(title_width);
+
+        body.push_str(&format!(
+            "<text class=\"title\" x=\"{}\" y=\"{}\">{}</text>\n",
+            PADDING,
+            y + TITLE_HEIGHT / 2,
+            escape_xml(&title),
+        ));
+
+        let x = PADDING;
+        let track = y + TITLE_HEIGHT + up.max(BOX_HEIGHT / 2);
+        render_path(
+            &mut body,
+            format!("M{} {}v20m10 -20v20M{} {}h{}", x, track - 10, x, track, MARKER_WIDTH),
+        );
+        diagram.render(x + MARKER_WIDTH, track, &mut body);
+        let end = x + MARKER_WIDTH + diagram_width;
+        render_path(&mut body, format!("M{} {}h{}m-10 -10v20m10 -20v20", end, track, MARKER_WIDTH));
+
+        y = track + down.max(BOX_HEIGHT / 2) + PADDING;
+    }
+
+    let width = width + 2 * PADDING;
+    let height = y;
+    let mut output = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+        width, height, width, height,
+    );
+    output.push_str(&format!("<style>{}</style>\n", STYLE));
+    output.push_str(&body);
+    output.push_str("</svg>\n");
+    output
+}
+
+/// Renders an SVG path.
+fn render_path(output: &mut String, path: String) {
+    output.push_str(&format!("<path d=\"{}\"/>\n", path));
+}
+
+/// Escapes the special characters of XML in a text.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            char => escaped.push(char),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,61 @@
+use {
+    crate::prelude::*,
+    std::path::{
+        Path,
+        PathBuf,
+    },
+};
+
+
+/// Resolver of the grammars imported with `%import` (e.g., `%import "expressions.lr"`).
+pub trait GrammarResolver {
+    /// Resolves the path of an import into the name and the content of the imported grammar.
+    ///
+    /// Importer is the name of the importing grammar, or `None` if it's the root grammar.
+    /// Names are used to detect cyclic imports and to report errors in imported grammars.
+    fn resolve(&mut self, path: &str, importer: Option<&str>)
+    -> Result<(SmolStr, String), SmolStr>;
+}
+
+
+/// Grammar resolver that reads the imported grammars from the file system.
+#[derive(Clone, Debug)]
+pub struct FileSystemResolver {
+    root: PathBuf,
+}
+
+impl FileSystemResolver {
+    /// Creates a file system resolver, which resolves the imports of the root grammar relative to a directory.
+    pub fn new(root: impl Into<PathBuf>) -> FileSystemResolver {
+        FileSystemResolver { root: root.into() }
+    }
+}
+
+impl FileSystemResolver {
+    /// Gets the directory the imports of the root grammar are resolved relative to.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl GrammarResolver for FileSystemResolver {
+    fn resolve(
+        &mut self,
+        path: &str,
+        importer: Option<&str>,
+    ) -> Result<(SmolStr, String), SmolStr> {
+        // Imports of imported grammars are resolved relative to the importing grammar.
+        let directory = match importer {
+            Some(importer) => Path::new(importer).parent().unwrap_or(Path::new("")),
+            None => &self.root,
+        };
+
+        let path = directory.join(path);
+        let path = std::fs::canonicalize(&path).unwrap_or(path);
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => Ok((format_smolstr!("{}", path.display()), content)),
+            Err(error) => Err(format_smolstr!("{}", error)),
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use crate::prelude::*;
 
 /// Position information of a token in the input string.
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
 #[derive(Clone, Debug, PartialEq)]
 pub struct Span {
@@ -13,6 +13,22 @@ pub struct Span {
     pub line: usize,
     /// Column number of the span in the input string.
     pub column: usize,
+    /// Name of the imported grammar the span is in, or `None` if it's in the input string itself.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub source: Option<SmolStr>,
+}
+
+impl Span {
+    /// Joins the span with a span that ends after it, keeping the line and column of the span.
+    pub fn join(&self, end: &Span) -> Span {
+        Span {
+            offset: self.offset,
+            len: (end.offset + end.len).max(self.offset + self.len) - self.offset,
+            line: self.line,
+            column: self.column,
+            source: self.source.clone(),
+        }
+    }
 }
 
 /// Wrapper over any type with span information.
@@ -1,16 +1,78 @@
-use crate::prelude::*;
+use {
+    crate::prelude::*,
+    std::cmp::Ordering,
+};
+
+
+/// Nullable table of the symbols in a grammar.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Debug, PartialEq)]
+pub struct NullableTable(IndexSet<Symbol>);
+
+impl NullableTable {
+    /// Constructs the nullable table from the grammar.
+    pub fn construct(grammar: &Grammar) -> NullableTable {
+        let mut nullable_table = IndexSet::new();
+
+        let mut done = false;
+        while !done {
+            done = true;
+            for rule in grammar.rules().iter() {
+                let symbol = rule.symbol();
+                if nullable_table.contains(symbol) {
+                    continue;
+                }
+
+                let pattern_is_nullable = rule.pattern().iter().all(|atomic_pattern| {
+                    match atomic_pattern {
+                        AtomicPattern::Symbol(symbol) => nullable_table.contains(symbol),
+                        AtomicPattern::Token(_) => false,
+                    }
+                });
+                if pattern_is_nullable {
+                    nullable_table.insert(symbol.clone());
+                    done = false;
+                }
+            }
+        }
+
+        NullableTable(nullable_table)
+    }
+}
+
+impl NullableTable {
+    /// Gets whether a sequence of atomic patterns can derive the empty string.
+    pub fn is_nullable(&self, atomic_patterns: &[AtomicPattern]) -> bool {
+        atomic_patterns.iter().all(|atomic_pattern| {
+            match atomic_pattern {
+                AtomicPattern::Symbol(symbol) => self.0.contains(symbol),
+                AtomicPattern::Token(_) => false,
+            }
+        })
+    }
+}
+
+impl Deref for NullableTable {
+    type Target = IndexSet<Symbol>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
 
 
 /// First table of the symbols in a grammar.
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct FirstTable(IndexMap<Symbol, IndexSet<Token>>);
 
 impl FirstTable {
     /// Constructs the first table from the grammar.
-    pub fn construct(grammar: &Grammar) -> FirstTable {
+    pub fn construct(grammar: &Grammar, nullable_table: &NullableTable) -> FirstTable {
         let mut first_table = IndexMap::new();
 
         let mut done = false;
@@ -18,14 +80,6 @@ impl FirstTable {
             done = true;
             for rule in grammar.rules().iter() {
                 let symbol = rule.symbol();
-                let first_atomic_pattern = rule.pattern().first().unwrap();
-
-                if matches!(
-                    first_atomic_pattern,
-                    AtomicPattern::Symbol(atomic_pattern) if atomic_pattern == symbol
-                ) {
-                    continue;
-                }
 
                 let mut possible_first_tokens = match first_table.get_mut(symbol) {
                     Some(entry) => std::mem::take(entry),
@@ -33,15 +87,22 @@ impl FirstTable {
                 };
 
                 let old_possible_first_token_count = possible_first_tokens.len();
-                match first_atomic_pattern {
-                    AtomicPattern::Symbol(symbol) => {
-                        if let Some(new_possible_first_tokens) = first_table.get(symbol) {
-                            possible_first_tokens.extend(new_possible_first_tokens.iter().cloned());
-                        }
-                    },
-                    AtomicPattern::Token(token) => {
-                        possible_first_tokens.insert(token.clone());
-                    },
+                for atomic_pattern in rule.pattern().iter() {
+                    match atomic_pattern {
+                        AtomicPattern::Symbol(symbol) => {
+                            if let Some(new_possible_first_tokens) = first_table.get(symbol) {
+                                possible_first_tokens
+                                    .extend(new_possible_first_tokens.iter().cloned());
+                            }
+                            if !nullable_table.contains(symbol) {
+                                break;
+                            }
+                        },
+                        AtomicPattern::Token(token) => {
+                            possible_first_tokens.insert(token.clone());
+                            break;
+                        },
+                    }
                 }
                 let new_possible_first_token_count = possible_first_tokens.len();
 
@@ -65,6 +126,37 @@ impl FirstTable {
     }
 }
 
+impl FirstTable {
+    /// Computes the first set of a sequence of atomic patterns.
+    ///
+    /// The empty string is not included in the result,
+    /// [NullableTable::is_nullable] can be used to check for it.
+    pub fn first_of(
+        &self,
+        nullable_table: &NullableTable,
+        atomic_patterns: &[AtomicPattern],
+    ) -> IndexSet<Token> {
+        let mut first_set = IndexSet::new();
+        for atomic_pattern in atomic_patterns {
+            match atomic_pattern {
+                AtomicPattern::Symbol(symbol) => {
+                    if let Some(first_tokens) = self.0.get(symbol) {
+                        first_set.extend(first_tokens.iter().cloned());
+                    }
+                    if !nullable_table.contains(symbol) {
+                        break;
+                    }
+                },
+                AtomicPattern::Token(token) => {
+                    first_set.insert(token.clone());
+                    break;
+                },
+            }
+        }
+        first_set
+    }
+}
+
 impl Deref for FirstTable {
     type Target = IndexMap<Symbol, IndexSet<Token>>;
 
@@ -76,75 +168,49 @@ impl Deref for FirstTable {
 
 /// Follow table of the symbols in a grammar.
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct FollowTable(IndexMap<Symbol, IndexSet<Token>>);
 
 impl FollowTable {
     /// Constructs the follow table from the grammar.
-    pub fn construct(grammar: &Grammar, first_table: &FirstTable) -> FollowTable {
-        let mut follow_table =
-            IndexMap::from([(grammar.start_symbol().clone(), IndexSet::from([Token::Eof]))]);
+    pub fn construct(
+        grammar: &Grammar,
+        nullable_table: &NullableTable,
+        first_table: &FirstTable,
+    ) -> FollowTable {
+        let mut follow_table = grammar
+            .entry_symbols()
+            .iter()
+            .map(|entry_symbol| (entry_symbol.clone(), IndexSet::from([Token::Eof])))
+            .collect::<IndexMap<_, _>>();
 
         let mut done = false;
         while !done {
             done = true;
             for rule in grammar.rules().iter() {
-                for consecutive_atomic_patterns in rule.pattern().windows(2) {
-                    let ap1 = &consecutive_atomic_patterns[0];
-                    let ap2 = &consecutive_atomic_patterns[1];
-
-                    let ap1_symbol = match ap1 {
+                for (index, atomic_pattern) in rule.pattern().iter().enumerate() {
+                    let symbol = match atomic_pattern {
                         AtomicPattern::Symbol(symbol) => symbol,
                         AtomicPattern::Token(_) => continue,
                     };
+                    let remaining_atomic_patterns = &rule.pattern()[index + 1..];
 
-                    let mut possible_follow_tokens = match follow_table.get_mut(ap1_symbol) {
+                    let mut possible_follow_tokens = match follow_table.get_mut(symbol) {
                         Some(entry) => std::mem::take(entry),
                         None => IndexSet::new(),
                     };
 
                     let old_possible_follow_token_count = possible_follow_tokens.len();
-                    match ap2 {
-                        AtomicPattern::Symbol(symbol) => {
-                            if let Some(new_possible_follow_tokens) = first_table.get(symbol) {
-                                possible_follow_tokens
-                                    .extend(new_possible_follow_tokens.iter().cloned());
-                            }
-                        },
-                        AtomicPattern::Token(token) => {
-                            possible_follow_tokens.insert(token.clone());
-                        },
-                    }
-                    let new_possible_follow_token_count = possible_follow_tokens.len();
-
-                    if new_possible_follow_token_count != old_possible_follow_token_count {
-                        done = false;
-                    }
-
-                    if new_possible_follow_token_count > 0 {
-                        match follow_table.get_mut(ap1_symbol) {
-                            Some(entry) => {
-                                *entry = possible_follow_tokens;
-                            },
-                            None => {
-                                follow_table.insert(ap1_symbol.clone(), possible_follow_tokens);
-                            },
+                    possible_follow_tokens
+                        .extend(first_table.first_of(nullable_table, remaining_atomic_patterns));
+                    if nullable_table.is_nullable(remaining_atomic_patterns) {
+                        if let Some(new_possible_follow_tokens) = follow_table.get(rule.symbol()) {
+                            possible_follow_tokens
+                                .extend(new_possible_follow_tokens.iter().cloned());
                         }
                     }
-                }
-
-                if let AtomicPattern::Symbol(last_ap) = rule.pattern().last().unwrap() {
-                    let mut possible_follow_tokens = match follow_table.get_mut(last_ap) {
-                        Some(entry) => std::mem::take(entry),
-                        None => IndexSet::new(),
-                    };
-
-                    let old_possible_follow_token_count = possible_follow_tokens.len();
-                    if let Some(new_possible_follow_tokens) = follow_table.get(rule.symbol()) {
-                        possible_follow_tokens.extend(new_possible_follow_tokens.iter().cloned());
-                    }
                     let new_possible_follow_token_count = possible_follow_tokens.len();
 
                     if new_possible_follow_token_count != old_possible_follow_token_count {
@@ -152,12 +218,12 @@ impl FollowTable {
                     }
 
                     if new_possible_follow_token_count > 0 {
-                        match follow_table.get_mut(last_ap) {
+                        match follow_table.get_mut(symbol) {
                             Some(entry) => {
                                 *entry = possible_follow_tokens;
                             },
                             None => {
-                                follow_table.insert(last_ap.clone(), possible_follow_tokens);
+                                follow_table.insert(symbol.clone(), possible_follow_tokens);
                             },
                         }
                     }
@@ -178,8 +244,8 @@ impl Deref for FollowTable {
 }
 
 
-/// Action (e.g., `Shift 3`, `Reduce 2`, `Accept 1`) to perform during a parsing step.
-#[cfg_attr(feature = "serde", derive(Serialize))]
+/// Action (e.g., `Shift 3`, `Reduce 2`, `Accept`) to perform during a parsing step.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
 #[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
@@ -194,11 +260,8 @@ pub enum Action {
         /// Index of the rule to reduce.
         rule_index: usize,
     },
-    /// Accept the parse and finish parsing.
-    Accept {
-        /// Index of the rule that was matched.
-        rule_index: usize,
-    },
+    /// Accept the parse of the entry symbol and finish parsing.
+    Accept,
 }
 
 impl Display for Action {
@@ -206,7 +269,72 @@ impl Display for Action {
         match self {
             Action::Shift { next_state } => write!(f, "s{}", next_state),
             Action::Reduce { rule_index } => write!(f, "r{}", rule_index + 1),
-            Action::Accept { rule_index } => write!(f, "a{}", rule_index + 1),
+            Action::Accept => write!(f, "acc"),
+        }
+    }
+}
+
+
+/// Shift/reduce conflict (e.g., `s3, r2` on `'+'`) resolved using precedences.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolvedConflict {
+    state: usize,
+    token: Token,
+    shift: Action,
+    reduce: Action,
+    token_precedence: Precedence,
+    rule_precedence: Precedence,
+    resolution: Option<Action>,
+}
+
+impl ResolvedConflict {
+    /// Gets the state the conflict was in.
+    pub fn state(&self) -> usize {
+        self.state
+    }
+
+    /// Gets the lookahead token the conflict was on.
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    /// Gets the conflicting shift action.
+    pub fn shift(&self) -> Action {
+        self.shift
+    }
+
+    /// Gets the conflicting reduce action.
+    pub fn reduce(&self) -> Action {
+        self.reduce
+    }
+
+    /// Gets the precedence of the lookahead token.
+    pub fn token_precedence(&self) -> Precedence {
+        self.token_precedence
+    }
+
+    /// Gets the precedence of the rule to reduce.
+    pub fn rule_precedence(&self) -> Precedence {
+        self.rule_precedence
+    }
+
+    /// Gets the action that was kept, or `None` if both of them were removed (i.e., `%nonassoc`).
+    pub fn resolution(&self) -> Option<Action> {
+        self.resolution
+    }
+}
+
+impl ResolvedConflict {
+    /// Gets the explanation of the resolution.
+    pub fn reason(&self) -> String {
+        if self.rule_precedence.level() > self.token_precedence.level() {
+            format!("rule has higher precedence than {}", self.token)
+        } else if self.rule_precedence.level() < self.token_precedence.level() {
+            format!("{} has higher precedence than rule", self.token)
+        } else {
+            format!("{} is {}", self.token, self.token_precedence.associativity())
         }
     }
 }
@@ -214,12 +342,13 @@ impl Display for Action {
 
 /// Action and goto tables of a parser.
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct ParsingTables {
     action_table: Vec<IndexMap<Token, IndexSet<Action>>>,
     goto_table: Vec<IndexMap<Symbol, usize>>,
+    resolved_conflicts: Vec<ResolvedConflict>,
 }
 
 impl ParsingTables {
@@ -231,13 +360,20 @@ impl ParsingTables {
     ) -> Result<ParsingTables, ParserError> {
         let mut action_table = Vec::with_capacity(automaton.states().len());
         let mut goto_table = Vec::with_capacity(automaton.states().len());
+        let mut resolved_conflicts = Vec::new();
 
-        for state in automaton.states().iter() {
+        for (state_index, state) in automaton.states().iter().enumerate() {
             let mut actions = IndexMap::<Token, IndexSet<Action>>::new();
             let mut gotos = IndexMap::<Symbol, usize>::new();
 
             for item in state.items() {
                 if item.dot() == item.rule().pattern().len() {
+                    // Augmented items (e.g., `S' -> S .`) are the only items of symbols
+                    // which are not in the grammar, and they accept the input at its end.
+                    if !grammar.symbols().contains(item.rule().symbol()) {
+                        actions.entry(Token::Eof).or_default().insert(Action::Accept);
+                        continue;
+                    }
                     if let Some(follows) = follow_table.get(item.rule().symbol()) {
                         let rule_index =
                             grammar.rules().iter().position(|rule| rule == item.rule()).unwrap();
@@ -245,20 +381,10 @@ impl ParsingTables {
                             if !item.lookahead().contains(token) {
                                 continue;
                             }
-
-                            if *token == Token::Eof
-                                && item.rule().symbol() == grammar.start_symbol()
-                            {
-                                actions
-                                    .entry(token.clone())
-                                    .or_default()
-                                    .insert(Action::Accept { rule_index });
-                            } else {
-                                actions
-                                    .entry(token.clone())
-                                    .or_default()
-                                    .insert(Action::Reduce { rule_index });
-                            }
+                            actions
+                                .entry(token.clone())
+                                .or_default()
+                                .insert(Action::Reduce { rule_index });
                         }
                     }
                 } else {
@@ -278,11 +404,73 @@ impl ParsingTables {
                 }
             }
 
+            for (token, token_actions) in actions.iter_mut() {
+                if let Some(resolved_conflict) =
+                    ParsingTables::resolve_conflict(grammar, state_index, token, token_actions)
+                {
+                    resolved_conflicts.push(resolved_conflict);
+                }
+            }
+            actions.retain(|_, token_actions| !token_actions.is_empty());
+
             action_table.push(actions);
             goto_table.push(gotos);
         }
 
-        Ok(ParsingTables { action_table, goto_table })
+        Ok(ParsingTables { action_table, goto_table, resolved_conflicts })
+    }
+
+    /// Resolves a shift/reduce conflict using the precedences in the grammar.
+    ///
+    /// Conflict is resolved in favor of the one with the higher precedence. If the precedences
+    /// are the same, left associativity favors reduce, right associativity favors shift, and
+    /// non associativity removes both actions so the token results in a parsing error.
+    fn resolve_conflict(
+        grammar: &Grammar,
+        state: usize,
+        token: &Token,
+        actions: &mut IndexSet<Action>,
+    ) -> Option<ResolvedConflict> {
+        if actions.len() != 2 {
+            return None;
+        }
+
+        let shift = *actions.iter().find(|action| matches!(action, Action::Shift { .. }))?;
+        let reduce = *actions.iter().find(|action| matches!(action, Action::Reduce { .. }))?;
+        let rule_index = match reduce {
+            Action::Reduce { rule_index } => rule_index,
+            _ => unreachable!(),
+        };
+
+        let token_precedence = *grammar.precedences().get(token)?;
+        let rule_precedence = grammar.precedence_of(&grammar.rules()[rule_index])?;
+
+        let resolution = match rule_precedence.level().cmp(&token_precedence.level()) {
+            Ordering::Greater => Some(reduce),
+            Ordering::Less => Some(shift),
+            Ordering::Equal => {
+                match token_precedence.associativity() {
+                    Associativity::Left => Some(reduce),
+                    Associativity::Right => Some(shift),
+                    Associativity::NonAssoc => None,
+                }
+            },
+        };
+
+        actions.clear();
+        if let Some(action) = resolution {
+            actions.insert(action);
+        }
+
+        Some(ResolvedConflict {
+            state,
+            token: token.clone(),
+            shift,
+            reduce,
+            token_precedence,
+            rule_precedence,
+            resolution,
+        })
     }
 }
 
@@ -296,4 +484,9 @@ impl ParsingTables {
     pub fn goto_table(&self) -> &[IndexMap<Symbol, usize>] {
         &self.goto_table
     }
+
+    /// Gets the shift/reduce conflicts resolved using precedences.
+    pub fn resolved_conflicts(&self) -> &[ResolvedConflict] {
+        &self.resolved_conflicts
+    }
 }
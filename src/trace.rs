@@ -99,9 +99,7 @@ impl Trace<'_> {
                 Action::Reduce { rule_index } => {
                     format!("Reduce {} ({})", rule_index + 1, grammar.rules()[rule_index])
                 },
-                Action::Accept { rule_index } => {
-                    format!("Accept {} ({})", rule_index + 1, grammar.rules()[rule_index])
-                },
+                Action::Accept => "Accept".to_owned(),
             };
 
             pretty_trace_table.add_row(row![
@@ -0,0 +1,640 @@
+use crate::prelude::*;
+
+
+/// Rewrite applied to the rules of a grammar by a transformation.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Rewrite {
+    /// Rules of a symbol that cannot derive any sequence of tokens are removed.
+    UnproductiveSymbolRemoved { symbol: Symbol },
+    /// Rules of a symbol that cannot be reached from the entry symbols are removed.
+    UnreachableSymbolRemoved { symbol: Symbol },
+    /// A rule is removed (e.g., `A -> A` or a rule using an unproductive symbol).
+    RuleRemoved { rule: Rule },
+    /// Rules of the symbol at the start of a rule are substituted into the rule.
+    RulesSubstituted { rule: Rule, symbol: Symbol },
+    /// Left recursive rules of a symbol are rewritten into right recursive rules of a new symbol.
+    LeftRecursionEliminated { symbol: Symbol, tail: Symbol },
+    /// Common prefix of the rules of a symbol is factored out into rules of a new symbol.
+    LeftFactored { symbol: Symbol, prefix: Vec<AtomicPattern>, suffix: Symbol },
+    /// New entry symbol is added in place of an entry symbol that is used in the rules.
+    StartSymbolAdded { symbol: Symbol, start: Symbol },
+    /// Token is replaced by a new symbol in the rules with multiple atomic patterns.
+    TokenReplaced { token: Token, symbol: Symbol },
+    /// Rule with more than two atomic patterns is split into rules of new symbols.
+    RuleSplit { rule: Rule, symbols: Vec<Symbol> },
+    /// A rule is added (e.g., a rule without a nullable symbol of another rule).
+    RuleAdded { rule: Rule },
+}
+
+impl Display for Rewrite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rewrite::UnproductiveSymbolRemoved { symbol } => {
+                write!(f, "removed {} as it cannot derive any sequence of tokens", symbol)
+            },
+            Rewrite::UnreachableSymbolRemoved { symbol } => {
+                write!(f, "removed {} as it is not reachable from the entry symbols", symbol)
+            },
+            Rewrite::RuleRemoved { rule } => {
+                write!(f, "removed {}", rule)
+            },
+            Rewrite::RulesSubstituted { rule, symbol } => {
+                write!(f, "substituted the rules of {} into {}", symbol, rule)
+            },
+            Rewrite::LeftRecursionEliminated { symbol, tail } => {
+                write!(f, "eliminated the left recursion of {} by introducing {}", symbol, tail)
+            },
+            Rewrite::LeftFactored { symbol, prefix, suffix } => {
+                write!(
+                    f,
+                    "factored {} out of the rules of {} by introducing {}",
+                    prefix.iter().join(" "),
+                    symbol,
+                    suffix,
+                )
+            },
+            Rewrite::StartSymbolAdded { symbol, start } => {
+                write!(f, "added {} as the entry symbol in place of {}", start, symbol)
+            },
+            Rewrite::TokenReplaced { token, symbol } => {
+                write!(
+                    f,
+                    "replaced {} by {} in the rules with multiple atomic patterns",
+                    token, symbol
+                )
+            },
+            Rewrite::RuleSplit { rule, symbols } => {
+                write!(f, "split {} by introducing {}", rule, symbols.iter().join(", "))
+            },
+            Rewrite::RuleAdded { rule } => {
+                write!(f, "added {}", rule)
+            },
+        }
+    }
+}
+
+
+impl Grammar {
+    /// Eliminates the direct and the indirect left recursion of the grammar.
+    ///
+    /// Left recursion hidden behind nullable symbols (e.g., `A -> B A 'x'` with `B -> ε`)
+    /// is not eliminated, and neither is the one of symbols without non-recursive rules,
+    /// which cannot derive any sequence of tokens anyway (see `remove_useless_symbols`).
+    pub fn eliminate_left_recursion(&self) -> (Grammar, Vec<Rewrite>) {
+        let mut transformation = Transformation::new(self);
+
+        let symbols = transformation.defined_symbols();
+        for (i, symbol) in symbols.iter().enumerate() {
+            // Symbols before the symbol are substituted to expose its indirect left recursion.
+            while let Some((index, leading_symbol)) =
+                transformation.rules.iter().enumerate().find_map(|(index, rule)| {
+                    match rule.pattern.first() {
+                        Some(AtomicPattern::Symbol(leading_symbol))
+                            if rule.symbol == *symbol
+                                && symbols[..i].contains(leading_symbol)
+                                && transformation.left_derives(leading_symbol, symbol) =>
+                        {
+                            Some((index, leading_symbol.clone()))
+                        },
+                        _ => None,
+                    }
+                })
+            {
+                transformation.substitute_leading_symbol(index, &leading_symbol);
+            }
+
+            transformation.eliminate_direct_left_recursion(symbol, false);
+        }
+
+        transformation.finish()
+    }
+
+    /// Factors the common prefixes out of the rules of the symbols of the grammar.
+    pub fn left_factor(&self) -> (Grammar, Vec<Rewrite>) {
+        let mut transformation = Transformation::new(self);
+
+        let mut symbols = transformation.defined_symbols();
+        let mut index = 0;
+        while let Some(symbol) = symbols.get(index).cloned() {
+            while let Some(suffix) = transformation.factor_common_prefix(&symbol) {
+                symbols.push(suffix);
+            }
+            index += 1;
+        }
+
+        transformation.finish()
+    }
+
+    /// Removes the symbols that cannot derive any sequence of tokens,
+    /// and then the symbols that cannot be reached from the entry symbols.
+    pub fn remove_useless_symbols(&self) -> (Grammar, Vec<Rewrite>) {
+        let mut transformation = Transformation::new(self);
+
+        let productive_symbols = self.productive_symbols_internal();
+        for symbol in transformation.defined_symbols() {
+            if !productive_symbols.contains(&symbol) {
+                transformation.rewrites.push(Rewrite::UnproductiveSymbolRemoved { symbol });
+            }
+        }
+        let rules = std::mem::take(&mut transformation.rules);
+        for rule in rules {
+            if !productive_symbols.contains(&rule.symbol) {
+                continue;
+            }
+            let uses_unproductive_symbol = rule.pattern.iter().any(|atomic_pattern| {
+                match atomic_pattern {
+                    AtomicPattern::Symbol(symbol) => !productive_symbols.contains(symbol),
+                    AtomicPattern::Token(_) => false,
+                }
+            });
+            if uses_unproductive_symbol {
+                transformation.rewrites.push(Rewrite::RuleRemoved { rule });
+            } else {
+                transformation.rules.push(rule);
+            }
+        }
+
+        let productive_grammar = self.with_rules_internal(
+            transformation.rules.clone(),
+            transformation.entry_symbols.clone(),
+            IndexMap::new(),
+            IndexMap::new(),
+        );
+        let reachable_symbols = productive_grammar.reachable_symbols_internal();
+        for symbol in transformation.defined_symbols() {
+            if !reachable_symbols.contains(&symbol) {
+                transformation.rewrites.push(Rewrite::UnreachableSymbolRemoved { symbol });
+            }
+        }
+        transformation.rules.retain(|rule| reachable_symbols.contains(&rule.symbol));
+
+        transformation.finish()
+    }
+}
+
+
+/// Transformation of the rules of a grammar that is in progress.
+pub(crate) struct Transformation<'g> {
+    pub(crate) grammar: &'g Grammar,
+    pub(crate) rules: Vec<Rule>,
+    pub(crate) entry_symbols: IndexSet<Symbol>,
+    pub(crate) rewrites: Vec<Rewrite>,
+    pub(crate) taken_symbols: IndexSet<Symbol>,
+}
+
+impl<'g> Transformation<'g> {
+    /// Starts a transformation of the rules of a grammar.
+    pub(crate) fn new(grammar: &'g Grammar) -> Transformation<'g> {
+        let mut taken_symbols = grammar.symbols().clone();
+        for rule in grammar.rules() {
+            for atomic_pattern in rule.pattern() {
+                if let AtomicPattern::Symbol(symbol) = atomic_pattern {
+                    taken_symbols.insert(symbol.clone());
+                }
+            }
+        }
+        Transformation {
+            grammar,
+            rules: grammar.rules().to_vec(),
+            entry_symbols: grammar.entry_symbols().clone(),
+            rewrites: vec![],
+            taken_symbols,
+        }
+    }
+}
+
+impl Transformation<'_> {
+    /// Gets the symbols with at least one rule, in the order they are defined.
+    pub(crate) fn defined_symbols(&self) -> Vec<Symbol> {
+        self.rules.iter().map(|rule| rule.symbol.clone()).unique().collect()
+    }
+
+    /// Gets the rules of a symbol.
+    pub(crate) fn rules_of<'r>(&'r self, symbol: &'r Symbol) -> impl Iterator<Item = &'r Rule> {
+        self.rules.iter().filter(move |rule| rule.symbol == *symbol)
+    }
+
+    /// Gets whether a symbol can derive a sequence starting with another symbol.
+    pub(crate) fn left_derives(&self, symbol: &Symbol, leading_symbol: &Symbol) -> bool {
+        let mut left_derived_symbols = IndexSet::from([symbol]);
+
+        let mut index = 0;
+        while let Some(symbol) = left_derived_symbols.get_index(index).copied() {
+            for rule in self.rules_of(symbol) {
+                if let Some(AtomicPattern::Symbol(left_derived_symbol)) = rule.pattern.first() {
+                    if left_derived_symbol == leading_symbol {
+                        return true;
+                    }
+                    left_derived_symbols.insert(left_derived_symbol);
+                }
+            }
+            index += 1;
+        }
+
+        false
+    }
+
+    /// Creates a symbol that is not used in the grammar, based on the name of a symbol.
+    pub(crate) fn fresh_symbol(&mut self, symbol: &Symbol, suffix: &str) -> Symbol {
+        let base = match self.grammar.auxiliary_symbols().get(symbol) {
+            Some(EbnfConstruct::Optional) => "Optional",
+            Some(EbnfConstruct::ZeroOrMore) => "ZeroOrMore",
+            Some(EbnfConstruct::OneOrMore) => "OneOrMore",
+            Some(EbnfConstruct::Group) => "Group",
+            None => {
+                match self.grammar.instantiations().get(symbol) {
+                    Some(parameterized_symbol) => parameterized_symbol.as_str(),
+                    None => symbol.as_str(),
+                }
+            },
+        };
+        self.fresh_symbol_named(&format_smolstr!("{}{}", base, suffix))
+    }
+
+    /// Creates a symbol that is not used in the grammar, based on a name.
+    pub(crate) fn fresh_symbol_named(&mut self, name: &str) -> Symbol {
+        let mut fresh_symbol = Symbol::from(name);
+        let mut counter = 2;
+        while self.taken_symbols.contains(&fresh_symbol) {
+            fresh_symbol = Symbol::from(format_smolstr!("{}{}", name, counter));
+            counter += 1;
+        }
+
+        self.taken_symbols.insert(fresh_symbol.clone());
+        fresh_symbol
+    }
+
+    /// Substitutes the rules of the symbol at the start of a rule into the rule.
+    ///
+    /// A -> B γ
+    /// B -> δ1 | δ2
+    ///
+    /// is rewritten into
+    ///
+    /// A -> δ1 γ
+    /// A -> δ2 γ
+    pub(crate) fn substitute_leading_symbol(&mut self, index: usize, leading_symbol: &Symbol) {
+        let rule = self.rules.remove(index);
+        let substituted_rules = self
+            .rules_of(leading_symbol)
+            .map(|leading_rule| {
+                let mut substituted_rule = leading_rule.clone();
+                substituted_rule.symbol = rule.symbol.clone();
+                substituted_rule.extend_internal(&rule, 1);
+                substituted_rule.precedence = rule.precedence.clone();
+                substituted_rule.span = rule.span.clone();
+                substituted_rule
+            })
+            .collect::<Vec<_>>();
+        self.rules.splice(index..index, substituted_rules);
+        self.rewrites.push(Rewrite::RulesSubstituted { rule, symbol: leading_symbol.clone() });
+    }
+
+    /// Replaces the rules of a symbol, keeping them at the position of its first rule.
+    pub(crate) fn replace_rules_of(&mut self, symbol: &Symbol, rules: Vec<Rule>) {
+        let position = self.rules.iter().position(|rule| rule.symbol == *symbol);
+        self.rules.retain(|rule| rule.symbol != *symbol);
+
+        let position = position.unwrap_or(self.rules.len());
+        self.rules.splice(position..position, rules);
+    }
+
+    /// Eliminates the direct left recursion of a symbol.
+    ///
+    /// A -> A α | β
+    ///
+    /// is rewritten into
+    ///
+    /// A -> β A'
+    /// A' -> α A'
+    /// A' -> ε
+    ///
+    /// or without empty rules into
+    ///
+    /// A -> β | β A'
+    /// A' -> α | α A'
+    pub(crate) fn eliminate_direct_left_recursion(
+        &mut self,
+        symbol: &Symbol,
+        without_empty_rules: bool,
+    ) {
+        let is_left_recursive =
+            |rule: &Rule| rule.pattern.first() == Some(&AtomicPattern::Symbol(symbol.clone()));
+
+        let mut recursive_rules = Vec::new();
+        let mut non_recursive_rules = Vec::new();
+        for rule in self.rules_of(symbol).cloned().collect::<Vec<_>>() {
+            if rule.pattern.len() == 1 && is_left_recursive(&rule) {
+                self.rewrites.push(Rewrite::RuleRemoved { rule: rule.clone() });
+                self.rules.retain(|other_rule| *other_rule != rule);
+            } else if is_left_recursive(&rule) {
+                recursive_rules.push(rule);
+            } else {
+                non_recursive_rules.push(rule);
+            }
+        }
+        if recursive_rules.is_empty() || non_recursive_rules.is_empty() {
+            return;
+        }
+
+        let tail = self.fresh_symbol(symbol, "Tail");
+        let mut rules = Vec::new();
+        for rule in non_recursive_rules {
+            if without_empty_rules {
+                rules.push(rule.clone());
+            }
+            let mut rule = rule;
+            rule.push_internal(AtomicPattern::Symbol(tail.clone()));
+            rules.push(rule);
+        }
+        for recursive_rule in recursive_rules {
+            let mut rule = Rule::new(tail.clone(), []);
+            rule.extend_internal(&recursive_rule, 1);
+            rule.precedence = recursive_rule.precedence;
+            rule.span = recursive_rule.span;
+            if without_empty_rules {
+                rules.push(rule.clone());
+            }
+            rule.push_internal(AtomicPattern::Symbol(tail.clone()));
+            rules.push(rule);
+        }
+        if !without_empty_rules {
+            rules.push(Rule::new(tail.clone(), []));
+        }
+
+        self.replace_rules_of(symbol, rules);
+        self.rewrites.push(Rewrite::LeftRecursionEliminated { symbol: symbol.clone(), tail });
+    }
+
+    /// Factors the longest common prefix out of the first rules of a symbol sharing a prefix.
+    ///
+    /// A -> α β | α γ | δ
+    ///
+    /// is rewritten into
+    ///
+    /// A -> α A'
+    /// A -> δ
+    /// A' -> β
+    /// A' -> γ
+    pub(crate) fn factor_common_prefix(&mut self, symbol: &Symbol) -> Option<Symbol> {
+        let rules = self.rules_of(symbol).cloned().collect::<Vec<_>>();
+
+        let first_atomic_pattern = rules.iter().enumerate().find_map(|(i, rule)| {
+            let first_atomic_pattern = rule.pattern.first()?;
+            let shared = rules[i + 1..]
+                .iter()
+                .any(|other_rule| other_rule.pattern.first() == Some(first_atomic_pattern));
+            shared.then(|| first_atomic_pattern.clone())
+        })?;
+        let factored_rules = rules
+            .iter()
+            .filter(|rule| rule.pattern.first() == Some(&first_atomic_pattern))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let prefix_len = (1..)
+            .take_while(|&len| {
+                factored_rules.iter().all(|rule| {
+                    rule.pattern.len() >= len
+                        && rule.pattern[..len] == factored_rules[0].pattern[..len]
+                })
+            })
+            .last()
+            .unwrap_or(1);
+        let prefix = factored_rules[0].pattern[..prefix_len].to_vec();
+
+        let suffix = self.fresh_symbol(symbol, "Suffix");
+
+        let mut factored_rule = Rule::new(symbol.clone(), []);
+        factored_rule.extend_prefix_internal(&factored_rules[0], prefix_len);
+        factored_rule.push_internal(AtomicPattern::Symbol(suffix.clone()));
+        factored_rule.span = factored_rules[0].span.clone();
+
+        let mut suffix_rules: Vec<Rule> = Vec::new();
+        for factored_rule in factored_rules.iter() {
+            let mut suffix_rule = Rule::new(suffix.clone(), []);
+            suffix_rule.extend_internal(factored_rule, prefix_len);
+            suffix_rule.precedence = factored_rule.precedence.clone();
+            suffix_rule.span = factored_rule.span.clone();
+            if !suffix_rules.contains(&suffix_rule) {
+                suffix_rules.push(suffix_rule);
+            }
+        }
+
+        // Factored rule takes the place of the first rule it is factored from.
+        let mut factored = false;
+        let mut rules = rules
+            .into_iter()
+            .filter_map(|rule| {
+                if rule.pattern.first() != Some(&first_atomic_pattern) {
+                    Some(rule)
+                } else if !factored {
+                    factored = true;
+                    Some(factored_rule.clone())
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        rules.extend(suffix_rules);
+
+        self.replace_rules_of(symbol, rules);
+        self.rewrites.push(Rewrite::LeftFactored {
+            symbol: symbol.clone(),
+            prefix,
+            suffix: suffix.clone(),
+        });
+
+        Some(suffix)
+    }
+
+    /// Finishes the transformation, creating the transformed grammar.
+    ///
+    /// Auxiliary and instantiated symbols are only kept as they are if their rules are unchanged,
+    /// otherwise, they are renamed so that their rules can be displayed.
+    pub(crate) fn finish(mut self) -> (Grammar, Vec<Rewrite>) {
+        let generated_symbols = self
+            .grammar
+            .auxiliary_symbols()
+            .keys()
+            .chain(self.grammar.instantiations().keys())
+            .cloned()
+            .collect::<IndexSet<Symbol>>();
+
+        let mut kept_symbols = generated_symbols
+            .iter()
+            .filter(|symbol| {
+                let original_rules =
+                    self.grammar.rules().iter().filter(|rule| rule.symbol == **symbol);
+                original_rules.eq(self.rules_of(symbol))
+            })
+            .cloned()
+            .collect::<IndexSet<Symbol>>();
+
+        // Kept symbols can only use the generated symbols that are also kept.
+        let mut done = false;
+        while !done {
+            done = true;
+            let uses_renamed_symbol = |symbol: &Symbol| {
+                self.rules_of(symbol).any(|rule| {
+                    rule.pattern.iter().any(|atomic_pattern| {
+                        match atomic_pattern {
+                            AtomicPattern::Symbol(symbol) => {
+                                generated_symbols.contains(symbol) && !kept_symbols.contains(symbol)
+                            },
+                            AtomicPattern::Token(_) => false,
+                        }
+                    })
+                })
+            };
+            if let Some(symbol) = kept_symbols.iter().find(|symbol| uses_renamed_symbol(symbol)) {
+                let symbol = symbol.clone();
+                kept_symbols.shift_remove(&symbol);
+                done = false;
+            }
+        }
+
+        let mut renames = IndexMap::new();
+        for symbol in generated_symbols.iter() {
+            if !kept_symbols.contains(symbol) && self.rules_of(symbol).next().is_some() {
+                let renamed_symbol = self.fresh_symbol(symbol, "");
+                renames.insert(symbol.clone(), renamed_symbol);
+            }
+        }
+        for rule in self.rules.iter_mut() {
+            rule.rename_internal(&renames);
+        }
+        for rewrite in self.rewrites.iter_mut() {
+            rewrite.rename_internal(&renames);
+        }
+
+        let auxiliary_symbols = self
+            .grammar
+            .auxiliary_symbols()
+            .iter()
+            .filter(|(symbol, _)| kept_symbols.contains(*symbol))
+            .map(|(symbol, construct)| (symbol.clone(), *construct))
+            .collect();
+        let instantiations = self
+            .grammar
+            .instantiations()
+            .iter()
+            .filter(|(symbol, _)| kept_symbols.contains(*symbol))
+            .map(|(symbol, parameterized_symbol)| (symbol.clone(), parameterized_symbol.clone()))
+            .collect();
+
+        let grammar = self.grammar.with_rules_internal(
+            self.rules,
+            self.entry_symbols,
+            auxiliary_symbols,
+            instantiations,
+        );
+        (grammar, self.rewrites)
+    }
+}
+
+
+impl Rule {
+    /// Internal extension of the pattern of the rule with an atomic pattern.
+    pub(crate) fn push_internal(&mut self, atomic_pattern: AtomicPattern) {
+        self.pattern.push(atomic_pattern);
+        self.labels.push(None);
+        self.pattern_spans.push(None);
+    }
+
+    /// Internal extension of the pattern of the rule with an atomic pattern of another rule.
+    pub(crate) fn push_from_internal(&mut self, rule: &Rule, index: usize) {
+        self.pattern.push(rule.pattern[index].clone());
+        self.labels.push(rule.labels[index].clone());
+        self.pattern_spans.push(rule.pattern_spans[index].clone());
+    }
+
+    /// Internal extension of the pattern of the rule with the pattern of another rule after an index.
+    pub(crate) fn extend_internal(&mut self, rule: &Rule, from: usize) {
+        self.pattern.extend(rule.pattern[from..].iter().cloned());
+        self.labels.extend(rule.labels[from..].iter().cloned());
+        self.pattern_spans.extend(rule.pattern_spans[from..].iter().cloned());
+    }
+
+    /// Internal extension of the pattern of the rule with the pattern of another rule before an index.
+    pub(crate) fn extend_prefix_internal(&mut self, rule: &Rule, to: usize) {
+        self.pattern.extend(rule.pattern[..to].iter().cloned());
+        self.labels.extend(rule.labels[..to].iter().cloned());
+        self.pattern_spans.extend(rule.pattern_spans[..to].iter().cloned());
+    }
+
+    /// Internal renaming of the symbols of the rule.
+    fn rename_internal(&mut self, renames: &IndexMap<Symbol, Symbol>) {
+        if let Some(renamed_symbol) = renames.get(&self.symbol) {
+            self.symbol = renamed_symbol.clone();
+        }
+        for atomic_pattern in self.pattern.iter_mut() {
+            atomic_pattern.rename_internal(renames);
+        }
+    }
+}
+
+impl AtomicPattern {
+    /// Internal renaming of the symbol of the atomic pattern.
+    fn rename_internal(&mut self, renames: &IndexMap<Symbol, Symbol>) {
+        if let AtomicPattern::Symbol(symbol) = self {
+            if let Some(renamed_symbol) = renames.get(symbol) {
+                *symbol = renamed_symbol.clone();
+            }
+        }
+    }
+}
+
+impl Rewrite {
+    /// Internal renaming of the symbols of the rewrite.
+    fn rename_internal(&mut self, renames: &IndexMap<Symbol, Symbol>) {
+        let rename = |symbol: &mut Symbol| {
+            if let Some(renamed_symbol) = renames.get(symbol) {
+                *symbol = renamed_symbol.clone();
+            }
+        };
+        match self {
+            Rewrite::UnproductiveSymbolRemoved { symbol }
+            | Rewrite::UnreachableSymbolRemoved { symbol } => {
+                rename(symbol);
+            },
+            Rewrite::RuleRemoved { rule } => {
+                rule.rename_internal(renames);
+            },
+            Rewrite::RulesSubstituted { rule, symbol } => {
+                rule.rename_internal(renames);
+                rename(symbol);
+            },
+            Rewrite::LeftRecursionEliminated { symbol, tail } => {
+                rename(symbol);
+                rename(tail);
+            },
+            Rewrite::LeftFactored { symbol, prefix, suffix } => {
+                rename(symbol);
+                for atomic_pattern in prefix.iter_mut() {
+                    atomic_pattern.rename_internal(renames);
+                }
+                rename(suffix);
+            },
+            Rewrite::StartSymbolAdded { symbol, start } => {
+                rename(symbol);
+                rename(start);
+            },
+            Rewrite::TokenReplaced { symbol, .. } => {
+                rename(symbol);
+            },
+            Rewrite::RuleSplit { rule, symbols } => {
+                rule.rename_internal(renames);
+                for symbol in symbols.iter_mut() {
+                    rename(symbol);
+                }
+            },
+            Rewrite::RuleAdded { rule } => {
+                rule.rename_internal(renames);
+            },
+        }
+    }
+}
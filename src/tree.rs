@@ -24,6 +24,8 @@ pub enum Tree<'i> {
         symbol: Symbol,
         /// Matching pattern.
         pattern: Vec<Tree<'i>>,
+        /// Labels of the matching pattern.
+        labels: Vec<Option<SmolStr>>,
     },
 }
 impl Tree<'_> {
@@ -33,6 +35,75 @@ impl Tree<'_> {
     }
 }
 
+impl<'i> Tree<'i> {
+    /// Gets the first child of the node with a label.
+    pub fn child(&self, label: &str) -> Option<&Tree<'i>> {
+        match self {
+            Tree::Terminal { .. } => None,
+            Tree::NonTerminal { pattern, labels, .. } => {
+                pattern
+                    .iter()
+                    .zip(labels)
+                    .find(|(_, candidate)| candidate.as_deref() == Some(label))
+                    .map(|(branch, _)| branch)
+            },
+        }
+    }
+
+    /// Gets the children of the node with a label.
+    ///
+    /// Flattened repetitions can have multiple children with the same label.
+    pub fn children<'t>(&'t self, label: &'t str) -> impl Iterator<Item = &'t Tree<'i>> + 't {
+        let (pattern, labels) = match self {
+            Tree::Terminal { .. } => (&[][..], &[][..]),
+            Tree::NonTerminal { pattern, labels, .. } => (pattern.as_slice(), labels.as_slice()),
+        };
+        pattern
+            .iter()
+            .zip(labels)
+            .filter(move |(_, candidate)| candidate.as_deref() == Some(label))
+            .map(|(branch, _)| branch)
+    }
+
+    /// Flattens the nodes of the auxiliary symbols of the EBNF constructs in the parse tree.
+    ///
+    /// Left recursive chains of repetitions (e.g., `Value*`, `Value+`) are collapsed
+    /// into a single node with all the repeated elements as its direct children.
+    pub fn flatten(self, grammar: &Grammar) -> Tree<'i> {
+        match self {
+            Tree::Terminal { .. } => self,
+            Tree::NonTerminal { symbol, pattern, labels } => {
+                let repetition = matches!(
+                    grammar.auxiliary_symbols().get(&symbol),
+                    Some(EbnfConstruct::ZeroOrMore | EbnfConstruct::OneOrMore),
+                );
+
+                let mut flattened_pattern = Vec::with_capacity(pattern.len());
+                let mut flattened_labels = Vec::with_capacity(labels.len());
+                for (index, (branch, label)) in pattern.into_iter().zip(labels).enumerate() {
+                    let branch = branch.flatten(grammar);
+                    match branch {
+                        Tree::NonTerminal {
+                            symbol: branch_symbol,
+                            pattern: branch_pattern,
+                            labels: branch_labels,
+                        } if repetition && index == 0 && branch_symbol == symbol => {
+                            flattened_pattern.extend(branch_pattern);
+                            flattened_labels.extend(branch_labels);
+                        },
+                        branch => {
+                            flattened_pattern.push(branch);
+                            flattened_labels.push(label);
+                        },
+                    }
+                }
+
+                Tree::NonTerminal { symbol, pattern: flattened_pattern, labels: flattened_labels }
+            },
+        }
+    }
+}
+
 impl Display for Tree<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fn display_name_of(tree: &Tree) -> String {
@@ -40,6 +40,43 @@ where
     map_serializer.end()
 }
 
+/// Serializes a list of regex objects to a list of regex strings.
+#[cfg(feature = "serde")]
+pub fn serialize_regex_vec<S>(regexes: &[Regex], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut seq_serializer = serializer.serialize_seq(Some(regexes.len()))?;
+    for regex in regexes {
+        seq_serializer.serialize_element(&regex.to_string())?;
+    }
+    seq_serializer.end()
+}
+
+/// Deserializes a map of regex strings to a map of regex objects, recompiling the regexes.
+#[cfg(feature = "serde")]
+pub fn deserialize_regex_map<'de, D>(
+    deserializer: D,
+) -> Result<IndexMap<RegexToken, Regex>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let map = IndexMap::<RegexToken, String>::deserialize(deserializer)?;
+    map.into_iter()
+        .map(|(key, value)| Ok((key, Regex::new(&value).map_err(D::Error::custom)?)))
+        .collect()
+}
+
+/// Deserializes a list of regex strings to a list of regex objects, recompiling the regexes.
+#[cfg(feature = "serde")]
+pub fn deserialize_regex_vec<'de, D>(deserializer: D) -> Result<Vec<Regex>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let regexes = Vec::<String>::deserialize(deserializer)?;
+    regexes.into_iter().map(|regex| Regex::new(&regex).map_err(D::Error::custom)).collect()
+}
+
 
 /// Counts the number of new lines in a slice and returns the offset after the last new line.
 pub fn count_new_lines(slice: &str) -> (usize, Option<usize>) {
@@ -60,3 +97,95 @@ pub fn count_new_lines(slice: &str) -> (usize, Option<usize>) {
 pub fn count_col_position(slice: &str) -> usize {
     slice.chars().count() + 1
 }
+
+
+/// Unescapes the escape sequences (e.g., `\'`, `\\`, `\n`, `\u{3b5}`) in a constant token literal.
+///
+/// Returns `None` if the literal has an unknown or an incomplete escape sequence.
+pub fn unescape_constant_token(literal: &str) -> Option<SmolStr> {
+    let mut unescaped = String::with_capacity(literal.len());
+    let mut chars = literal.chars();
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            unescaped.push(char);
+            continue;
+        }
+        let escaped = match chars.next()? {
+            '\'' => '\'',
+            '\\' => '\\',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'u' => {
+                let rest = chars.as_str().strip_prefix('{')?;
+                let (code, rest) = rest.split_once('}')?;
+                chars = rest.chars();
+                char::from_u32(u32::from_str_radix(code, 16).ok()?)?
+            },
+            _ => return None,
+        };
+        unescaped.push(escaped);
+    }
+    Some(unescaped.into())
+}
+
+/// Escapes a constant token so it can be written as a literal in a grammar.
+pub fn escape_constant_token(constant_token: &str) -> String {
+    let mut escaped = String::with_capacity(constant_token.len());
+    for char in constant_token.chars() {
+        match char {
+            '\'' => escaped.push_str("\\'"),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            char if char.is_control() => escaped.push_str(&format!("\\u{{{:x}}}", char as u32)),
+            char => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+/// Unescapes the slashes (i.e., `\/`) in a regex literal.
+///
+/// Other escape sequences are left as is, since they are part of the regex syntax.
+pub fn unescape_regex(literal: &str) -> SmolStr {
+    let mut unescaped = String::with_capacity(literal.len());
+    let mut chars = literal.chars();
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => {
+                match chars.next() {
+                    Some('/') => unescaped.push('/'),
+                    Some(escaped) => {
+                        unescaped.push('\\');
+                        unescaped.push(escaped);
+                    },
+                    None => unescaped.push('\\'),
+                }
+            },
+            char => unescaped.push(char),
+        }
+    }
+    unescaped.into()
+}
+
+/// Escapes the slashes in a regex so it can be written as a literal in a grammar.
+pub fn escape_regex(regex: &str) -> String {
+    let mut escaped = String::with_capacity(regex.len());
+    let mut chars = regex.chars();
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => {
+                escaped.push('\\');
+                if let Some(escaped_char) = chars.next() {
+                    escaped.push(escaped_char);
+                }
+            },
+            '/' => escaped.push_str("\\/"),
+            '\n' => escaped.push_str("\\n"),
+            char => escaped.push(char),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,192 @@
+mod common;
+
+use dotlr::{
+    BisonWarning,
+    Grammar,
+    GrammarError,
+    Parser,
+};
+
+
+#[test]
+fn correctly_translating_bison_grammars() {
+    let (grammar_string, warnings) =
+        Grammar::translate_bison(common::grammars::BISON_CALCULATOR).unwrap();
+    assert_eq!(
+        grammar_string.trim(),
+        r#"
+%start Expression
+%left '+' '-'
+%left '*' '/'
+%right '^'
+%right %uminus
+
+Expression -> Expression '+' Expression
+Expression -> Expression '-' Expression
+Expression -> Expression '*' Expression
+Expression -> Expression '/' Expression
+Expression -> Expression '^' Expression
+Expression -> '-' Expression %prec %uminus
+Expression -> '(' Expression ')'
+Expression -> %number
+
+%number -> /NUMBER/
+        "#
+        .trim(),
+    );
+    assert_eq!(
+        warnings.iter().map(|warning| warning.to_string()).collect::<Vec<_>>(),
+        ["token NUMBER at line 14 column 16 has no lexical definition \
+             so it is translated as %number matching its name",],
+    );
+}
+
+#[test]
+fn correctly_parsing_with_grammars_of_bison_grammars() {
+    let (grammar, _) = Grammar::parse_bison(common::grammars::BISON_CALCULATOR).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let expression = "- NUMBER + (NUMBER ^ NUMBER ^ NUMBER) * NUMBER";
+    let tokens = parser.tokenize(expression).unwrap();
+
+    let parse_tree = parser.parse(tokens).unwrap();
+    assert_eq!(
+        parse_tree.to_string().trim(),
+        r#"
+Expression
+├─ Expression
+│  ├─ -
+│  └─ Expression
+│     └─ NUMBER
+├─ +
+└─ Expression
+   ├─ Expression
+   │  ├─ (
+   │  ├─ Expression
+   │  │  ├─ Expression
+   │  │  │  └─ NUMBER
+   │  │  ├─ ^
+   │  │  └─ Expression
+   │  │     ├─ Expression
+   │  │     │  └─ NUMBER
+   │  │     ├─ ^
+   │  │     └─ Expression
+   │  │        └─ NUMBER
+   │  └─ )
+   ├─ *
+   └─ Expression
+      └─ NUMBER
+        "#
+        .trim(),
+    );
+}
+
+#[test]
+fn correctly_translating_aliased_tokens_of_bison_grammars() {
+    let bison_grammar = r#"
+%token ARROW "->" ID
+%%
+rule: ID ARROW ids | ID "->" ;
+ids: ids ID | ID ;
+    "#;
+
+    let (grammar_string, warnings) = Grammar::translate_bison(bison_grammar).unwrap();
+    assert_eq!(
+        grammar_string.trim(),
+        r#"
+Rule -> %id '->' Ids
+Rule -> %id '->'
+
+Ids -> Ids %id
+Ids -> %id
+
+%id -> /ID/
+        "#
+        .trim(),
+    );
+    assert_eq!(
+        warnings.iter().map(|warning| warning.to_string()).collect::<Vec<_>>(),
+        ["token ID at line 2 column 19 has no lexical definition \
+             so it is translated as %id matching its name",],
+    );
+
+    let (grammar, _) = Grammar::parse_bison(bison_grammar).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let tokens = parser.tokenize("ID -> ID ID").unwrap();
+    assert!(parser.parse(tokens).is_ok());
+}
+
+#[test]
+fn correctly_reporting_untranslated_constructs_of_bison_grammars() {
+    let (grammar_string, warnings) =
+        Grammar::translate_bison(common::grammars::BISON_STATEMENTS).unwrap();
+    assert_eq!(
+        grammar_string.trim(),
+        r#"
+Program -> ε
+Program -> Program Statement
+
+Statement -> name:%identifier '=' v:Value ';'
+Statement -> 'if' '(' Value ')' Statement
+Statement -> 'if' '(' Value ')' Statement 'else' Statement
+
+Value -> %number
+Value -> %identifier
+Value -> %string
+
+%identifier -> /IDENTIFIER/
+%number -> /NUMBER/
+%string -> /STRING/
+        "#
+        .trim(),
+    );
+
+    let untranslated = warnings
+        .iter()
+        .filter(|warning| !matches!(warning, BisonWarning::PlaceholderRegex { .. }))
+        .map(|warning| warning.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        untranslated,
+        [
+            "directive %glr-parser at line 3 column 1 is not translated",
+            "directive %expect at line 4 column 1 is not translated",
+            "directive %dprec at line 15 column 49 is not translated",
+            "directive %dprec at line 16 column 51 is not translated",
+            "rule of statement at line 17 column 7 uses the error token so it is removed",
+            "symbol STRING at line 20 column 30 is neither declared as a token nor defined by rules \
+             so it is translated as a token",
+        ],
+    );
+
+    let span = warnings[0].span();
+    assert_eq!(
+        &common::grammars::BISON_STATEMENTS[span.offset..span.offset + span.len],
+        "%glr-parser",
+    );
+}
+
+#[test]
+fn raising_correct_error_when_parsing_bison_grammar_without_rules_section() {
+    let error = Grammar::parse_bison("%token NUMBER\n%left '+'\n").unwrap_err();
+    match error {
+        GrammarError::UnexpectedEof { expected, .. } => {
+            assert_eq!(expected.as_slice(), ["%%"]);
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn raising_correct_error_when_parsing_bison_grammar_with_unexpected_token() {
+    let error = Grammar::parse_bison("%%\nexp: exp '+' exp\n | NUM\n | : NUM\n;\n").unwrap_err();
+    match error {
+        GrammarError::UnexpectedToken { line, column, token, expected, .. } => {
+            assert_eq!((line, column), (4, 4));
+            assert_eq!(token.as_str(), ":");
+            assert_eq!(expected.as_slice(), ["symbol", "token"]);
+        },
+        _ => unreachable!(),
+    }
+}
@@ -3,16 +3,49 @@ pub mod grammars {
     // ----------------
     // Correct grammars
     // ----------------
-    pub const CORRECT: &[&str] =
-        &[BINARY_ADDITION, CALCULATOR, CONDITIONAL, G10, G11, JSON, NOT_LALR];
+    pub const CORRECT: &[&str] = &[
+        BINARY_ADDITION,
+        CALCULATOR,
+        CALCULATOR_ALTERNATION,
+        CALCULATOR_LABELED,
+        CALCULATOR_PRECEDENCE,
+        CONDITIONAL,
+        G10,
+        G11,
+        INTERPOLATION,
+        JSON,
+        JSON_EBNF,
+        KEYWORDS,
+        LISTS,
+        NOT_LALR,
+        OPTIONAL,
+        QUERY,
+        STATEMENTS,
+        SUMS,
+    ];
 
     pub const BINARY_ADDITION: &str = include_str!("../assets/grammars/correct/binary-addition.lr");
     pub const CALCULATOR: &str = include_str!("../assets/grammars/correct/calculator.lr");
+    pub const CALCULATOR_ALTERNATION: &str =
+        include_str!("../assets/grammars/correct/calculator.alternation.lr");
+    pub const CALCULATOR_LABELED: &str =
+        include_str!("../assets/grammars/correct/calculator.labeled.lr");
+    pub const CALCULATOR_PRECEDENCE: &str =
+        include_str!("../assets/grammars/correct/calculator.precedence.lr");
     pub const CONDITIONAL: &str = include_str!("../assets/grammars/correct/conditional.lr");
     pub const G10: &str = include_str!("../assets/grammars/correct/g10.lr");
     pub const G11: &str = include_str!("../assets/grammars/correct/g11.lr");
+    pub const INTERPOLATION: &str = include_str!("../assets/grammars/correct/interpolation.lr");
     pub const JSON: &str = include_str!("../assets/grammars/correct/json.lr");
+    pub const JSON_EBNF: &str = include_str!("../assets/grammars/correct/json.ebnf.lr");
+    pub const JSON_OPTIMIZED: &str = include_str!("../assets/grammars/correct/json.optimized.lr");
+    pub const KEYWORDS: &str = include_str!("../assets/grammars/correct/keywords.lr");
+    pub const LISTS: &str = include_str!("../assets/grammars/correct/lists.lr");
     pub const NOT_LALR: &str = include_str!("../assets/grammars/correct/not-lalr.lr");
+    pub const OPTIONAL: &str = include_str!("../assets/grammars/correct/optional.lr");
+    pub const QUERY: &str = include_str!("../assets/grammars/correct/query.lr");
+    pub const STATEMENTS: &str = include_str!("../assets/grammars/correct/statements.lr");
+    pub const SUMS: &str = include_str!("../assets/grammars/correct/sums.lr");
 
     // --------------------------------
     // Syntactically incorrect grammars
@@ -44,4 +77,24 @@ pub mod grammars {
         include_str!("../assets/grammars/incorrect/semantic/undefined-regex-token.lr");
     pub const UNDEFINED_SYMBOL: &str =
         include_str!("../assets/grammars/incorrect/semantic/undefined-symbol.lr");
+
+    // ---------------------
+    // Grammars with imports
+    // ---------------------
+    pub const CORRECT_IMPORTS_DIRECTORY: &str =
+        concat!(env!("CARGO_MANIFEST_DIR"), "/assets/grammars/correct/imports");
+    pub const INCORRECT_IMPORTS_DIRECTORY: &str =
+        concat!(env!("CARGO_MANIFEST_DIR"), "/assets/grammars/incorrect/imports");
+
+    pub const PROGRAM: &str = include_str!("../assets/grammars/correct/imports/program.lr");
+    pub const CYCLIC_IMPORT: &str =
+        include_str!("../assets/grammars/incorrect/imports/cyclic-a.lr");
+    pub const INVALID_IMPORT: &str =
+        include_str!("../assets/grammars/incorrect/imports/invalid-import.lr");
+
+    // --------------
+    // Bison grammars
+    // --------------
+    pub const BISON_CALCULATOR: &str = include_str!("../assets/grammars/bison/calculator.y");
+    pub const BISON_STATEMENTS: &str = include_str!("../assets/grammars/bison/statements.y");
 }
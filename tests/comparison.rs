@@ -0,0 +1,77 @@
+mod common;
+
+use dotlr::{
+    Grammar,
+    Parser,
+    Token,
+};
+
+
+#[test]
+fn correctly_comparing_equivalent_grammars() {
+    let json = Grammar::parse(common::grammars::JSON).unwrap();
+    for other_grammar_string in [common::grammars::JSON_EBNF, common::grammars::JSON_OPTIMIZED] {
+        let other_json = Grammar::parse(other_grammar_string).unwrap();
+
+        let comparison = json.compare(&other_json, 6, 10000).unwrap();
+        assert!(comparison.is_equivalent());
+        assert!(comparison.is_exhaustive());
+        assert!(comparison.compared_sentences() > 0);
+    }
+}
+
+#[test]
+fn correctly_reporting_counterexamples() {
+    let grammar = Grammar::parse("E -> E '+' %n | %n\n%n -> /[0-9]+/\n").unwrap();
+    let other_grammar = Grammar::parse("E -> %n '+' E | %n '-' E | %n\n%n -> /[0-9]+/\n").unwrap();
+
+    let comparison = grammar.compare(&other_grammar, 3, 10000).unwrap();
+    assert!(!comparison.is_equivalent());
+    assert_eq!(comparison.only_accepted_by_first(), Vec::<Vec<Token>>::new());
+    assert_eq!(
+        comparison
+            .only_accepted_by_second()
+            .iter()
+            .map(|sentence| {
+                sentence.iter().map(|token| token.to_string()).collect::<Vec<_>>().join(" ")
+            })
+            .collect::<Vec<_>>(),
+        ["%n '-' %n"],
+    );
+}
+
+#[test]
+fn correctly_comparing_sentences_rejected_by_their_own_parser() {
+    // Non associative '==' rejects 'x' '==' 'x' '==' 'x' even if the grammar derives it.
+    let grammar = Grammar::parse("%nonassoc '=='\nE -> E '==' E | 'x'\n").unwrap();
+    let other_grammar = Grammar::parse("E -> 'x' '==' 'x' | 'x'\n").unwrap();
+
+    let comparison = grammar.compare(&other_grammar, 5, 10000).unwrap();
+    assert!(comparison.is_equivalent());
+}
+
+#[test]
+fn correctly_comparing_with_lalr_parsers() {
+    let grammar = Grammar::parse(common::grammars::G10).unwrap();
+    let parser = Parser::lalr(grammar.clone()).unwrap();
+    let other_parser = Parser::lr(grammar).unwrap();
+
+    let comparison = parser.compare(&other_parser, 5, 10000);
+    assert!(comparison.is_equivalent());
+    assert_eq!(comparison.max_length(), 5);
+}
+
+#[test]
+fn correctly_limiting_the_number_of_sentences() {
+    let grammar = Grammar::parse("L -> L 'a' | L 'b' | ε\n").unwrap();
+    let other_grammar = Grammar::parse("L -> 'a' L | 'b' L | ε\n").unwrap();
+
+    let comparison = grammar.compare(&other_grammar, 10, 10000).unwrap();
+    assert!(comparison.is_exhaustive());
+    assert_eq!(comparison.compared_sentences(), 2 * ((1 << 11) - 1));
+
+    let comparison = grammar.compare(&other_grammar, 10, 100).unwrap();
+    assert!(!comparison.is_exhaustive());
+    assert_eq!(comparison.compared_sentences(), 2 * 100);
+    assert!(comparison.is_equivalent());
+}
@@ -0,0 +1,286 @@
+mod common;
+
+use dotlr::{
+    ExportFormat,
+    ExportWarning,
+    Grammar,
+};
+
+
+#[test]
+fn correctly_exporting_correct_grammars() {
+    for grammar_string in common::grammars::CORRECT {
+        let grammar = Grammar::parse(grammar_string).unwrap();
+
+        let (bison_grammar, _) = grammar.to_bison();
+        assert!(bison_grammar.contains("\n%%\n"));
+
+        let (lark_grammar, _) = grammar.to_lark();
+        assert!(lark_grammar.starts_with("?start: "));
+
+        let (tree_sitter_grammar, _) = grammar.to_tree_sitter("test");
+        assert!(tree_sitter_grammar.starts_with("module.exports = grammar({\n  name: 'test',\n"));
+    }
+}
+
+#[test]
+fn correctly_exporting_grammars_to_bison() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR_PRECEDENCE).unwrap();
+
+    let (bison_grammar, warnings) = grammar.to_bison();
+    assert_eq!(
+        bison_grammar.trim(),
+        r#"
+%{
+int yylex(void);
+void yyerror(const char *message);
+%}
+
+%token F /* [0-9]+(\.[0-9]+)? */
+
+%left '+' '-'
+%left '*' '/'
+%right '^'
+%right UMINUS
+
+%%
+
+expr
+    : expr '+' expr
+    | expr '-' expr
+    | expr '*' expr
+    | expr '/' expr
+    | expr '^' expr
+    | '-' expr %prec UMINUS
+    | '(' expr ')'
+    | F
+    ;
+
+%%
+        "#
+        .trim(),
+    );
+    assert_eq!(warnings, []);
+}
+
+#[test]
+fn correctly_exporting_grammars_with_named_tokens_to_bison() {
+    let grammar = Grammar::parse(
+        "S -> 'if' E 'then' S | 'null' | E\nE -> E '==' E | %v\n%v -> /[a-z]+/\n%nonassoc '=='\n",
+    )
+    .unwrap();
+
+    let (bison_grammar, _) = grammar.to_bison();
+    assert!(
+        bison_grammar.contains(
+            r#"
+%token V /* [a-z]+ */
+%token IF "if"
+%token THEN "then"
+%token NULL2 "null"
+%token EQUAL_EQUAL "=="
+
+%nonassoc "=="
+        "#
+            .trim(),
+        )
+    );
+    assert!(
+        bison_grammar.contains(
+            r#"
+s
+    : "if" e "then" s
+    | "null"
+    | e
+    ;
+        "#
+            .trim(),
+        )
+    );
+}
+
+#[test]
+fn correctly_exporting_grammars_to_lark() {
+    let grammar = Grammar::parse(common::grammars::JSON_EBNF).unwrap();
+
+    let (lark_grammar, warnings) = grammar.to_lark();
+    assert_eq!(
+        lark_grammar.trim(),
+        r#"
+?start: json
+
+json: value
+
+value: null
+     | boolean
+     | number
+     | string
+     | array
+     | object
+
+null: "null"
+
+boolean: "true"
+       | "false"
+
+number: F
+
+string: S
+
+array: "[" (value ("," value)*)? "]"
+
+object: "{" (member ("," member)*)? "}"
+
+member: string ":" value
+
+F: /[-]?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?/
+S: /"([^"\\]|\\["\\bnfrt]|u[a-fA-F0-9]{4})*"/
+
+%import common.WS
+%ignore WS
+        "#
+        .trim(),
+    );
+    assert_eq!(warnings, []);
+}
+
+#[test]
+fn correctly_exporting_grammars_to_tree_sitter() {
+    let grammar = Grammar::parse(common::grammars::KEYWORDS).unwrap();
+
+    let (tree_sitter_grammar, warnings) = grammar.to_tree_sitter("keywords");
+    assert_eq!(
+        tree_sitter_grammar.trim(),
+        r#"
+module.exports = grammar({
+  name: 'keywords',
+
+  rules: {
+    program: $ => repeat($.statement),
+
+    statement: $ => choice(
+      seq('if', $.value, 'then', $.statement),
+      seq($.identifier, '=', $.value, ';'),
+    ),
+
+    value: $ => choice(
+      $.identifier,
+      $.number,
+      $.color,
+    ),
+
+    identifier: $ => /[a-z_][a-z0-9_]*/,
+
+    number: $ => /[0-9]+/,
+
+    color: $ => token(prec(1, /[a-f]{6}/)),
+  },
+});
+        "#
+        .trim(),
+    );
+    assert_eq!(warnings, []);
+}
+
+#[test]
+fn correctly_exporting_grammars_with_precedence_and_labels_to_tree_sitter() {
+    let grammar = Grammar::parse(
+        "E -> lhs:E '+' rhs:E | lhs:E '^' rhs:E | %n\n%n -> /[0-9]+/\n%left '+'\n%right '^'\n",
+    )
+    .unwrap();
+
+    let (tree_sitter_grammar, _) = grammar.to_tree_sitter("calculator");
+    assert!(
+        tree_sitter_grammar.contains(
+            r#"
+    e: $ => choice(
+      prec.left(1, seq(field('lhs', $.e), '+', field('rhs', $.e))),
+      prec.right(2, seq(field('lhs', $.e), '^', field('rhs', $.e))),
+      $.n,
+    ),
+        "#
+            .trim_end(),
+        )
+    );
+}
+
+#[test]
+fn correctly_exporting_grammars_with_empty_rules_to_tree_sitter() {
+    let grammar =
+        Grammar::parse("S -> 'x' A B C\nA -> 'a' | ε\nB -> 'b'*\nC -> D E\nD -> 'd'?\nE -> ε\n")
+            .unwrap();
+
+    // Only the start rule can match the empty string in tree-sitter grammars.
+    let (tree_sitter_grammar, _) = grammar.to_tree_sitter("empty");
+    assert!(
+        tree_sitter_grammar.contains(
+            r#"
+    s: $ => seq('x', optional($.a), optional($.b), optional($.c)),
+
+    a: $ => 'a',
+
+    b: $ => repeat1('b'),
+
+    c: $ => $.d,
+
+    d: $ => 'd',
+        "#
+            .trim_end(),
+        )
+    );
+    assert!(!tree_sitter_grammar.contains("    e: "));
+}
+
+#[test]
+fn correctly_translating_regexes_when_exporting() {
+    let grammar =
+        Grammar::parse("S -> %w %t\n%w -> /(?<word>\\p{L}+)\\z/\n%t -> /[]a\\/]/\n").unwrap();
+
+    let (lark_grammar, warnings) = grammar.to_lark();
+    assert!(lark_grammar.contains("W: /(?P<word>\\p{L}+)\\Z/\nT: /[]a\\/]/\n"));
+    assert_eq!(
+        warnings,
+        [ExportWarning::UnsupportedRegex {
+            regex_token: "w".into(),
+            construct: "unicode classes".into(),
+            format: ExportFormat::Lark,
+        }],
+    );
+
+    let (tree_sitter_grammar, warnings) = grammar.to_tree_sitter("regexes");
+    assert!(tree_sitter_grammar.contains("    w: $ => /(?<word>\\p{L}+)\\z/,\n"));
+    assert!(tree_sitter_grammar.contains("    t: $ => /[\\]a\\/]/,\n"));
+    assert_eq!(
+        warnings.iter().map(|warning| warning.to_string()).collect::<Vec<_>>(),
+        ["text anchors in the regex of %w cannot be expressed in tree-sitter"],
+    );
+}
+
+#[test]
+fn correctly_warning_about_unsupported_features_when_exporting() {
+    let grammar = Grammar::parse(common::grammars::INTERPOLATION).unwrap();
+    for (_, warnings) in [grammar.to_bison(), grammar.to_lark(), grammar.to_tree_sitter("test")] {
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].to_string().starts_with("lexer modes cannot be expressed in "));
+    }
+
+    let grammar = Grammar::parse(common::grammars::CALCULATOR_LABELED).unwrap();
+    let (_, warnings) = grammar.to_lark();
+    assert_eq!(
+        warnings,
+        [ExportWarning::UnsupportedFeature {
+            feature: "labels".into(),
+            format: ExportFormat::Lark
+        }],
+    );
+
+    let grammar = Grammar::parse(common::grammars::QUERY).unwrap();
+    let (_, warnings) = grammar.to_bison();
+    assert_eq!(
+        warnings.iter().map(|warning| warning.to_string()).collect::<Vec<_>>(),
+        [
+            "keywords cannot be expressed in bison",
+            "case insensitive tokens cannot be expressed in bison",
+        ],
+    );
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use dotlr::{
+    Grammar,
+    GrammarError,
+};
+
+
+#[test]
+fn correctly_formatting_grammars() {
+    let grammar_string = r#"
+
+# Expressions.
+%left   '+'  '-'
+
+
+Expr->Expr '+' Expr   # addition
+  |  Expr '-' Expr
+# parenthesized
+   | '(' Expr ')' |%n
+List( X ,Sep )   ->   X ( Sep X ) *
+Call -> name:%n '(' List( Expr , ',' ) ? ')'
+
+%n ->   /[0-9]+/
+
+
+"#;
+
+    let formatted = Grammar::format(grammar_string).unwrap();
+    assert_eq!(
+        formatted,
+        r#"# Expressions.
+%left '+' '-'
+
+Expr -> Expr '+' Expr # addition
+     | Expr '-' Expr
+     # parenthesized
+     | '(' Expr ')' | %n
+List(X, Sep) -> X (Sep X)*
+Call -> name:%n '(' List(Expr, ',')? ')'
+
+%n -> /[0-9]+/
+"#,
+    );
+
+    let grammar = Grammar::parse(grammar_string).unwrap();
+    let formatted_grammar = Grammar::parse(&formatted).unwrap();
+    assert_eq!(formatted_grammar.to_string(), grammar.to_string());
+}
+
+#[test]
+fn correctly_formatting_grammars_idempotently() {
+    for grammar_string in common::grammars::CORRECT {
+        let formatted = Grammar::format(grammar_string).unwrap();
+        assert_eq!(Grammar::format(&formatted).unwrap(), formatted);
+
+        let grammar = Grammar::parse(grammar_string).unwrap();
+        let formatted_grammar = Grammar::parse(&formatted).unwrap();
+        assert_eq!(formatted_grammar.to_string(), grammar.to_string());
+    }
+}
+
+#[test]
+fn correctly_keeping_formatted_grammars_unchanged() {
+    for grammar_string in
+        [common::grammars::CALCULATOR_PRECEDENCE, common::grammars::LISTS, common::grammars::SUMS]
+    {
+        assert_eq!(Grammar::format(grammar_string).unwrap(), grammar_string);
+    }
+}
+
+#[test]
+fn raising_correct_error_when_formatting_grammars_with_invalid_tokens() {
+    let error = Grammar::format("E -> 'a'\nF -> $\n").unwrap_err();
+    match error {
+        GrammarError::UnexpectedToken { line, token, .. } => {
+            assert_eq!(line, 2);
+            assert_eq!(token.as_str(), "$");
+        },
+        _ => unreachable!(),
+    }
+}
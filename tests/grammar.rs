@@ -1,11 +1,27 @@
 mod common;
 
-use dotlr::{
-    ConstantToken,
-    Grammar,
-    RegexToken,
-    Rule,
-    Symbol,
+use {
+    dotlr::{
+        Associativity,
+        AtomicPattern,
+        ConstantToken,
+        EbnfConstruct,
+        FileSystemResolver,
+        Grammar,
+        GrammarBuilder,
+        GrammarResolver,
+        ModeTransition,
+        Parser,
+        ParserError,
+        Precedence,
+        RegexToken,
+        Rule,
+        Span,
+        Symbol,
+        Token,
+    },
+    itertools::Itertools,
+    smol_str::SmolStr,
 };
 
 
@@ -31,7 +47,7 @@ fn failing_to_parse_syntactically_incorrect_grammars() {
 fn raising_correct_error_when_parsing_unexpected_token_grammar() {
     let error = Grammar::parse(common::grammars::UNEXPECTED_TOKEN).unwrap_err();
     match error {
-        dotlr::GrammarError::UnexpectedToken { line, column, token, expected } => {
+        dotlr::GrammarError::UnexpectedToken { line, column, token, expected, .. } => {
             assert_eq!(line, 1);
             assert_eq!(column, 6);
             assert_eq!(token.as_str(), "->");
@@ -49,9 +65,9 @@ fn raising_correct_error_when_parsing_unexpected_token_grammar() {
 fn raising_correct_error_when_parsing_invalid_regex_grammar() {
     let error = Grammar::parse(common::grammars::INVALID_REGEX).unwrap_err();
     match error {
-        dotlr::GrammarError::InvalidRegex { line, column, regex } => {
+        dotlr::GrammarError::InvalidRegex { line, column, regex, .. } => {
             assert_eq!(line, 3);
-            assert_eq!(column, 8);
+            assert_eq!(column, 7);
             assert_eq!(regex.as_str(), "/[1-9][0-9+/");
         },
         _ => unreachable!(),
@@ -196,3 +212,1341 @@ Term -> %f
         ],
     );
 }
+
+#[test]
+fn correctly_parsing_optional_grammar() {
+    let grammar = Grammar::parse(common::grammars::OPTIONAL).unwrap();
+
+    assert_eq!(
+        grammar.to_string().trim(),
+        r#"
+
+Declaration -> 'let' %identifier Annotation Initializer ';'
+Annotation -> ':' %identifier
+Annotation -> ε
+Initializer -> '=' %number
+Initializer -> ε
+
+%identifier -> /^[a-zA-Z_][a-zA-Z0-9_]*/
+%number -> /^[0-9]+/
+
+        "#
+        .trim(),
+    );
+
+    assert_eq!(
+        grammar.constant_tokens().iter().map(|token| token.as_str()).collect::<Vec<_>>(),
+        ["let", ";", ":", "="],
+    );
+
+    #[rustfmt::skip]
+    assert_eq!(
+        &grammar.rules()[1..],
+        [
+            // Annotation -> ':' %identifier
+            Rule::new(
+                "Annotation",
+                [
+                    ConstantToken::from(":").into(),
+                    RegexToken::from("identifier").into(),
+                ]
+            ),
+            // Annotation -> ε
+            Rule::new(
+                "Annotation",
+                []
+            ),
+
+            // Initializer -> '=' %number
+            Rule::new(
+                "Initializer",
+                [
+                    ConstantToken::from("=").into(),
+                    RegexToken::from("number").into(),
+                ]
+            ),
+            // Initializer -> ε
+            Rule::new(
+                "Initializer",
+                []
+            ),
+        ],
+    );
+}
+
+#[test]
+fn raising_correct_error_when_parsing_non_empty_pattern_after_empty_pattern() {
+    let error = Grammar::parse("S -> ε 'a'").unwrap_err();
+    match error {
+        dotlr::GrammarError::UnexpectedToken { line, column, token, expected, .. } => {
+            assert_eq!(line, 1);
+            assert_eq!(column, 8);
+            assert_eq!(token.as_str(), "'a'");
+            assert_eq!(
+                expected.iter().map(|token| token.as_str()).collect::<Vec<_>>(),
+                ["new line"]
+            );
+        },
+        _ => unreachable!(),
+    }
+}
+
+
+#[test]
+fn correctly_parsing_json_ebnf_grammar() {
+    let grammar = Grammar::parse(common::grammars::JSON_EBNF).unwrap();
+
+    assert_eq!(
+        grammar.to_string().trim(),
+        r#"
+
+Json -> Value
+Value -> Null
+Value -> Boolean
+Value -> Number
+Value -> String
+Value -> Array
+Value -> Object
+Null -> 'null'
+Boolean -> 'true'
+Boolean -> 'false'
+Number -> %f
+String -> %s
+Array -> '[' (Value (',' Value)*)? ']'
+Object -> '{' (Member (',' Member)*)? '}'
+Member -> String ':' Value
+
+%f -> /^[-]?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?/
+%s -> /^"([^"\\]|\\["\\bnfrt]|u[a-fA-F0-9]{4})*"/
+
+        "#
+        .trim(),
+    );
+
+    assert_eq!(
+        grammar
+            .auxiliary_symbols()
+            .iter()
+            .map(|(symbol, construct)| (symbol.as_str(), *construct))
+            .collect::<Vec<_>>(),
+        [
+            ("(',' Value)*", EbnfConstruct::ZeroOrMore),
+            ("(Value (',' Value)*)?", EbnfConstruct::Optional),
+            ("(',' Member)*", EbnfConstruct::ZeroOrMore),
+            ("(Member (',' Member)*)?", EbnfConstruct::Optional),
+        ],
+    );
+
+    assert_eq!(
+        grammar.rules()[12..17].iter().map(|rule| rule.to_string()).collect::<Vec<_>>(),
+        [
+            "Array -> '[' (Value (',' Value)*)? ']'",
+            "(',' Value)* -> (',' Value)* ',' Value",
+            "(',' Value)* -> ε",
+            "(Value (',' Value)*)? -> Value (',' Value)*",
+            "(Value (',' Value)*)? -> ε",
+        ],
+    );
+}
+
+#[test]
+fn correctly_desugaring_ebnf_constructs() {
+    let grammar = Grammar::parse("S -> 'a'+ 'b'? (S 'd') ('e')").unwrap();
+    assert_eq!(
+        grammar.rules().iter().map(|rule| rule.to_string()).collect::<Vec<_>>(),
+        [
+            "S -> 'a'+ 'b'? (S 'd') 'e'",
+            "'a'+ -> 'a'+ 'a'",
+            "'a'+ -> 'a'",
+            "'b'? -> 'b'",
+            "'b'? -> ε",
+            "(S 'd') -> S 'd'",
+        ],
+    );
+    assert_eq!(
+        grammar
+            .auxiliary_symbols()
+            .iter()
+            .map(|(symbol, construct)| (symbol.as_str(), *construct))
+            .collect::<Vec<_>>(),
+        [
+            ("'a'+", EbnfConstruct::OneOrMore),
+            ("'b'?", EbnfConstruct::Optional),
+            ("(S 'd')", EbnfConstruct::Group),
+        ],
+    );
+}
+
+#[test]
+fn raising_correct_error_when_parsing_repetition_without_element() {
+    let error = Grammar::parse("S -> * 'a'").unwrap_err();
+    match error {
+        dotlr::GrammarError::UnexpectedToken { line, column, token, expected, .. } => {
+            assert_eq!(line, 1);
+            assert_eq!(column, 6);
+            assert_eq!(token.as_str(), "*");
+            assert_eq!(
+                expected.iter().map(|token| token.as_str()).collect::<Vec<_>>(),
+                ["symbol", "constant token", "regular expression token"]
+            );
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn raising_correct_error_when_parsing_unclosed_group() {
+    let error = Grammar::parse("S -> ('a' 'b'\n").unwrap_err();
+    match error {
+        dotlr::GrammarError::UnexpectedToken { line, column, token, expected, .. } => {
+            assert_eq!(line, 1);
+            assert_eq!(column, 14);
+            assert_eq!(token.as_str(), "\\n");
+            assert_eq!(
+                expected.iter().map(|token| token.as_str()).collect::<Vec<_>>(),
+                ["symbol", "constant token", "regular expression token", "')'"]
+            );
+        },
+        _ => unreachable!(),
+    }
+}
+
+
+#[test]
+fn correctly_parsing_calculator_alternation_grammar() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR_ALTERNATION).unwrap();
+    let expected_grammar = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+
+    assert_eq!(grammar.rules(), expected_grammar.rules());
+    assert_eq!(grammar.to_string(), expected_grammar.to_string());
+
+    assert_eq!(
+        format!("{:#}", grammar).trim(),
+        r#"
+
+Expr -> Expr '+' Factor
+     | Expr '-' Factor
+     | Factor
+Factor -> Factor '*' Exponent
+       | Factor '/' Exponent
+       | Exponent
+Exponent -> Term '^' Exponent
+         | Term
+Term -> '(' Expr ')'
+     | %f
+
+%f -> /^[-]?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?/
+
+        "#
+        .trim(),
+    );
+}
+
+#[test]
+fn correctly_parsing_alternation_in_groups() {
+    let grammar = Grammar::parse("S -> S ('+' | '-' | '*' '*') 'x' | ε | 'x'").unwrap();
+    assert_eq!(
+        grammar.rules().iter().map(|rule| rule.to_string()).collect::<Vec<_>>(),
+        [
+            "S -> S ('+' | '-' | '*' '*') 'x'",
+            "('+' | '-' | '*' '*') -> '+'",
+            "('+' | '-' | '*' '*') -> '-'",
+            "('+' | '-' | '*' '*') -> '*' '*'",
+            "S -> ε",
+            "S -> 'x'",
+        ],
+    );
+
+    let formatted_grammar = format!("{:#}", grammar);
+    assert_eq!(formatted_grammar.trim(), "S -> S ('+' | '-' | '*' '*') 'x'\n  | ε\n  | 'x'");
+    assert_eq!(Grammar::parse(&formatted_grammar).unwrap().rules(), grammar.rules());
+}
+
+#[test]
+fn raising_correct_error_when_parsing_alternative_without_rule() {
+    let error = Grammar::parse("%f -> /[0-9]+/\n| 'x'").unwrap_err();
+    match error {
+        dotlr::GrammarError::UnexpectedToken { line, column, token, expected, .. } => {
+            assert_eq!(line, 2);
+            assert_eq!(column, 1);
+            assert_eq!(token.as_str(), "|");
+            assert_eq!(
+                expected.iter().map(|token| token.as_str()).collect::<Vec<_>>(),
+                ["symbol", "regular expression token"]
+            );
+        },
+        _ => unreachable!(),
+    }
+}
+
+
+#[test]
+fn correctly_parsing_calculator_precedence_grammar() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR_PRECEDENCE).unwrap();
+
+    assert_eq!(
+        grammar.to_string().trim(),
+        r#"
+
+%left '+' '-'
+%left '*' '/'
+%right '^'
+%right %uminus
+
+Expr -> Expr '+' Expr
+Expr -> Expr '-' Expr
+Expr -> Expr '*' Expr
+Expr -> Expr '/' Expr
+Expr -> Expr '^' Expr
+Expr -> '-' Expr %prec %uminus
+Expr -> '(' Expr ')'
+Expr -> %f
+
+%f -> /^[0-9]+(\.[0-9]+)?/
+
+        "#
+        .trim(),
+    );
+
+    assert_eq!(
+        grammar
+            .precedences()
+            .iter()
+            .map(|(token, precedence)| (token.to_string(), *precedence))
+            .collect::<Vec<_>>(),
+        [
+            ("'+'".to_owned(), Precedence::new(1, Associativity::Left)),
+            ("'-'".to_owned(), Precedence::new(1, Associativity::Left)),
+            ("'*'".to_owned(), Precedence::new(2, Associativity::Left)),
+            ("'/'".to_owned(), Precedence::new(2, Associativity::Left)),
+            ("'^'".to_owned(), Precedence::new(3, Associativity::Right)),
+            ("%uminus".to_owned(), Precedence::new(4, Associativity::Right)),
+        ],
+    );
+
+    assert_eq!(grammar.rules()[5].precedence(), Some(&RegexToken::from("uminus").into()));
+    assert_eq!(
+        grammar.rules().iter().map(|rule| grammar.precedence_of(rule)).collect::<Vec<_>>(),
+        [
+            Some(Precedence::new(1, Associativity::Left)),
+            Some(Precedence::new(1, Associativity::Left)),
+            Some(Precedence::new(2, Associativity::Left)),
+            Some(Precedence::new(2, Associativity::Left)),
+            Some(Precedence::new(3, Associativity::Right)),
+            Some(Precedence::new(4, Associativity::Right)),
+            None,
+            None,
+        ],
+    );
+}
+
+#[test]
+fn raising_correct_error_when_parsing_symbol_after_rule_precedence() {
+    let error = Grammar::parse("%left '+'\nE -> E '+' E %prec '+' E").unwrap_err();
+    match error {
+        dotlr::GrammarError::UnexpectedToken { line, column, token, expected, .. } => {
+            assert_eq!(line, 2);
+            assert_eq!(column, 24);
+            assert_eq!(token.as_str(), "E");
+            assert_eq!(
+                expected.iter().map(|token| token.as_str()).collect::<Vec<_>>(),
+                ["new line"]
+            );
+        },
+        _ => unreachable!(),
+    }
+}
+
+
+#[test]
+fn correctly_parsing_statements_grammar() {
+    let grammar = Grammar::parse(common::grammars::STATEMENTS).unwrap();
+
+    assert_eq!(grammar.start_symbol().as_str(), "Program");
+    assert_eq!(
+        grammar.entry_symbols().iter().map(|symbol| symbol.as_str()).collect::<Vec<_>>(),
+        ["Program", "Statement", "Expr"],
+    );
+
+    assert_eq!(
+        grammar.to_string().trim(),
+        r#"
+
+%start Program Statement Expr
+
+Program -> Statement+
+Statement -> %identifier '=' Expr ';'
+Statement -> 'print' Expr ';'
+Expr -> Expr '+' Term
+Expr -> Term
+Term -> %identifier
+Term -> %number
+Term -> '(' Expr ')'
+
+%identifier -> /^[a-z]+/
+%number -> /^[0-9]+/
+
+        "#
+        .trim(),
+    );
+}
+
+#[test]
+fn correctly_parsing_start_directive_after_rules() {
+    let grammar = Grammar::parse("A -> 'a' B\nB -> 'b'\n%start B").unwrap();
+
+    assert_eq!(grammar.start_symbol().as_str(), "B");
+    assert_eq!(
+        grammar.entry_symbols().iter().map(|symbol| symbol.as_str()).collect::<Vec<_>>(),
+        ["B"],
+    );
+    assert_eq!(grammar.to_string().trim(), "%start B\n\nA -> 'a' B\nB -> 'b'");
+
+    let grammar = Grammar::parse("%start A\nA -> 'a' B\nB -> 'b'").unwrap();
+    assert_eq!(grammar.to_string().trim(), "A -> 'a' B\nB -> 'b'");
+}
+
+#[test]
+fn correctly_parsing_sums_grammar() {
+    let grammar = Grammar::parse(common::grammars::SUMS).unwrap();
+
+    assert_eq!(
+        grammar
+            .ignored_regular_expressions()
+            .iter()
+            .map(|regex| regex.to_string())
+            .collect::<Vec<_>>(),
+        ["^[ \\t]+", "^#[^\\n]*"],
+    );
+
+    assert_eq!(
+        grammar.to_string().trim(),
+        r#"
+
+Program -> Line+
+Line -> Sum %newline
+Line -> %newline
+Sum -> Sum '+' %number
+Sum -> %number
+
+%number -> /^[0-9]+/
+%newline -> /^\n/
+
+%ignore /^[ \t]+/
+%ignore /^#[^\n]*/
+
+        "#
+        .trim(),
+    );
+}
+
+#[test]
+fn raising_correct_error_when_ignore_directive_is_missing_regex() {
+    let error = Grammar::parse("%ignore\nE -> 'x'").unwrap_err();
+    match error {
+        dotlr::GrammarError::UnexpectedToken { line, column, token, expected, .. } => {
+            assert_eq!(line, 1);
+            assert_eq!(column, 8);
+            assert_eq!(token.as_str(), "\\n");
+            assert_eq!(
+                expected.iter().map(|token| token.as_str()).collect::<Vec<_>>(),
+                ["regular expression"]
+            );
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn correctly_parsing_calculator_labeled_grammar() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR_LABELED).unwrap();
+
+    let rule = &grammar.rules()[0];
+    assert_eq!(rule.labels(), [Some("lhs".into()), Some("op".into()), Some("rhs".into())]);
+    assert_eq!(rule.index_of("rhs"), Some(2));
+    assert_eq!(rule.index_of("value"), None);
+
+    let rule = &grammar.rules()[8];
+    assert_eq!(rule.labels(), [None, Some("value".into()), None]);
+
+    assert_eq!(
+        grammar.to_string().trim(),
+        r#"
+
+Expr -> lhs:Expr op:'+' rhs:Factor
+Expr -> lhs:Expr op:'-' rhs:Factor
+Expr -> value:Factor
+Factor -> lhs:Factor op:'*' rhs:Exponent
+Factor -> lhs:Factor op:'/' rhs:Exponent
+Factor -> value:Exponent
+Exponent -> lhs:Term op:'^' rhs:Exponent
+Exponent -> value:Term
+Term -> '(' value:Expr ')'
+Term -> value:%f
+
+%f -> /^[-]?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?/
+
+        "#
+        .trim(),
+    );
+}
+
+#[test]
+fn correctly_parsing_labels_of_ebnf_constructs() {
+    let grammar =
+        Grammar::parse("List -> '[' items:(item:%n (',' item:%n)*)? ']'\n%n -> /[0-9]+/").unwrap();
+
+    assert_eq!(
+        grammar.rules().iter().map(|rule| rule.to_string()).collect::<Vec<_>>(),
+        [
+            "List -> '[' items:(item:%n (',' item:%n)*)? ']'",
+            "(',' item:%n)* -> (',' item:%n)* ',' item:%n",
+            "(',' item:%n)* -> ε",
+            "(item:%n (',' item:%n)*)? -> item:%n (',' item:%n)*",
+            "(item:%n (',' item:%n)*)? -> ε",
+        ],
+    );
+}
+
+#[test]
+fn raising_correct_error_when_label_is_not_followed_by_an_element() {
+    let error = Grammar::parse("E -> lhs: '+'\nE -> lhs:\n").unwrap_err();
+    match error {
+        dotlr::GrammarError::UnexpectedToken { line, column, token, expected, .. } => {
+            assert_eq!(line, 2);
+            assert_eq!(column, 10);
+            assert_eq!(token.as_str(), "\\n");
+            assert_eq!(
+                expected.iter().map(|token| token.as_str()).collect::<Vec<_>>(),
+                ["symbol", "constant token", "regular expression token"]
+            );
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn correctly_parsing_program_grammar_with_imports() {
+    let mut resolver = FileSystemResolver::new(common::grammars::CORRECT_IMPORTS_DIRECTORY);
+    let grammar = Grammar::parse_with_resolver(common::grammars::PROGRAM, &mut resolver).unwrap();
+
+    assert_eq!(grammar.start_symbol().as_str(), "Program");
+    assert_eq!(grammar.ignored_regular_expressions().len(), 2);
+    assert_eq!(
+        grammar.precedences().get(&Token::Constant(ConstantToken::from("*"))).copied(),
+        Some(Precedence::new(2, Associativity::Left)),
+    );
+
+    assert_eq!(
+        grammar.to_string().trim(),
+        r#"
+
+%start Program
+%left '+' '-'
+%left '*' '/'
+
+Expr -> Expr '+' Expr
+Expr -> Expr '-' Expr
+Expr -> Expr '*' Expr
+Expr -> Expr '/' Expr
+Expr -> '(' Expr ')'
+Expr -> %identifier
+Expr -> %number
+Statement -> %identifier '=' Expr ';'
+Statement -> 'print' Expr ';'
+Program -> Statement+
+
+%identifier -> /^[a-z]+/
+%number -> /^[0-9]+/
+
+%ignore /^\s+/
+%ignore /^#[^\n]*/
+
+        "#
+        .trim(),
+    );
+}
+
+#[test]
+fn correctly_merging_imported_grammars_into_importing_grammar() {
+    struct InMemoryResolver;
+    impl GrammarResolver for InMemoryResolver {
+        fn resolve(&mut self, path: &str, _: Option<&str>) -> Result<(SmolStr, String), SmolStr> {
+            match path {
+                "a.lr" => Ok((path.into(), "%left 'a'\nA -> 'a'\n%a -> /a/".into())),
+                "b.lr" => Ok((path.into(), "%import \"a.lr\"\n%right 'b'\nB -> 'b' A".into())),
+                _ => Err("not found".into()),
+            }
+        }
+    }
+
+    let grammar = Grammar::parse_with_resolver(
+        "%import \"a.lr\"\n%import \"b.lr\"\n%nonassoc 'c'\nC -> A B 'c'",
+        &mut InMemoryResolver,
+    )
+    .unwrap();
+
+    assert_eq!(grammar.start_symbol().as_str(), "C");
+    assert_eq!(
+        grammar.rules().iter().map(|rule| rule.to_string()).collect::<Vec<_>>(),
+        ["A -> 'a'", "B -> 'b' A", "C -> A B 'c'"],
+    );
+    assert_eq!(
+        grammar
+            .precedences()
+            .iter()
+            .map(|(token, precedence)| (token.to_string(), precedence.level()))
+            .collect::<Vec<_>>(),
+        [("'a'".to_owned(), 1), ("'b'".to_owned(), 2), ("'c'".to_owned(), 3)],
+    );
+}
+
+#[test]
+fn correctly_recording_source_grammars_of_imported_spans() {
+    struct InMemoryResolver;
+    impl GrammarResolver for InMemoryResolver {
+        fn resolve(&mut self, path: &str, _: Option<&str>) -> Result<(SmolStr, String), SmolStr> {
+            match path {
+                "a.lr" => Ok((path.into(), "A -> 'a' %a\n%a -> /a/".into())),
+                "b.lr" => Ok((path.into(), "%import \"a.lr\"\nB -> 'b' A".into())),
+                _ => Err("not found".into()),
+            }
+        }
+    }
+
+    let grammar =
+        Grammar::parse_with_resolver("%import \"b.lr\"\nC -> A B 'c'", &mut InMemoryResolver)
+            .unwrap();
+
+    assert_eq!(
+        grammar
+            .rules()
+            .iter()
+            .map(|rule| {
+                let span = rule.span().unwrap();
+                (rule.to_string(), span.source.as_ref().map(|source| source.as_str()), span.line)
+            })
+            .collect::<Vec<_>>(),
+        [
+            ("A -> 'a' %a".to_owned(), Some("a.lr"), 1),
+            ("B -> 'b' A".to_owned(), Some("b.lr"), 2),
+            ("C -> A B 'c'".to_owned(), None, 2),
+        ],
+    );
+    for rule in grammar.rules() {
+        for span in rule.pattern_spans().iter().flatten() {
+            assert_eq!(span.source, rule.span().unwrap().source);
+        }
+    }
+
+    let span = &grammar.regex_spans()[&RegexToken::from("a")];
+    assert_eq!(span.source.as_ref().map(|source| source.as_str()), Some("a.lr"));
+    assert_eq!(span.line, 2);
+}
+
+#[test]
+fn raising_correct_error_when_imported_parameterized_rule_is_instantiated_incorrectly() {
+    struct InMemoryResolver;
+    impl GrammarResolver for InMemoryResolver {
+        fn resolve(&mut self, path: &str, _: Option<&str>) -> Result<(SmolStr, String), SmolStr> {
+            match path {
+                "lists.lr" => Ok((path.into(), "List(X) -> X Rest(X)".into())),
+                _ => Err("not found".into()),
+            }
+        }
+    }
+
+    let error =
+        Grammar::parse_with_resolver("%import \"lists.lr\"\nS -> List('a')", &mut InMemoryResolver)
+            .unwrap_err();
+    match error {
+        dotlr::GrammarError::InImportedGrammar { grammar, error } => {
+            assert_eq!(grammar.as_str(), "lists.lr");
+            assert!(matches!(
+                *error,
+                dotlr::GrammarError::UndefinedParameterizedSymbol { line: 1, column: 14, .. }
+            ));
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn raising_correct_error_when_imported_rule_uses_undefined_symbol() {
+    struct InMemoryResolver;
+    impl GrammarResolver for InMemoryResolver {
+        fn resolve(&mut self, path: &str, _: Option<&str>) -> Result<(SmolStr, String), SmolStr> {
+            match path {
+                "lib.lr" => Ok((path.into(), "A -> 'a'\nA -> 'a' B".into())),
+                _ => Err("not found".into()),
+            }
+        }
+    }
+
+    let grammar =
+        Grammar::parse_with_resolver("%import \"lib.lr\"\nS -> A", &mut InMemoryResolver).unwrap();
+    let error = Parser::lr(grammar).unwrap_err();
+    assert!(error.to_string().ends_with("lib.lr"));
+    match error {
+        ParserError::UndefinedSymbol { symbol, span, .. } => {
+            assert_eq!(symbol.as_str(), "B");
+            let span = span.unwrap();
+            assert_eq!(span.source.as_ref().map(|source| source.as_str()), Some("lib.lr"));
+            assert_eq!((span.line, span.column), (2, 10));
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn raising_correct_error_when_import_cannot_be_resolved() {
+    let error = Grammar::parse("%import \"expressions.lr\"\nS -> E").unwrap_err();
+    match error {
+        dotlr::GrammarError::UnresolvedImport { line, column, path, reason, .. } => {
+            assert_eq!(line, 1);
+            assert_eq!(column, 9);
+            assert_eq!(path.as_str(), "expressions.lr");
+            assert_eq!(reason.as_str(), "imports can only be resolved with a resolver");
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn raising_correct_error_when_imports_are_cyclic() {
+    let mut resolver = FileSystemResolver::new(common::grammars::INCORRECT_IMPORTS_DIRECTORY);
+    let error =
+        Grammar::parse_with_resolver(common::grammars::CYCLIC_IMPORT, &mut resolver).unwrap_err();
+    match error {
+        dotlr::GrammarError::InImportedGrammar { grammar, error } => {
+            assert!(grammar.ends_with("cyclic-a.lr"));
+            match *error {
+                dotlr::GrammarError::CyclicImport { line, column, path, .. } => {
+                    assert_eq!(line, 1);
+                    assert_eq!(column, 9);
+                    assert_eq!(path.as_str(), "cyclic-b.lr");
+                },
+                _ => unreachable!(),
+            }
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn raising_correct_error_when_imported_grammar_is_invalid() {
+    let mut resolver = FileSystemResolver::new(common::grammars::INCORRECT_IMPORTS_DIRECTORY);
+    let error =
+        Grammar::parse_with_resolver(common::grammars::INVALID_IMPORT, &mut resolver).unwrap_err();
+    match error {
+        dotlr::GrammarError::InImportedGrammar { grammar, error } => {
+            assert!(grammar.ends_with("unexpected-token.lr"));
+            assert!(matches!(
+                *error,
+                dotlr::GrammarError::UnexpectedToken { line: 1, column: 6, .. }
+            ));
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn correctly_parsing_lists_grammar() {
+    let grammar = Grammar::parse(common::grammars::LISTS).unwrap();
+
+    assert_eq!(
+        grammar.rules().iter().map(|rule| rule.to_string()).collect::<Vec<_>>(),
+        [
+            "Program -> '[' List(Value, ',')? ']'",
+            "List(Value, ',') -> Value (',' Value)*",
+            "(',' Value)* -> (',' Value)* ',' Value",
+            "(',' Value)* -> ε",
+            "List(Value, ',')? -> List(Value, ',')",
+            "List(Value, ',')? -> ε",
+            "Value -> %number",
+            "Value -> '[' List(Value, ',')? ']'",
+            "Value -> '{' List(Entry, ';')? '}'",
+            "List(Entry, ';') -> Entry (';' Entry)*",
+            "(';' Entry)* -> (';' Entry)* ';' Entry",
+            "(';' Entry)* -> ε",
+            "List(Entry, ';')? -> List(Entry, ';')",
+            "List(Entry, ';')? -> ε",
+            "Entry -> %string ':' Value",
+        ],
+    );
+
+    assert_eq!(
+        grammar
+            .instantiations()
+            .iter()
+            .map(|(instance, symbol)| (instance.as_str(), symbol.as_str()))
+            .collect::<Vec<_>>(),
+        [("List(Value, ',')", "List"), ("List(Entry, ';')", "List")],
+    );
+
+    assert_eq!(
+        grammar.to_string().trim(),
+        r#"
+
+Program -> '[' List(Value, ',')? ']'
+Value -> %number
+Value -> '[' List(Value, ',')? ']'
+Value -> '{' List(Entry, ';')? '}'
+Entry -> %string ':' Value
+List(X, Sep) -> X (Sep X)*
+
+%number -> /^[0-9]+/
+%string -> /^"[^"]*"/
+
+        "#
+        .trim(),
+    );
+}
+
+#[test]
+fn correctly_instantiating_recursive_parameterized_rules() {
+    let grammar =
+        Grammar::parse("S -> Nested(%n)\nNested(X) -> X | '(' Nested(X) ')'\n%n -> /[0-9]+/")
+            .unwrap();
+    assert_eq!(
+        grammar.rules().iter().map(|rule| rule.to_string()).collect::<Vec<_>>(),
+        ["S -> Nested(%n)", "Nested(%n) -> %n", "Nested(%n) -> '(' Nested(%n) ')'"],
+    );
+}
+
+#[test]
+fn raising_correct_error_when_instantiation_does_not_terminate() {
+    let error =
+        Grammar::parse("S -> Nested(%n)\nNested(X) -> X | '[' Nested('-' X) ']'\n%n -> /[0-9]+/")
+            .unwrap_err();
+    match error {
+        dotlr::GrammarError::UnboundedInstantiation { line, column, symbol, .. } => {
+            assert_eq!(line, 2);
+            assert_eq!(column, 22);
+            assert_eq!(symbol.as_str(), "Nested");
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn raising_correct_error_when_parameterized_symbol_is_not_defined() {
+    let error = Grammar::parse("S -> List(%n)\n%n -> /[0-9]+/").unwrap_err();
+    match error {
+        dotlr::GrammarError::UndefinedParameterizedSymbol { line, column, symbol, .. } => {
+            assert_eq!(line, 1);
+            assert_eq!(column, 6);
+            assert_eq!(symbol.as_str(), "List");
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn raising_correct_error_when_parameterized_symbol_has_wrong_number_of_arguments() {
+    let error =
+        Grammar::parse("S -> List(%n)\nList(X, Sep) -> X (Sep X)*\n%n -> /[0-9]+/").unwrap_err();
+    match error {
+        dotlr::GrammarError::ArgumentCountMismatch {
+            line, column, symbol, expected, got, ..
+        } => {
+            assert_eq!(line, 1);
+            assert_eq!(column, 6);
+            assert_eq!(symbol.as_str(), "List");
+            assert_eq!(expected, 2);
+            assert_eq!(got, 1);
+        },
+        _ => unreachable!(),
+    }
+
+    let error = Grammar::parse("List(X, Sep) -> X (Sep X)*\nList(X) -> X\n").unwrap_err();
+    match error {
+        dotlr::GrammarError::ArgumentCountMismatch { line, symbol, expected, got, .. } => {
+            assert_eq!(line, 2);
+            assert_eq!(symbol.as_str(), "List");
+            assert_eq!(expected, 2);
+            assert_eq!(got, 1);
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn correctly_parsing_escape_sequences_in_literals() {
+    let grammar = Grammar::parse(
+        r#"
+Item -> '\'' %path '\'' '\\' '\u{3b5}' '\n'
+%path -> /[a-z]+(\/[a-z]+)*/
+%ignore /\/\/[^\n]*/
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        grammar.constant_tokens().iter().map(|token| token.as_str()).collect::<Vec<_>>(),
+        ["'", "\\", "ε", "\n"],
+    );
+    assert_eq!(grammar.regular_expressions()[0].as_str(), "^[a-z]+(/[a-z]+)*");
+    assert_eq!(grammar.ignored_regular_expressions()[0].as_str(), "^//[^\\n]*");
+
+    let grammar_string = grammar.to_string();
+    assert_eq!(
+        grammar_string.trim(),
+        r#"
+
+Item -> '\'' %path '\'' '\\' 'ε' '\n'
+
+%path -> /^[a-z]+(\/[a-z]+)*/
+
+%ignore /^\/\/[^\n]*/
+
+        "#
+        .trim(),
+    );
+
+    let reparsed_grammar = Grammar::parse(&grammar_string).unwrap();
+    assert_eq!(reparsed_grammar.to_string(), grammar_string);
+    assert_eq!(reparsed_grammar.constant_tokens(), grammar.constant_tokens());
+}
+
+#[test]
+fn correctly_round_tripping_correct_grammars_through_display() {
+    for grammar_string in common::grammars::CORRECT {
+        let grammar = Grammar::parse(grammar_string).unwrap();
+        let reparsed_grammar = Grammar::parse(&grammar.to_string()).unwrap();
+        assert_eq!(reparsed_grammar.to_string(), grammar.to_string());
+    }
+}
+
+#[test]
+fn raising_correct_error_when_parsing_unknown_escape_sequence() {
+    let error = Grammar::parse("E -> '\\q'").unwrap_err();
+    match error {
+        dotlr::GrammarError::UnexpectedToken { line, column, token, .. } => {
+            assert_eq!(line, 1);
+            assert_eq!(column, 6);
+            assert_eq!(token.as_str(), "'\\q'");
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn correctly_parsing_keywords_grammar() {
+    let grammar = Grammar::parse(common::grammars::KEYWORDS).unwrap();
+
+    assert_eq!(
+        grammar.keywords().iter().map(|keyword| keyword.as_str()).collect::<Vec<_>>(),
+        ["if", "then", "else", "while"],
+    );
+    assert!(grammar.constant_tokens().contains(&ConstantToken::from("while")));
+
+    assert_eq!(grammar.priority_of(&RegexToken::from("color")), 1);
+    assert_eq!(grammar.priority_of(&RegexToken::from("identifier")), 0);
+
+    assert_eq!(
+        grammar.to_string().trim(),
+        r#"
+
+Program -> Statement*
+Statement -> 'if' Value 'then' Statement
+Statement -> %identifier '=' Value ';'
+Value -> %identifier
+Value -> %number
+Value -> %color
+
+%identifier -> /^[a-z_][a-z0-9_]*/
+%number -> /^[0-9]+/
+%color -> /^[a-f]{6}/ %prio 1
+
+%keyword 'if' 'then' 'else' 'while'
+
+        "#
+        .trim(),
+    );
+}
+
+#[test]
+fn raising_correct_error_when_priority_is_not_a_number() {
+    let error = Grammar::parse("E -> %n\n%n -> /[0-9]+/ %prio high\n").unwrap_err();
+    match error {
+        dotlr::GrammarError::UnexpectedToken { line, token, expected, .. } => {
+            assert_eq!(line, 2);
+            assert_eq!(token.as_str(), "high");
+            assert_eq!(
+                expected.iter().map(|token| token.as_str()).collect::<Vec<_>>(),
+                ["priority"]
+            );
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn correctly_parsing_interpolation_grammar() {
+    let grammar = Grammar::parse(common::grammars::INTERPOLATION).unwrap();
+
+    assert_eq!(
+        grammar
+            .modes()
+            .iter()
+            .map(|(mode, tokens)| (mode.as_str(), tokens.iter().join(" ")))
+            .collect::<Vec<_>>(),
+        [("string", "%text '\"' '${'".to_owned()), ("default", "'\"'".to_owned())],
+    );
+
+    let quote = Token::Constant(ConstantToken::from("\""));
+    assert_eq!(
+        grammar.transition_of("default", &quote),
+        Some(&ModeTransition::Push("string".into())),
+    );
+    assert_eq!(grammar.transition_of("string", &quote), Some(&ModeTransition::Pop));
+    assert_eq!(grammar.transition_of("string", &Token::Regex(RegexToken::from("text"))), None);
+
+    assert!(grammar.recognizes("default", &quote));
+    assert!(grammar.recognizes("default", &Token::Regex(RegexToken::from("identifier"))));
+    assert!(!grammar.recognizes("default", &Token::Regex(RegexToken::from("text"))));
+    assert!(!grammar.recognizes("string", &Token::Regex(RegexToken::from("identifier"))));
+
+    assert_eq!(
+        grammar.to_string().trim(),
+        r#"
+
+Expr -> Expr '+' Term
+Expr -> Term
+Term -> %identifier
+Term -> '"' Part* '"'
+Part -> %text
+Part -> '${' Expr '}'
+
+%identifier -> /^[a-z]+/
+%text -> /^[^"$]+/
+
+%mode string %text '"' '${'
+%mode default '"'
+%on default '"' %push string
+%on default '}' %pop
+%on string '"' %pop
+%on string '${' %push default
+
+        "#
+        .trim(),
+    );
+}
+
+#[test]
+fn raising_correct_error_when_transition_uses_undeclared_mode() {
+    let error =
+        Grammar::parse("E -> '\"' %text '\"'\n%text -> /[^\"]+/\n%on default '\"' %push string\n")
+            .unwrap_err();
+    match error {
+        dotlr::GrammarError::UndefinedMode { line, column, mode, .. } => {
+            assert_eq!(line, 3);
+            assert_eq!(column, 23);
+            assert_eq!(mode.as_str(), "string");
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn correctly_parsing_query_grammar() {
+    let grammar = Grammar::parse(common::grammars::QUERY).unwrap();
+
+    assert!(!grammar.case_insensitive());
+    assert_eq!(
+        grammar.case_insensitive_tokens().iter().map(|token| token.as_str()).collect::<Vec<_>>(),
+        ["select", "from", "where"],
+    );
+    assert!(grammar.is_case_insensitive(&ConstantToken::from("where")));
+    assert!(!grammar.is_case_insensitive(&ConstantToken::from("*")));
+
+    assert_eq!(
+        grammar.to_string().trim(),
+        r#"
+
+Query -> 'select' Columns 'from' %identifier Condition?
+Columns -> '*'
+Columns -> %identifier (',' %identifier)*
+Condition -> 'where' %identifier '=' %number
+
+%identifier -> /^[a-zA-Z_][a-zA-Z0-9_]*/
+%number -> /^[0-9]+/
+
+%keyword 'select' 'from' 'where'
+%caseless 'select' 'from' 'where'
+
+        "#
+        .trim(),
+    );
+}
+
+#[test]
+fn correctly_parsing_grammar_wide_case_insensitivity() {
+    let grammar = Grammar::parse("Program -> 'begin' 'end'\n%caseless\n").unwrap();
+
+    assert!(grammar.case_insensitive());
+    assert!(grammar.case_insensitive_tokens().is_empty());
+    assert!(grammar.is_case_insensitive(&ConstantToken::from("begin")));
+
+    assert_eq!(grammar.to_string().trim(), "Program -> 'begin' 'end'\n\n%caseless");
+}
+
+
+#[test]
+fn correctly_building_calculator_grammar() {
+    let symbol = |symbol: &str| AtomicPattern::from(Symbol::from(symbol));
+    let constant = |token: &str| AtomicPattern::from(ConstantToken::from(token));
+    let regex = |token: &str| AtomicPattern::from(RegexToken::from(token));
+
+    let grammar = GrammarBuilder::new()
+        .rule(Rule::new("Expr", [symbol("Expr"), constant("+"), symbol("Factor")]))
+        .rule(Rule::new("Expr", [symbol("Expr"), constant("-"), symbol("Factor")]))
+        .rule(Rule::new("Expr", [symbol("Factor")]))
+        .rule(Rule::new("Factor", [symbol("Factor"), constant("*"), symbol("Exponent")]))
+        .rule(Rule::new("Factor", [symbol("Factor"), constant("/"), symbol("Exponent")]))
+        .rule(Rule::new("Factor", [symbol("Exponent")]))
+        .rule(Rule::new("Exponent", [symbol("Term"), constant("^"), symbol("Exponent")]))
+        .rule(Rule::new("Exponent", [symbol("Term")]))
+        .rule(Rule::new("Term", [constant("("), symbol("Expr"), constant(")")]))
+        .rule(Rule::new("Term", [regex("f")]))
+        .regex_token("f", r"[-]?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?")
+        .unwrap()
+        .build()
+        .unwrap();
+    let parsed = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+
+    assert_eq!(grammar.to_string(), parsed.to_string());
+    assert_eq!(grammar.symbols(), parsed.symbols());
+    assert_eq!(grammar.start_symbol(), parsed.start_symbol());
+    assert_eq!(grammar.entry_symbols(), parsed.entry_symbols());
+    assert_eq!(grammar.constant_tokens(), parsed.constant_tokens());
+    assert_eq!(grammar.rules(), parsed.rules());
+}
+
+#[test]
+fn correctly_building_grammar_with_start_symbol() {
+    let grammar = GrammarBuilder::new()
+        .rule(Rule::new("A", [AtomicPattern::from(ConstantToken::from("a"))]))
+        .rule(Rule::new("B", [AtomicPattern::from(Symbol::from("A"))]))
+        .constant_token("b")
+        .start_symbol("B")
+        .build()
+        .unwrap();
+    let parsed = Grammar::parse(
+        "%start B
+
+A -> 'a'
+B -> A
+",
+    )
+    .unwrap();
+
+    assert_eq!(grammar.start_symbol().as_str(), "B");
+    assert_eq!(grammar.entry_symbols(), parsed.entry_symbols());
+    assert_eq!(
+        grammar.constant_tokens().iter().map(|token| token.as_str()).collect::<Vec<_>>(),
+        ["a", "b"],
+    );
+}
+
+#[test]
+fn raising_correct_error_when_building_grammar_with_invalid_regex() {
+    let error = GrammarBuilder::new().regex_token("f", "[0-9").unwrap_err();
+    let dotlr::GrammarBuilderError::InvalidRegex { regex_token, regex, .. } = error;
+    assert_eq!(regex_token.as_str(), "f");
+    assert_eq!(regex.as_str(), "[0-9");
+}
+
+#[test]
+fn raising_correct_error_when_building_grammar_with_undefined_symbol() {
+    let errors = GrammarBuilder::new()
+        .rule(Rule::new("E", [AtomicPattern::from(Symbol::from("T"))]))
+        .build()
+        .unwrap_err();
+    match errors.as_slice() {
+        [ParserError::UndefinedSymbol { symbol, rule, span }] => {
+            assert_eq!(symbol.as_str(), "T");
+            assert_eq!(*span, None);
+            assert_eq!(rule.to_string(), "E -> T");
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn raising_correct_error_when_building_grammar_with_undefined_regex_token() {
+    let errors = GrammarBuilder::new()
+        .rule(Rule::new("E", [AtomicPattern::from(RegexToken::from("f"))]))
+        .build()
+        .unwrap_err();
+    match errors.as_slice() {
+        [ParserError::UndefinedRegexToken { regex_token, rule, span }] => {
+            assert_eq!(regex_token.as_str(), "f");
+            assert_eq!(*span, None);
+            assert_eq!(rule.to_string(), "E -> %f");
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn raising_correct_error_when_building_grammar_with_undefined_start_symbol() {
+    let errors = GrammarBuilder::new()
+        .rule(Rule::new("E", [AtomicPattern::from(ConstantToken::from("x"))]))
+        .start_symbol("S")
+        .build()
+        .unwrap_err();
+    match errors.as_slice() {
+        [ParserError::UndefinedEntrySymbol { symbol, span }] => {
+            assert_eq!(symbol.as_str(), "S");
+            assert_eq!(*span, None);
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn raising_all_errors_when_building_grammar_with_multiple_undefined_definitions() {
+    let errors = GrammarBuilder::new()
+        .rule(Rule::new(
+            "E",
+            [AtomicPattern::from(Symbol::from("T")), RegexToken::from("f").into()],
+        ))
+        .rule(Rule::new("E", [AtomicPattern::from(Symbol::from("F"))]))
+        .start_symbol("S")
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        errors.iter().map(|error| error.to_string()).collect::<Vec<_>>(),
+        [
+            "entry symbol S is not defined",
+            "symbol T in rule E -> T %f is not defined",
+            "regex token %f in rule E -> T %f is not defined",
+            "symbol F in rule E -> F is not defined",
+        ],
+    );
+}
+
+
+#[test]
+fn correctly_recording_spans_of_rules_and_their_elements() {
+    let grammar_string = "E -> lhs:E '+' T\n  | T\nT -> %n ('*' %n)* %prec '+'\n\n%n -> /[0-9]+/\n";
+    let grammar = Grammar::parse(grammar_string).unwrap();
+
+    let slice = |span: &Span| &grammar_string[span.offset..span.offset + span.len];
+    let rule_slices = grammar
+        .rules()
+        .iter()
+        .map(|rule| {
+            let pattern_slices =
+                rule.pattern_spans().iter().map(|span| slice(span.as_ref().unwrap())).collect();
+            (slice(rule.span().unwrap()), pattern_slices)
+        })
+        .collect::<Vec<(&str, Vec<&str>)>>();
+
+    assert_eq!(
+        rule_slices,
+        [
+            ("E -> lhs:E '+' T", vec!["lhs:E", "'+'", "T"]),
+            ("T", vec!["T"]),
+            ("T -> %n ('*' %n)* %prec '+'", vec!["%n", "('*' %n)*"]),
+            ("('*' %n)*", vec!["('*' %n)*", "'*'", "%n"]),
+            ("('*' %n)*", vec![]),
+        ]
+    );
+
+    let regex_span = &grammar.regex_spans()[&RegexToken::from("n")];
+    assert_eq!(slice(regex_span), "%n -> /[0-9]+/");
+    assert_eq!(regex_span.line, 5);
+}
+
+#[test]
+fn correctly_recording_spans_of_instantiated_rules() {
+    let grammar_string = "L -> List(%n)\nList(X) -> X (',' X)*\n%n -> /[0-9]+/\n";
+    let grammar = Grammar::parse(grammar_string).unwrap();
+
+    let slice = |span: &Span| &grammar_string[span.offset..span.offset + span.len];
+    assert_eq!(slice(grammar.rules()[0].span().unwrap()), "L -> List(%n)");
+    assert_eq!(slice(grammar.rules()[0].pattern_spans()[0].as_ref().unwrap()), "List(%n)");
+    assert_eq!(slice(grammar.rules()[1].span().unwrap()), "List(X) -> X (',' X)*");
+    assert_eq!(slice(grammar.rules()[1].pattern_spans()[0].as_ref().unwrap()), "%n");
+}
+
+#[test]
+fn correctly_reporting_spans_in_grammar_errors() {
+    let grammar_string = "E -> 'a'\nT -> -> 'b'\n";
+    match Grammar::parse(grammar_string).unwrap_err() {
+        dotlr::GrammarError::UnexpectedToken { line, column, span, .. } => {
+            assert_eq!(&grammar_string[span.offset..span.offset + span.len], "->");
+            assert_eq!((span.line, span.column), (line, column));
+        },
+        _ => unreachable!(),
+    }
+
+    let grammar_string = "E -> ('a'";
+    match Grammar::parse(grammar_string).unwrap_err() {
+        dotlr::GrammarError::UnexpectedEof { span, .. } => {
+            assert_eq!(span.offset, grammar_string.len());
+            assert_eq!(span.len, 0);
+        },
+        _ => unreachable!(),
+    }
+
+    let grammar_string = "E -> Pair('a')\nPair(A, B) -> A B\n";
+    match Grammar::parse(grammar_string).unwrap_err() {
+        dotlr::GrammarError::ArgumentCountMismatch { span, .. } => {
+            assert_eq!(&grammar_string[span.offset..span.offset + span.len], "Pair('a')");
+        },
+        _ => unreachable!(),
+    }
+
+    let grammar_string = "E -> 'a'\n%on default 'a' %push string\n";
+    match Grammar::parse(grammar_string).unwrap_err() {
+        dotlr::GrammarError::UndefinedMode { span, .. } => {
+            assert_eq!(&grammar_string[span.offset..span.offset + span.len], "string");
+        },
+        _ => unreachable!(),
+    }
+}
+
+
+#[test]
+fn correctly_reporting_all_errors_of_grammars() {
+    let grammar_string = "E -> E '+' T\n  | T )\nT -> %n (\nF -> -> x\n%n -> /[/\nG -> Pair(%n)\nPair(A, B) -> A B\n";
+    let errors = Grammar::parse_all_errors(grammar_string).unwrap_err();
+
+    let lines = errors
+        .iter()
+        .map(|error| {
+            match error {
+                dotlr::GrammarError::UnexpectedToken { span, .. }
+                | dotlr::GrammarError::InvalidRegex { span, .. }
+                | dotlr::GrammarError::ArgumentCountMismatch { span, .. } => span.line,
+                _ => unreachable!(),
+            }
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(lines, [2, 3, 4, 5, 6]);
+
+    assert!(matches!(errors[3], dotlr::GrammarError::InvalidRegex { .. }));
+    assert!(matches!(errors[4], dotlr::GrammarError::ArgumentCountMismatch { .. }));
+}
+
+#[test]
+fn correctly_reporting_first_error_of_all_errors_when_parsing() {
+    let grammar_string = "E -> 'a' )\nT -> -> 'b'\n";
+    let error = Grammar::parse(grammar_string).unwrap_err();
+    let errors = Grammar::parse_all_errors(grammar_string).unwrap_err();
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(error.to_string(), errors[0].to_string());
+}
+
+#[test]
+fn correctly_parsing_grammar_without_errors_when_reporting_all_errors() {
+    for grammar_string in common::grammars::CORRECT {
+        let grammar = Grammar::parse_all_errors(grammar_string).unwrap();
+        assert_eq!(grammar.to_string(), Grammar::parse(grammar_string).unwrap().to_string());
+    }
+}
@@ -0,0 +1,148 @@
+mod common;
+
+use {
+    dotlr::{
+        Grammar,
+        GrammarResolver,
+        GrammarWarning,
+        Span,
+    },
+    smol_str::SmolStr,
+};
+
+
+#[test]
+fn correctly_linting_correct_grammars_without_warnings() {
+    for grammar_string in common::grammars::CORRECT {
+        let grammar = Grammar::parse(grammar_string).unwrap();
+        assert_eq!(grammar.lint(), []);
+    }
+}
+
+#[test]
+fn correctly_warning_about_unreachable_symbols() {
+    let grammar_string = "E -> 'a'\nF -> 'b' G\nG -> 'c'\n";
+    let grammar = Grammar::parse(grammar_string).unwrap();
+
+    let warnings = grammar.lint();
+    assert_eq!(warnings.len(), 2);
+
+    match &warnings[0] {
+        GrammarWarning::UnreachableSymbol { symbol, span } => {
+            assert_eq!(symbol.as_str(), "F");
+            let span = span.as_ref().unwrap();
+            assert_eq!(&grammar_string[span.offset..span.offset + span.len], "F -> 'b' G");
+        },
+        _ => unreachable!(),
+    }
+    match &warnings[1] {
+        GrammarWarning::UnreachableSymbol { symbol, .. } => {
+            assert_eq!(symbol.as_str(), "G");
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn correctly_considering_all_entry_symbols_reachable() {
+    let grammar = Grammar::parse("%start E F\nE -> 'a'\nF -> 'b'\n").unwrap();
+    assert_eq!(grammar.lint(), []);
+}
+
+#[test]
+fn correctly_warning_about_unproductive_symbols() {
+    let grammar = Grammar::parse("E -> 'a' | F\nF -> F 'b'\n").unwrap();
+
+    let warnings = grammar.lint();
+    assert_eq!(warnings.len(), 1);
+
+    match &warnings[0] {
+        GrammarWarning::UnproductiveSymbol { symbol, .. } => {
+            assert_eq!(symbol.as_str(), "F");
+        },
+        _ => unreachable!(),
+    }
+    assert_eq!(
+        warnings[0].to_string(),
+        "symbol F at line 2 column 1 cannot derive any sequence of tokens",
+    );
+}
+
+#[test]
+fn correctly_warning_about_unused_regex_tokens() {
+    let grammar_string = "E -> %a\n%a -> /a/\n%b -> /b/\n";
+    let grammar = Grammar::parse(grammar_string).unwrap();
+
+    let warnings = grammar.lint();
+    assert_eq!(warnings.len(), 1);
+
+    match &warnings[0] {
+        GrammarWarning::UnusedRegexToken { regex_token, span } => {
+            assert_eq!(regex_token.as_str(), "b");
+            let span = span.as_ref().unwrap();
+            assert_eq!(&grammar_string[span.offset..span.offset + span.len], "%b -> /b/");
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn correctly_warning_about_duplicate_rules() {
+    let grammar_string = "E -> 'a' 'b'\nE -> 'c'\nE -> 'a' 'b'\n";
+    let grammar = Grammar::parse(grammar_string).unwrap();
+
+    let warnings = grammar.lint();
+    assert_eq!(warnings.len(), 1);
+
+    match &warnings[0] {
+        GrammarWarning::DuplicateRule { rule, span } => {
+            assert_eq!(rule.to_string(), "E -> 'a' 'b'");
+            assert_eq!(span, &Some(Span { offset: 22, len: 12, line: 3, column: 1, source: None }));
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn correctly_warning_about_trivial_cycles() {
+    let grammar = Grammar::parse("E -> E | F | 'a'\nF -> E\n").unwrap();
+
+    let warnings = grammar.lint();
+    let cyclic_rules = warnings
+        .iter()
+        .map(|warning| {
+            match warning {
+                GrammarWarning::TrivialCycle { rule, .. } => rule.to_string(),
+                _ => unreachable!(),
+            }
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(cyclic_rules, ["E -> E", "E -> F", "F -> E"]);
+}
+
+#[test]
+fn correctly_locating_warnings_about_imported_definitions() {
+    struct InMemoryResolver;
+    impl GrammarResolver for InMemoryResolver {
+        fn resolve(&mut self, path: &str, _: Option<&str>) -> Result<(SmolStr, String), SmolStr> {
+            match path {
+                "lib.lr" => Ok((path.into(), "A -> 'a'\nB -> 'b'\n%c -> /c/".into())),
+                _ => Err("not found".into()),
+            }
+        }
+    }
+
+    let grammar =
+        Grammar::parse_with_resolver("%import \"lib.lr\"\nS -> A\nT -> 't'", &mut InMemoryResolver)
+            .unwrap();
+
+    let warnings = grammar.lint();
+    assert_eq!(
+        warnings.iter().map(|warning| warning.to_string()).collect::<Vec<_>>(),
+        [
+            "symbol B at line 2 column 1 in lib.lr is not reachable from the entry symbols",
+            "symbol T at line 3 column 1 is not reachable from the entry symbols",
+            "regex token %c at line 3 column 1 in lib.lr is not used in any rule",
+        ],
+    );
+}
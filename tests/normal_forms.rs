@@ -0,0 +1,260 @@
+mod common;
+
+use dotlr::{
+    AtomicPattern,
+    Grammar,
+    Parser,
+    Rewrite,
+    Rule,
+};
+
+
+fn is_in_chomsky_normal_form(grammar: &Grammar, rule: &Rule) -> bool {
+    match rule.pattern() {
+        [] => grammar.entry_symbols().contains(rule.symbol()),
+        [AtomicPattern::Token(_)] => true,
+        [AtomicPattern::Symbol(first), AtomicPattern::Symbol(second)] => {
+            !grammar.entry_symbols().contains(first) && !grammar.entry_symbols().contains(second)
+        },
+        _ => false,
+    }
+}
+
+fn is_in_greibach_normal_form(grammar: &Grammar, rule: &Rule) -> bool {
+    match rule.pattern() {
+        [] => grammar.entry_symbols().contains(rule.symbol()),
+        [AtomicPattern::Token(_), rest @ ..] => {
+            rest.iter().all(|atomic_pattern| {
+                match atomic_pattern {
+                    AtomicPattern::Symbol(symbol) => !grammar.entry_symbols().contains(symbol),
+                    AtomicPattern::Token(_) => false,
+                }
+            })
+        },
+        _ => false,
+    }
+}
+
+
+#[test]
+fn correctly_converting_correct_grammars_to_normal_forms() {
+    for grammar_string in common::grammars::CORRECT {
+        let grammar = Grammar::parse(grammar_string).unwrap();
+
+        let (chomsky_grammar, _) = grammar.to_chomsky_normal_form();
+        for rule in chomsky_grammar.rules() {
+            assert!(is_in_chomsky_normal_form(&chomsky_grammar, rule), "{}", rule);
+        }
+        assert!(Parser::check(&chomsky_grammar).is_ok());
+
+        let (greibach_grammar, _) = grammar.to_greibach_normal_form();
+        for rule in greibach_grammar.rules() {
+            assert!(is_in_greibach_normal_form(&greibach_grammar, rule), "{}", rule);
+        }
+        assert!(Parser::check(&greibach_grammar).is_ok());
+    }
+}
+
+#[test]
+fn correctly_adding_start_symbols() {
+    let grammar = Grammar::parse("S -> '(' S ')' | 'x'\n").unwrap();
+
+    let (transformed, rewrites) = grammar.add_start_symbols();
+    assert_eq!(
+        transformed.to_string().trim(),
+        r#"
+SStart -> S
+S -> '(' S ')'
+S -> 'x'
+        "#
+        .trim(),
+    );
+    assert_eq!(transformed.start_symbol().as_str(), "SStart");
+    assert_eq!(
+        rewrites,
+        [Rewrite::StartSymbolAdded { symbol: "S".into(), start: "SStart".into() }],
+    );
+
+    // Entry symbols that are not used in the rules are kept.
+    let (transformed, rewrites) = transformed.add_start_symbols();
+    assert_eq!(transformed.start_symbol().as_str(), "SStart");
+    assert_eq!(rewrites, []);
+}
+
+#[test]
+fn correctly_isolating_tokens() {
+    let grammar = Grammar::parse("E -> E '+' %n | %n | 'if' E '+' E\n%n -> /[0-9]+/\n").unwrap();
+
+    let (transformed, rewrites) = grammar.isolate_tokens();
+    assert_eq!(
+        transformed.to_string().trim(),
+        r#"
+E -> E Token NToken
+E -> %n
+E -> IfToken E Token E
+Token -> '+'
+NToken -> %n
+IfToken -> 'if'
+
+%n -> /^[0-9]+/
+        "#
+        .trim(),
+    );
+    assert_eq!(
+        rewrites.iter().map(|rewrite| rewrite.to_string()).collect::<Vec<_>>(),
+        [
+            "replaced '+' by Token in the rules with multiple atomic patterns",
+            "replaced %n by NToken in the rules with multiple atomic patterns",
+            "replaced 'if' by IfToken in the rules with multiple atomic patterns",
+        ],
+    );
+}
+
+#[test]
+fn correctly_binarizing_rules() {
+    let grammar =
+        Grammar::parse("S -> A B C D | A B\nA -> 'a'\nB -> 'b'\nC -> 'c'\nD -> 'd'\n").unwrap();
+
+    let (transformed, rewrites) = grammar.binarize_rules();
+    assert_eq!(
+        transformed.to_string().trim(),
+        r#"
+S -> A SPart
+SPart -> B SPart2
+SPart2 -> C D
+S -> A B
+A -> 'a'
+B -> 'b'
+C -> 'c'
+D -> 'd'
+        "#
+        .trim(),
+    );
+    assert_eq!(
+        rewrites,
+        [Rewrite::RuleSplit {
+            rule: grammar.rules()[0].clone(),
+            symbols: vec!["SPart".into(), "SPart2".into()],
+        }],
+    );
+}
+
+#[test]
+fn correctly_removing_empty_rules() {
+    let grammar = Grammar::parse("S -> A 'x' A | ε\nA -> 'a' | B\nB -> ε\n").unwrap();
+
+    let (transformed, rewrites) = grammar.remove_empty_rules();
+    assert_eq!(
+        transformed.to_string().trim(),
+        r#"
+S -> A 'x' A
+S -> A 'x'
+S -> 'x' A
+S -> 'x'
+S -> ε
+A -> 'a'
+        "#
+        .trim(),
+    );
+    assert_eq!(
+        rewrites.iter().map(|rewrite| rewrite.to_string()).collect::<Vec<_>>(),
+        [
+            "added S -> A 'x'",
+            "added S -> 'x' A",
+            "added S -> 'x'",
+            "removed B -> ε",
+            "removed A -> B",
+        ],
+    );
+}
+
+#[test]
+fn correctly_removing_unit_rules() {
+    let grammar =
+        Grammar::parse("E -> T | E '+' T\nT -> F | T '*' F\nF -> %n\n%n -> /[0-9]+/\n").unwrap();
+
+    let (transformed, rewrites) = grammar.remove_unit_rules();
+    assert_eq!(
+        transformed.to_string().trim(),
+        r#"
+E -> E '+' T
+E -> T '*' F
+E -> %n
+T -> T '*' F
+T -> %n
+F -> %n
+
+%n -> /^[0-9]+/
+        "#
+        .trim(),
+    );
+    assert_eq!(
+        rewrites.iter().map(|rewrite| rewrite.to_string()).collect::<Vec<_>>(),
+        ["substituted the rules of T into E -> T", "substituted the rules of F into T -> F"],
+    );
+}
+
+#[test]
+fn correctly_converting_to_chomsky_normal_form() {
+    let grammar = Grammar::parse("S -> '(' S ')' | ε\n").unwrap();
+
+    let (transformed, _) = grammar.to_chomsky_normal_form();
+    assert_eq!(
+        transformed.to_string().trim(),
+        r#"
+SStart -> ε
+SStart -> Token SPart
+S -> Token SPart
+SPart -> S Token2
+SPart -> ')'
+Token -> '('
+Token2 -> ')'
+        "#
+        .trim(),
+    );
+
+    let parser = Parser::lr(transformed).unwrap();
+    assert!(parser.parse(parser.tokenize("").unwrap()).is_ok());
+    assert!(parser.parse(parser.tokenize("((()))").unwrap()).is_ok());
+    assert!(parser.parse(parser.tokenize("(()").unwrap()).is_err());
+}
+
+#[test]
+fn correctly_converting_to_greibach_normal_form() {
+    let grammar = Grammar::parse("S -> '(' S ')' | ε\n").unwrap();
+
+    let (transformed, _) = grammar.to_greibach_normal_form();
+    assert_eq!(
+        transformed.to_string().trim(),
+        r#"
+SStart -> ε
+SStart -> '(' SPart
+SPart -> '(' SPart Token2
+SPart -> ')'
+Token2 -> ')'
+        "#
+        .trim(),
+    );
+
+    let parser = Parser::lr(transformed).unwrap();
+    assert!(parser.parse(parser.tokenize("").unwrap()).is_ok());
+    assert!(parser.parse(parser.tokenize("((()))").unwrap()).is_ok());
+    assert!(parser.parse(parser.tokenize("(()").unwrap()).is_err());
+}
+
+#[test]
+fn correctly_converting_left_recursive_grammars_to_greibach_normal_form() {
+    let grammar =
+        Grammar::parse("E -> E '+' T | T\nT -> T '*' F | F\nF -> '(' E ')' | %n\n%n -> /[0-9]+/\n")
+            .unwrap();
+
+    let (transformed, _) = grammar.to_chomsky_normal_form();
+    let parser = Parser::lr(transformed).unwrap();
+    assert!(parser.parse(parser.tokenize("1 + 2 * (3 + 4)").unwrap()).is_ok());
+
+    let (transformed, _) = grammar.to_greibach_normal_form();
+    for rule in transformed.rules() {
+        assert!(is_in_greibach_normal_form(&transformed, rule), "{}", rule);
+    }
+    assert!(transformed.to_string().contains("ETail -> '+' T ETail"));
+}
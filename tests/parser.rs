@@ -10,6 +10,7 @@ use {
         ParserError,
         RegexToken,
         Rule,
+        Span,
         State,
         Symbol,
         Token,
@@ -66,9 +67,19 @@ fn raising_correct_error_when_creating_parser_for_shift_reduce_conflict_grammar(
     let error = Parser::lr(grammar).unwrap_err();
 
     let error_string = error.to_string();
-    if let ParserError::Conflict { parser, token, state } = error {
+    if let ParserError::Conflict { parser, token, state, spans } = error {
         assert_eq!(error_string, format!("conflict at state {} on {}", state, token));
 
+        // Spans are of the rule to reduce (i.e., `E -> E '+' E`) and the token to shift (i.e., `'+'`).
+        let slices = spans
+            .iter()
+            .map(|span| {
+                &common::grammars::SHIFT_REDUCE_CONFLICT[span.offset..span.offset + span.len]
+            })
+            .collect::<Vec<_>>();
+        assert!(slices.contains(&"E -> E '+' E"));
+        assert!(slices.contains(&"'+'"));
+
         let possible_actions = parser.action_table()[state].get(&token);
         assert!(possible_actions.is_some());
 
@@ -93,9 +104,17 @@ fn raising_correct_error_when_creating_parser_for_reduce_reduce_conflict_grammar
     let error = Parser::lr(grammar).unwrap_err();
 
     let error_string = error.to_string();
-    if let ParserError::Conflict { parser, token, state } = error {
+    if let ParserError::Conflict { parser, token, state, spans } = error {
         assert_eq!(error_string, format!("conflict at state {} on {}", state, token));
 
+        let slices = spans
+            .iter()
+            .map(|span| {
+                &common::grammars::REDUCE_REDUCE_CONFLICT[span.offset..span.offset + span.len]
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(slices, ["A -> '0'", "B -> '0'"]);
+
         let possible_actions = parser.action_table()[state].get(&token);
         assert!(possible_actions.is_some());
 
@@ -116,8 +135,9 @@ fn raising_correct_error_when_creating_lalr_parser_for_non_lalr_grammar() {
     let error = Parser::lalr(grammar).unwrap_err();
 
     let error_string = error.to_string();
-    if let ParserError::Conflict { parser, token, state } = error {
+    if let ParserError::Conflict { parser, token, state, spans } = error {
         assert_eq!(error_string, format!("conflict at state {} on {}", state, token));
+        assert!(spans.len() >= 2);
 
         let possible_actions = parser.action_table()[state].get(&token);
         assert!(possible_actions.is_some());
@@ -222,12 +242,14 @@ B -> '1'
         // +-------+------------------+------------+--------------+
         // | State |      Items       | Lookaheads | Transitions  |
         // +-------+------------------+------------+--------------+
-        // | 0     |  E -> . E '+' B  | { $, '+' } |   E   ->  1  |
-        // |       |  E -> . B        | { $, '+' } |   B   ->  2  |
-        // |       |  B -> . '0'      | { $, '+' } |  '0'  ->  3  |
-        // |       |  B -> . '1'      | { $, '+' } |  '1'  ->  4  |
+        // | 0     |  E' -> . E       | { $ }      |   E   ->  1  |
+        // |       |  E -> . E '+' B  | { $, '+' } |   B   ->  2  |
+        // |       |  E -> . B        | { $, '+' } |  '0'  ->  3  |
+        // |       |  B -> . '0'      | { $, '+' } |  '1'  ->  4  |
+        // |       |  B -> . '1'      | { $, '+' } |              |
         // +-------+------------------+------------+--------------+
-        // | 1     |  E -> E . '+' B  | { $, '+' } |  '+'  ->  5  |
+        // | 1     |  E' -> E .       | { $ }      |  '+'  ->  5  |
+        // |       |  E -> E . '+' B  | { $, '+' } |              |
         // +-------+------------------+------------+--------------+
         // | 2     |  E -> B .        | { $, '+' } |              |
         // +-------+------------------+------------+--------------+
@@ -250,6 +272,17 @@ B -> '1'
                 State::new(
                     0,
                     [
+                        // E' -> . E | { $ }
+                        Item::new(
+                            Rule::new(
+                                "E'",
+                                [
+                                    Symbol::from("E").into()
+                                ]
+                            ),
+                            0,
+                            [Token::Eof],
+                        ),
                         // E -> . E '+' B | { $, '+' }
                         Item::new(
                             Rule::new(
@@ -313,6 +346,17 @@ B -> '1'
                 State::new(
                     1,
                     [
+                        // E' -> E . | { $ }
+                        Item::new(
+                            Rule::new(
+                                "E'",
+                                [
+                                    Symbol::from("E").into()
+                                ]
+                            ),
+                            1,
+                            [Token::Eof],
+                        ),
                         // E -> E . '+' B | { $, '+' }
                         Item::new(
                             Rule::new(
@@ -477,9 +521,9 @@ B -> '1'
         // +-------+--------------------------------+--------------+
         // | 0     |     -     s3     s4      -     |    1    2    |
         // +-------+--------------------------------+--------------+
-        // | 1     |    s5      -      -      -     |    -    -    |
+        // | 1     |    s5      -      -     acc    |    -    -    |
         // +-------+--------------------------------+--------------+
-        // | 2     |    r2      -      -     a2     |    -    -    |
+        // | 2     |    r2      -      -     r2     |    -    -    |
         // +-------+--------------------------------+--------------+
         // | 3     |    r3      -      -     r3     |    -    -    |
         // +-------+--------------------------------+--------------+
@@ -487,7 +531,7 @@ B -> '1'
         // +-------+--------------------------------+--------------+
         // | 5     |     -     s3     s4      -     |    -    6    |
         // +-------+--------------------------------+--------------+
-        // | 6     |    r1      -      -     a1     |    -    -    |
+        // | 6     |    r1      -      -     r1     |    -    -    |
         // +-------+--------------------------------+--------------+
 
         #[rustfmt::skip]
@@ -514,6 +558,10 @@ B -> '1'
                             ConstantToken::from("+").into(),
                             IndexSet::from([Action::Shift { next_state: 5 }]),
                         ),
+                        (
+                            Token::Eof,
+                            IndexSet::from([Action::Accept]),
+                        ),
                     ],
                 ),
                 // State 2
@@ -525,7 +573,7 @@ B -> '1'
                         ),
                         (
                             Token::Eof,
-                            IndexSet::from([Action::Accept { rule_index: 1 }]),
+                            IndexSet::from([Action::Reduce { rule_index: 1 }]),
                         ),
                     ],
                 ),
@@ -577,7 +625,7 @@ B -> '1'
                         ),
                         (
                             Token::Eof,
-                            IndexSet::from([Action::Accept { rule_index: 0 }]),
+                            IndexSet::from([Action::Reduce { rule_index: 0 }]),
                         ),
                     ],
                 ),
@@ -770,36 +818,39 @@ T -> %id
         // +-------+------------------------+-----------------+--------------+
         // | State |         Items          |   Lookaheads    | Transitions  |
         // +-------+------------------------+-----------------+--------------+
-        // | 0     |  P -> . E              | { $ }           |   E   ->  1  |
-        // |       |  E -> . E '+' T        | { $, '+' }      |   T   ->  2  |
-        // |       |  E -> . T              | { $, '+' }      |  %id  ->  3  |
+        // | 0     |  P' -> . P             | { $ }           |   P   ->  1  |
+        // |       |  P -> . E              | { $ }           |   E   ->  2  |
+        // |       |  E -> . E '+' T        | { $, '+' }      |   T   ->  3  |
+        // |       |  E -> . T              | { $, '+' }      |  %id  ->  4  |
         // |       |  T -> . %id '(' E ')'  | { $, '+' }      |              |
         // |       |  T -> . %id            | { $, '+' }      |              |
         // +-------+------------------------+-----------------+--------------+
-        // | 1     |  P -> E .              | { $ }           |  '+'  ->  7  |
+        // | 1     |  P' -> P .             | { $ }           |              |
+        // +-------+------------------------+-----------------+--------------+
+        // | 2     |  P -> E .              | { $ }           |  '+'  ->  8  |
         // |       |  E -> E . '+' T        | { $, '+' }      |              |
         // +-------+------------------------+-----------------+--------------+
-        // | 2     |  E -> T .              | { $, '+', ')' } |              |
+        // | 3     |  E -> T .              | { $, '+', ')' } |              |
         // +-------+------------------------+-----------------+--------------+
-        // | 3     |  T -> %id . '(' E ')'  | { $, '+', ')' } |  '('  ->  4  |
+        // | 4     |  T -> %id . '(' E ')'  | { $, '+', ')' } |  '('  ->  5  |
         // |       |  T -> %id .            | { $, '+', ')' } |              |
         // +-------+------------------------+-----------------+--------------+
-        // | 4     |  T -> %id '(' . E ')'  | { $, '+', ')' } |   T   ->  2  |
-        // |       |  E -> . E '+' T        | { ')', '+' }    |  %id  ->  3  |
-        // |       |  E -> . T              | { ')', '+' }    |   E   ->  5  |
+        // | 5     |  T -> %id '(' . E ')'  | { $, '+', ')' } |   T   ->  3  |
+        // |       |  E -> . E '+' T        | { ')', '+' }    |  %id  ->  4  |
+        // |       |  E -> . T              | { ')', '+' }    |   E   ->  6  |
         // |       |  T -> . %id '(' E ')'  | { ')', '+' }    |              |
         // |       |  T -> . %id            | { ')', '+' }    |              |
         // +-------+------------------------+-----------------+--------------+
-        // | 5     |  T -> %id '(' E . ')'  | { $, '+', ')' } |  ')'  ->  6  |
-        // |       |  E -> E . '+' T        | { ')', '+' }    |  '+'  ->  7  |
+        // | 6     |  T -> %id '(' E . ')'  | { $, '+', ')' } |  ')'  ->  7  |
+        // |       |  E -> E . '+' T        | { ')', '+' }    |  '+'  ->  8  |
         // +-------+------------------------+-----------------+--------------+
-        // | 6     |  T -> %id '(' E ')' .  | { ')', '+', $ } |              |
+        // | 7     |  T -> %id '(' E ')' .  | { ')', '+', $ } |              |
         // +-------+------------------------+-----------------+--------------+
-        // | 7     |  E -> E '+' . T        | { ')', '+', $ } |  %id  ->  3  |
-        // |       |  T -> . %id '(' E ')'  | { ')', '+', $ } |   T   ->  8  |
+        // | 8     |  E -> E '+' . T        | { ')', '+', $ } |  %id  ->  4  |
+        // |       |  T -> . %id '(' E ')'  | { ')', '+', $ } |   T   ->  9  |
         // |       |  T -> . %id            | { ')', '+', $ } |              |
         // +-------+------------------------+-----------------+--------------+
-        // | 8     |  E -> E '+' T .        | { ')', '+', $ } |              |
+        // | 9     |  E -> E '+' T .        | { ')', '+', $ } |              |
         // +-------+------------------------+-----------------+--------------+
 
         #[rustfmt::skip]
@@ -810,6 +861,17 @@ T -> %id
                 State::new(
                     0,
                     [
+                        // P' -> . P | { $ }
+                        Item::new(
+                            Rule::new(
+                                "P'",
+                                [
+                                    Symbol::from("P").into(),
+                                ]
+                            ),
+                            0,
+                            [Token::Eof],
+                        ),
                         // P -> . E | { $ }
                         Item::new(
                             Rule::new(
@@ -872,18 +934,39 @@ T -> %id
                         ),
                     ],
                     [
-                        // E -> 1
-                        (Symbol::from("E").into(), 1),
-                        // T -> 2
-                        (Symbol::from("T").into(), 2),
-                        // %id -> 3
-                        (RegexToken::from("id").into(), 3),
+                        // P -> 1
+                        (Symbol::from("P").into(), 1),
+                        // E -> 2
+                        (Symbol::from("E").into(), 2),
+                        // T -> 3
+                        (Symbol::from("T").into(), 3),
+                        // %id -> 4
+                        (RegexToken::from("id").into(), 4),
                     ],
                 ),
 
-                 // State 1
+                // State 1
                 State::new(
                     1,
+                    [
+                        // P' -> P . | { $ }
+                        Item::new(
+                            Rule::new(
+                                "P'",
+                                [
+                                    Symbol::from("P").into(),
+                                ]
+                            ),
+                            1,
+                            [Token::Eof],
+                        ),
+                    ],
+                    [],
+                ),
+
+                // State 2
+                State::new(
+                    2,
                     [
                         // P -> E . | { $ }
                         Item::new(
@@ -911,14 +994,14 @@ T -> %id
                         ),
                     ],
                     [
-                        // '+' -> 7
-                        (ConstantToken::from("+").into(), 7),
+                        // '+' -> 8
+                        (ConstantToken::from("+").into(), 8),
                     ],
                 ),
 
-                // State 2
+                // State 3
                 State::new(
-                    2,
+                    3,
                     [
                         // E -> T . | { $, '+', ')' }
                         Item::new(
@@ -939,9 +1022,9 @@ T -> %id
                     [],
                 ),
 
-                // State 3
+                // State 4
                 State::new(
-                    3,
+                    4,
                     [
                         // T -> %id . '(' E ')' | { $, '+', ')' }
                         Item::new(
@@ -978,14 +1061,14 @@ T -> %id
                         ),
                     ],
                     [
-                        // '(' -> 4
-                        (ConstantToken::from("(").into(), 4),
+                        // '(' -> 5
+                        (ConstantToken::from("(").into(), 5),
                     ],
                 ),
 
-                // State 4
+                // State 5
                 State::new(
-                    4,
+                    5,
                     [
                         // T -> %id '(' . E ')' | { $, '+', ')' }
                         Item::new(
@@ -1056,18 +1139,18 @@ T -> %id
                         ),
                     ],
                     [
-                        // T -> 2
-                        (Symbol::from("T").into(), 2),
-                        // %id -> 3
-                        (RegexToken::from("id").into(), 3),
-                        // 'E' -> 5
-                        (Symbol::from("E").into(), 5),
+                        // T -> 3
+                        (Symbol::from("T").into(), 3),
+                        // %id -> 4
+                        (RegexToken::from("id").into(), 4),
+                        // 'E' -> 6
+                        (Symbol::from("E").into(), 6),
                     ],
                 ),
 
-                // State 5
+                // State 6
                 State::new(
-                    5,
+                    6,
                     [
                         // T -> %id '(' E . ')' | { $, '+', ')' }
                         Item::new(
@@ -1102,16 +1185,16 @@ T -> %id
                         ),
                     ],
                     [
-                        // ')' -> 6
-                        (ConstantToken::from(")").into(), 6),
-                        // '+' -> 7
-                        (ConstantToken::from("+").into(), 7),
+                        // ')' -> 7
+                        (ConstantToken::from(")").into(), 7),
+                        // '+' -> 8
+                        (ConstantToken::from("+").into(), 8),
                     ],
                 ),
 
-                // State 6
+                // State 7
                 State::new(
-                    6,
+                    7,
                     [
                         // T -> %id '(' E ')' . | { ')', '+', $ }
                         Item::new(
@@ -1135,9 +1218,9 @@ T -> %id
                     [],
                 ),
 
-                // State 7
+                // State 8
                 State::new(
-                    7,
+                    8,
                     [
                         // E -> E '+' . T | { ')', '+', $ }
                         Item::new(
@@ -1191,16 +1274,16 @@ T -> %id
                         ),
                     ],
                     [
-                        // %id -> 3
-                        (RegexToken::from("id").into(), 3),
-                        // T -> 8
-                        (Symbol::from("T").into(), 8),
+                        // %id -> 4
+                        (RegexToken::from("id").into(), 4),
+                        // T -> 9
+                        (Symbol::from("T").into(), 9),
                     ],
                 ),
 
-                // State 8
+                // State 9
                 State::new(
-                    8,
+                    9,
                     [
                         // E -> E '+' T . | { ')', '+', $ }
                         Item::new(
@@ -1233,23 +1316,25 @@ T -> %id
         // | State | ------------------------------------- |
         // |       |    '+'    '('    ')'    %id     $     |
         // +-------+---------------------------------------+
-        // | 0     |     -      -      -     s3      -     |
+        // | 0     |     -      -      -     s4      -     |
+        // +-------+---------------------------------------+
+        // | 1     |     -      -      -      -     acc    |
         // +-------+---------------------------------------+
-        // | 1     |    s7      -      -      -     a1     |
+        // | 2     |    s8      -      -      -     r1     |
         // +-------+---------------------------------------+
-        // | 2     |    r3      -     r3      -     r3     |
+        // | 3     |    r3      -     r3      -     r3     |
         // +-------+---------------------------------------+
-        // | 3     |    r5     s4     r5      -     r5     |
+        // | 4     |    r5     s5     r5      -     r5     |
         // +-------+---------------------------------------+
-        // | 4     |     -      -      -     s3      -     |
+        // | 5     |     -      -      -     s4      -     |
         // +-------+---------------------------------------+
-        // | 5     |    s7      -     s6      -      -     |
+        // | 6     |    s8      -     s7      -      -     |
         // +-------+---------------------------------------+
-        // | 6     |    r4      -     r4      -     r4     |
+        // | 7     |    r4      -     r4      -     r4     |
         // +-------+---------------------------------------+
-        // | 7     |     -      -      -     s3      -     |
+        // | 8     |     -      -      -     s4      -     |
         // +-------+---------------------------------------+
-        // | 8     |    r2      -     r2      -     r2     |
+        // | 9     |    r2      -     r2      -     r2     |
         // +-------+---------------------------------------+
 
         #[rustfmt::skip]
@@ -1261,24 +1346,33 @@ T -> %id
                     [
                         (
                             RegexToken::from("id").into(),
-                            IndexSet::from([Action::Shift { next_state: 3 }]),
+                            IndexSet::from([Action::Shift { next_state: 4 }]),
                         ),
                     ],
                 ),
                 // State 1
+                IndexMap::<Token, IndexSet<Action>>::from(
+                    [
+                        (
+                            Token::Eof,
+                            IndexSet::from([Action::Accept]),
+                        ),
+                    ],
+                ),
+                // State 2
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
                             ConstantToken::from("+").into(),
-                            IndexSet::from([Action::Shift { next_state: 7 }]),
+                            IndexSet::from([Action::Shift { next_state: 8 }]),
                         ),
                         (
                             Token::Eof,
-                            IndexSet::from([Action::Accept { rule_index: 0 }]),
+                            IndexSet::from([Action::Reduce { rule_index: 0 }]),
                         ),
                     ],
                 ),
-                // State 2
+                // State 3
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
@@ -1295,7 +1389,7 @@ T -> %id
                         ),
                     ],
                 ),
-                // State 3
+                // State 4
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
@@ -1304,7 +1398,7 @@ T -> %id
                         ),
                         (
                             ConstantToken::from("(").into(),
-                            IndexSet::from([Action::Shift { next_state: 4 }]),
+                            IndexSet::from([Action::Shift { next_state: 5 }]),
                         ),
                         (
                             ConstantToken::from(")").into(),
@@ -1316,29 +1410,29 @@ T -> %id
                         ),
                     ],
                 ),
-                // State 4
+                // State 5
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
                             RegexToken::from("id").into(),
-                            IndexSet::from([Action::Shift { next_state: 3 }]),
+                            IndexSet::from([Action::Shift { next_state: 4 }]),
                         ),
                     ],
                 ),
-                // State 5
+                // State 6
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
                             ConstantToken::from("+").into(),
-                            IndexSet::from([Action::Shift { next_state: 7 }]),
+                            IndexSet::from([Action::Shift { next_state: 8 }]),
                         ),
                         (
                             ConstantToken::from(")").into(),
-                            IndexSet::from([Action::Shift { next_state: 6 }]),
+                            IndexSet::from([Action::Shift { next_state: 7 }]),
                         ),
                     ],
                 ),
-                // State 6
+                // State 7
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
@@ -1355,16 +1449,16 @@ T -> %id
                         ),
                     ],
                 ),
-                // State 7
+                // State 8
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
                             RegexToken::from("id").into(),
-                            IndexSet::from([Action::Shift { next_state: 3 }]),
+                            IndexSet::from([Action::Shift { next_state: 4 }]),
                         ),
                     ],
                 ),
-                // State 8
+                // State 9
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
@@ -1387,29 +1481,31 @@ T -> %id
 
     let goto_table = parser.goto_table();
     {
-        // +-------+-------------------+
-        // |       |       Goto        |
-        // | State | ----------------- |
-        // |       |    P    E    T    |
-        // +-------+-------------------+
-        // | 0     |    -    1    2    |
-        // +-------+-------------------+
-        // | 1     |    -    -    -    |
-        // +-------+-------------------+
-        // | 2     |    -    -    -    |
-        // +-------+-------------------+
-        // | 3     |    -    -    -    |
-        // +-------+-------------------+
-        // | 4     |    -    5    2    |
-        // +-------+-------------------+
-        // | 5     |    -    -    -    |
-        // +-------+-------------------+
-        // | 6     |    -    -    -    |
-        // +-------+-------------------+
-        // | 7     |    -    -    8    |
-        // +-------+-------------------+
-        // | 8     |    -    -    -    |
-        // +-------+-------------------+
+        // +-------+----------------------+
+        // |       |         Goto         |
+        // | State | -------------------- |
+        // |       |    P     E     T     |
+        // +-------+----------------------+
+        // | 0     |    1     2     3     |
+        // +-------+----------------------+
+        // | 1     |    -     -     -     |
+        // +-------+----------------------+
+        // | 2     |    -     -     -     |
+        // +-------+----------------------+
+        // | 3     |    -     -     -     |
+        // +-------+----------------------+
+        // | 4     |    -     -     -     |
+        // +-------+----------------------+
+        // | 5     |    -     6     3     |
+        // +-------+----------------------+
+        // | 6     |    -     -     -     |
+        // +-------+----------------------+
+        // | 7     |    -     -     -     |
+        // +-------+----------------------+
+        // | 8     |    -     -     9     |
+        // +-------+----------------------+
+        // | 9     |    -     -     -     |
+        // +-------+----------------------+
 
         #[rustfmt::skip]
         assert_eq!(
@@ -1418,8 +1514,9 @@ T -> %id
                 // State 0
                 IndexMap::<Symbol, usize>::from_iter(
                     [
-                        (Symbol::from("E"), 1),
-                        (Symbol::from("T"), 2),
+                        (Symbol::from("P"), 1),
+                        (Symbol::from("E"), 2),
+                        (Symbol::from("T"), 3),
                     ],
                 ),
                 // State 1
@@ -1440,13 +1537,13 @@ T -> %id
                 // State 4
                 IndexMap::<Symbol, usize>::from_iter(
                     [
-                        (Symbol::from("E"), 5),
-                        (Symbol::from("T"), 2),
                     ],
                 ),
                 // State 5
                 IndexMap::<Symbol, usize>::from_iter(
                     [
+                        (Symbol::from("E"), 6),
+                        (Symbol::from("T"), 3),
                     ],
                 ),
                 // State 6
@@ -1457,10 +1554,15 @@ T -> %id
                 // State 7
                 IndexMap::<Symbol, usize>::from_iter(
                     [
-                        (Symbol::from("T"), 8),
                     ],
                 ),
                 // State 8
+                IndexMap::<Symbol, usize>::from_iter(
+                    [
+                        (Symbol::from("T"), 9),
+                    ],
+                ),
+                // State 9
                 IndexMap::<Symbol, usize>::from_iter(
                     [
                     ],
@@ -1469,3 +1571,289 @@ T -> %id
         );
     }
 }
+
+#[test]
+fn correctly_creating_lr_parser_for_optional_grammar() {
+    let grammar = Grammar::parse(common::grammars::OPTIONAL).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let nullable_table = parser.nullable_table();
+    {
+        // Annotation -> ε
+        // Initializer -> ε
+        assert_eq!(
+            *nullable_table.deref(),
+            [Symbol::from("Annotation"), Symbol::from("Initializer")]
+                .into_iter()
+                .collect::<IndexSet<_>>(),
+        );
+    }
+
+    let first_table = parser.first_table();
+    {
+        // +-------------+------------+
+        // | Symbol      | First Set  |
+        // +-------------+------------+
+        // | Declaration | { 'let' }  |
+        // +-------------+------------+
+        // | Annotation  | { ε, ':' } |
+        // +-------------+------------+
+        // | Initializer | { ε, '=' } |
+        // +-------------+------------+
+
+        #[rustfmt::skip]
+        assert_eq!(
+            *first_table.deref(),
+            [
+                (
+                    Symbol::from("Declaration"),
+                    [
+                        ConstantToken::from("let").into(),
+                    ]
+                        .into(),
+                ),
+                (
+                    Symbol::from("Annotation"),
+                    [
+                        ConstantToken::from(":").into(),
+                    ]
+                        .into(),
+                ),
+                (
+                    Symbol::from("Initializer"),
+                    [
+                        ConstantToken::from("=").into(),
+                    ]
+                        .into(),
+                ),
+            ]
+                .into_iter()
+                .collect::<IndexMap<_, _>>()
+        );
+    }
+
+    let follow_table = parser.follow_table();
+    {
+        // +-------------+---------------+
+        // | Symbol      | Follow Set    |
+        // +-------------+---------------+
+        // | Declaration | { $ }         |
+        // +-------------+---------------+
+        // | Annotation  | { '=', ';' }  |
+        // +-------------+---------------+
+        // | Initializer | { ';' }       |
+        // +-------------+---------------+
+
+        #[rustfmt::skip]
+        assert_eq!(
+            *follow_table.deref(),
+            [
+                (
+                    Symbol::from("Declaration"),
+                    [
+                        Token::Eof,
+                    ]
+                        .into(),
+                ),
+                (
+                    Symbol::from("Annotation"),
+                    [
+                        ConstantToken::from("=").into(),
+                        ConstantToken::from(";").into(),
+                    ]
+                        .into(),
+                ),
+                (
+                    Symbol::from("Initializer"),
+                    [
+                        ConstantToken::from(";").into(),
+                    ]
+                        .into(),
+                ),
+            ]
+                .into_iter()
+                .collect::<IndexMap<_, _>>()
+        );
+    }
+
+    let automaton = parser.automaton();
+    {
+        // The state after `'let' %identifier`, where both of the optional parts can be skipped.
+        let state = automaton
+            .states()
+            .iter()
+            .find(|state| {
+                state
+                    .items()
+                    .iter()
+                    .any(|item| item.rule().symbol().as_str() == "Declaration" && item.dot() == 2)
+            })
+            .unwrap();
+
+        #[rustfmt::skip]
+        assert!(
+            state.items().contains(
+                // Annotation -> . | { '=', ';' }
+                &Item::new(
+                    Rule::new("Annotation", []),
+                    0,
+                    [ConstantToken::from("=").into(), ConstantToken::from(";").into()],
+                ),
+            )
+        );
+
+        let action_table = parser.action_table();
+        assert_eq!(
+            action_table[state.id()].get(&Token::from(ConstantToken::from(";"))),
+            Some(&IndexSet::from([Action::Reduce { rule_index: 2 }])),
+        );
+        assert_eq!(
+            action_table[state.id()].get(&Token::from(ConstantToken::from("="))),
+            Some(&IndexSet::from([Action::Reduce { rule_index: 2 }])),
+        );
+    }
+}
+
+
+#[test]
+fn correctly_resolving_conflicts_of_calculator_precedence_grammar() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR_PRECEDENCE).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let resolved_conflicts = parser.parsing_tables().resolved_conflicts();
+    assert_eq!(resolved_conflicts.len(), 30);
+
+    for action_map in parser.action_table() {
+        for actions in action_map.values() {
+            assert_eq!(actions.len(), 1);
+        }
+    }
+
+    let resolution_of = |rule_index: usize, token: Token| {
+        let resolved_conflict = resolved_conflicts
+            .iter()
+            .find(|resolved_conflict| {
+                resolved_conflict.reduce() == Action::Reduce { rule_index }
+                    && *resolved_conflict.token() == token
+            })
+            .unwrap();
+        assert_eq!(
+            parser.action_table()[resolved_conflict.state()].get(&token).map(|actions| actions[0]),
+            resolved_conflict.resolution(),
+        );
+        resolved_conflict.resolution().unwrap()
+    };
+
+    // Expr -> Expr '+' Expr . with '+' is reduced as '+' is left associative.
+    assert!(matches!(
+        resolution_of(0, ConstantToken::from("+").into()),
+        Action::Reduce { rule_index: 0 },
+    ));
+    // Expr -> Expr '+' Expr . with '*' is shifted as '*' has higher precedence.
+    assert!(matches!(resolution_of(0, ConstantToken::from("*").into()), Action::Shift { .. }));
+    // Expr -> Expr '^' Expr . with '^' is shifted as '^' is right associative.
+    assert!(matches!(resolution_of(4, ConstantToken::from("^").into()), Action::Shift { .. }));
+    // Expr -> '-' Expr . with '^' is reduced as the rule has the precedence of %uminus.
+    assert!(matches!(
+        resolution_of(5, ConstantToken::from("^").into()),
+        Action::Reduce { rule_index: 5 },
+    ));
+}
+
+#[test]
+fn correctly_removing_actions_of_non_associative_tokens() {
+    let grammar = Grammar::parse("%nonassoc '<'\nE -> E '<' E | %n\n%n -> /[0-9]+/").unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let resolved_conflicts = parser.parsing_tables().resolved_conflicts();
+    assert_eq!(resolved_conflicts.len(), 1);
+    assert_eq!(resolved_conflicts[0].resolution(), None);
+    assert!(
+        !parser.action_table()[resolved_conflicts[0].state()]
+            .contains_key(resolved_conflicts[0].token())
+    );
+}
+
+
+#[test]
+fn raising_correct_error_when_creating_parser_for_undefined_entry_symbol() {
+    let grammar = Grammar::parse("%start S T\nS -> 'a'").unwrap();
+    let error = Parser::lr(grammar).unwrap_err();
+    match error {
+        ParserError::UndefinedEntrySymbol { symbol, span } => {
+            assert_eq!(symbol.as_str(), "T");
+            assert_eq!(span, Some(Span { offset: 9, len: 1, line: 1, column: 10, source: None }));
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn correctly_creating_start_states_for_statements_grammar() {
+    for parser in [
+        Parser::lr(Grammar::parse(common::grammars::STATEMENTS).unwrap()).unwrap(),
+        Parser::lalr(Grammar::parse(common::grammars::STATEMENTS).unwrap()).unwrap(),
+    ] {
+        assert_eq!(
+            parser
+                .automaton()
+                .start_states()
+                .iter()
+                .map(|(symbol, state)| (symbol.as_str(), *state))
+                .collect::<Vec<_>>(),
+            [("Program", 0), ("Statement", 1), ("Expr", 2)],
+        );
+        for (symbol, state) in parser.automaton().start_states() {
+            let state = &parser.automaton().states()[*state];
+            assert!(
+                state
+                    .items()
+                    .iter()
+                    .filter(|item| item.dot() == 0 && item.lookahead().contains(&Token::Eof))
+                    .any(|item| item.rule().symbol() == symbol)
+            );
+        }
+        assert!(parser.follow_table()[&Symbol::from("Statement")].contains(&Token::Eof));
+        assert!(parser.follow_table()[&Symbol::from("Expr")].contains(&Token::Eof));
+    }
+}
+
+
+#[test]
+fn raising_correct_error_with_span_when_creating_parser_for_undefined_symbol() {
+    let grammar_string = "E -> E '+' T\nE -> 'x'\n";
+    let grammar = Grammar::parse(grammar_string).unwrap();
+    let error = Parser::lr(grammar).unwrap_err();
+    match error {
+        ParserError::UndefinedSymbol { symbol, span, .. } => {
+            assert_eq!(symbol.as_str(), "T");
+            assert_eq!(span, Some(Span { offset: 11, len: 1, line: 1, column: 12, source: None }));
+        },
+        _ => unreachable!(),
+    }
+}
+
+
+#[test]
+fn correctly_reporting_all_errors_when_checking_grammar() {
+    let grammar = Grammar::parse("%start X\nE -> E '+' T\nT -> F %m\n").unwrap();
+    let errors = Parser::check(&grammar).unwrap_err();
+
+    let error_strings = errors.iter().map(|error| error.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        error_strings,
+        [
+            "entry symbol X is not defined",
+            "symbol F in rule T -> F %m is not defined",
+            "regex token %m in rule T -> F %m is not defined",
+        ]
+    );
+}
+
+#[test]
+fn correctly_checking_grammars_without_errors() {
+    for grammar_string in common::grammars::CORRECT {
+        let grammar = Grammar::parse(grammar_string).unwrap();
+        assert!(Parser::check(&grammar).is_ok());
+    }
+}
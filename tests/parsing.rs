@@ -4,6 +4,9 @@ use dotlr::{
     Action,
     Grammar,
     Parser,
+    ParsingError,
+    Symbol,
+    Tree,
 };
 
 
@@ -83,7 +86,9 @@ fn correctly_trace_parsing_of_calculator_grammar() {
         // +------+-----------------+------------------------------+--------------------+--------------------------------------+
         // | 14   | 0 1 32 35 28 31 | Expr '+' Factor '*' Exponent |                  $ | Reduce Factor -> Factor '*' Exponent |
         // +------+-----------------+------------------------------+--------------------+--------------------------------------+
-        // | 15   | 0 1 32 35       | Expr '+' Factor              |                  $ | Accept Expr -> Expr '+' Factor       |
+        // | 15   | 0 1 32 35       | Expr '+' Factor              |                  $ | Reduce Expr -> Expr '+' Factor       |
+        // +------+-----------------+------------------------------+--------------------+--------------------------------------+
+        // | 16   | 0 1             | Expr                         |                  $ | Accept                               |
         // +------+-----------------+------------------------------+--------------------+--------------------------------------+
         assert_eq!(
             parse_trace.steps().iter().map(|step| *step.action_taken()).collect::<Vec<_>>(),
@@ -118,7 +123,8 @@ fn correctly_trace_parsing_of_calculator_grammar() {
                 Action::Reduce { rule_index: 8 },
                 Action::Reduce { rule_index: 7 },
                 Action::Reduce { rule_index: 4 },
-                Action::Accept { rule_index: 0 }
+                Action::Reduce { rule_index: 0 },
+                Action::Accept,
             ],
         );
     }
@@ -300,3 +306,474 @@ Json
         .trim(),
     );
 }
+
+#[test]
+fn correctly_parsing_optional_grammar() {
+    let grammar = Grammar::parse(common::grammars::OPTIONAL).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let tokens = parser.tokenize("let x;").unwrap();
+    let parse_tree = parser.parse(tokens).unwrap();
+    assert_eq!(
+        parse_tree.to_string().trim(),
+        r#"
+
+Declaration
+├─ let
+├─ x
+├─ Annotation
+├─ Initializer
+└─ ;
+
+            "#
+        .trim(),
+    );
+
+    let tokens = parser.tokenize("let x: int = 3;").unwrap();
+    let parse_tree = parser.parse(tokens).unwrap();
+    assert_eq!(
+        parse_tree.to_string().trim(),
+        r#"
+
+Declaration
+├─ let
+├─ x
+├─ Annotation
+│  ├─ :
+│  └─ int
+├─ Initializer
+│  ├─ =
+│  └─ 3
+└─ ;
+
+            "#
+        .trim(),
+    );
+}
+
+#[test]
+fn correctly_parsing_nested_start_symbol_with_empty_pattern() {
+    let grammar = Grammar::parse("S -> 'a' S\nS -> ε").unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let tokens = parser.tokenize("a a").unwrap();
+    let (parse_trace, parse_tree) = parser.trace(tokens).unwrap();
+
+    assert_eq!(
+        parse_trace.steps().iter().map(|step| *step.action_taken()).collect::<Vec<_>>(),
+        [
+            Action::Shift { next_state: 2 },
+            Action::Shift { next_state: 2 },
+            Action::Reduce { rule_index: 1 },
+            Action::Reduce { rule_index: 0 },
+            Action::Reduce { rule_index: 0 },
+            Action::Accept,
+        ],
+    );
+    assert_eq!(
+        parse_tree.to_string().trim(),
+        r#"
+
+S
+├─ a
+└─ S
+   ├─ a
+   └─ S
+
+            "#
+        .trim(),
+    );
+}
+
+
+#[test]
+fn correctly_flattening_parse_tree_of_json_ebnf_grammar() {
+    let grammar = Grammar::parse(common::grammars::JSON_EBNF).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let tokens = parser.tokenize("[1, 2, 3]").unwrap();
+    let parse_tree = parser.parse(tokens).unwrap().flatten(parser.grammar());
+    assert_eq!(
+        parse_tree.to_string().trim(),
+        r#"
+
+Json
+└─ Value
+   └─ Array
+      ├─ [
+      ├─ (Value (',' Value)*)?
+      │  ├─ Value
+      │  │  └─ Number
+      │  │     └─ 1
+      │  └─ (',' Value)*
+      │     ├─ ,
+      │     ├─ Value
+      │     │  └─ Number
+      │     │     └─ 2
+      │     ├─ ,
+      │     └─ Value
+      │        └─ Number
+      │           └─ 3
+      └─ ]
+
+            "#
+        .trim(),
+    );
+}
+
+
+#[test]
+fn correctly_parsing_calculator_precedence_grammar() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR_PRECEDENCE).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let tokens = parser.tokenize("1 - 2 * -3 ^ 4 ^ 5").unwrap();
+    let parse_tree = parser.parse(tokens).unwrap();
+    assert_eq!(
+        parse_tree.to_string().trim(),
+        r#"
+
+Expr
+├─ Expr
+│  └─ 1
+├─ -
+└─ Expr
+   ├─ Expr
+   │  └─ 2
+   ├─ *
+   └─ Expr
+      ├─ Expr
+      │  ├─ -
+      │  └─ Expr
+      │     └─ 3
+      ├─ ^
+      └─ Expr
+         ├─ Expr
+         │  └─ 4
+         ├─ ^
+         └─ Expr
+            └─ 5
+
+            "#
+        .trim(),
+    );
+}
+
+#[test]
+fn raising_correct_error_when_chaining_non_associative_tokens() {
+    let grammar = Grammar::parse("%nonassoc '<'\nE -> E '<' E | %n\n%n -> /[0-9]+/").unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let tokens = parser.tokenize("1 < 2").unwrap();
+    assert!(parser.parse(tokens).is_ok());
+
+    let tokens = parser.tokenize("1 < 2 < 3").unwrap();
+    let error = parser.parse(tokens).unwrap_err();
+    match error {
+        dotlr::ParsingError::UnexpectedToken { token, span, .. } => {
+            assert_eq!(token.as_str(), "<");
+            assert_eq!(span.offset, 6);
+        },
+        _ => unreachable!(),
+    }
+}
+
+
+#[test]
+fn correctly_parsing_statements_grammar_from_different_entry_symbols() {
+    let grammar = Grammar::parse(common::grammars::STATEMENTS).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let tokens = parser.tokenize("x = 1;").unwrap();
+    let parse_tree = parser.parse(tokens).unwrap();
+    assert_eq!(
+        parse_tree.to_string().trim(),
+        r#"
+
+Program
+└─ Statement+
+   └─ Statement
+      ├─ x
+      ├─ =
+      ├─ Expr
+      │  └─ Term
+      │     └─ 1
+      └─ ;
+
+            "#
+        .trim(),
+    );
+
+    let tokens = parser.tokenize("print a + b;").unwrap();
+    let parse_tree = parser.parse_from(&Symbol::from("Statement"), tokens).unwrap();
+    assert_eq!(
+        parse_tree.to_string().trim(),
+        r#"
+
+Statement
+├─ print
+├─ Expr
+│  ├─ Expr
+│  │  └─ Term
+│  │     └─ a
+│  ├─ +
+│  └─ Term
+│     └─ b
+└─ ;
+
+            "#
+        .trim(),
+    );
+
+    let tokens = parser.tokenize("(1)").unwrap();
+    let (trace, parse_tree) = parser.trace_from(&Symbol::from("Expr"), tokens).unwrap();
+    assert_eq!(
+        parse_tree.to_string().trim(),
+        r#"
+
+Expr
+└─ Term
+   ├─ (
+   ├─ Expr
+   │  └─ Term
+   │     └─ 1
+   └─ )
+
+            "#
+        .trim(),
+    );
+    assert_eq!(trace.steps()[0].state_stack(), [2]);
+
+    let tokens = parser.tokenize("print a;").unwrap();
+    let error = parser.parse_from(&Symbol::from("Expr"), tokens).unwrap_err();
+    assert!(matches!(error, ParsingError::UnexpectedToken { .. }));
+}
+
+#[test]
+fn raising_correct_error_when_parsing_from_unknown_entry_symbol() {
+    let grammar = Grammar::parse(common::grammars::STATEMENTS).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let tokens = parser.tokenize("x").unwrap();
+    let error = parser.parse_from(&Symbol::from("Term"), tokens).unwrap_err();
+    match error {
+        ParsingError::UnknownEntrySymbol { symbol } => {
+            assert_eq!(symbol.as_str(), "Term");
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn correctly_parsing_sums_grammar_with_ignored_comments() {
+    let grammar = Grammar::parse(common::grammars::SUMS).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let input = "1 + 2 # first\n\n  # second\n3\n";
+    let tokens = parser.tokenize(input).unwrap();
+    assert_eq!(
+        tokens.iter().map(|(token, _)| token.to_string()).collect::<Vec<_>>(),
+        [
+            "%number", "'+'", "%number", "%newline", "%newline", "%newline", "%number", "%newline",
+            "$"
+        ],
+    );
+
+    assert!(parser.parse(tokens).is_ok());
+}
+
+#[test]
+fn raising_correct_error_when_ignored_whitespace_is_significant() {
+    let grammar = Grammar::parse(common::grammars::SUMS).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let tokens = parser.tokenize("1 +\n2\n").unwrap();
+    let error = parser.parse(tokens).unwrap_err();
+    assert_eq!(error.to_string(), "unexpected token \n at 1:4 (expected %number)");
+}
+
+#[test]
+fn correctly_accessing_labeled_children_of_calculator_labeled_grammar() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR_LABELED).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let tokens = parser.tokenize("1 + 2 * 3").unwrap();
+    let tree = parser.parse(tokens).unwrap();
+
+    match tree.child("op") {
+        Some(Tree::Terminal { slice, .. }) => assert_eq!(*slice, "+"),
+        _ => unreachable!(),
+    }
+    match tree.child("rhs").and_then(|rhs| rhs.child("lhs")) {
+        Some(Tree::NonTerminal { symbol, .. }) => assert_eq!(symbol.as_str(), "Factor"),
+        _ => unreachable!(),
+    }
+    assert!(tree.child("value").is_none());
+    assert!(tree.child("op").unwrap().child("op").is_none());
+}
+
+#[test]
+fn correctly_accessing_labeled_children_of_flattened_repetitions() {
+    let grammar =
+        Grammar::parse("List -> '[' items:(item:%n (',' item:%n)*)? ']'\n%n -> /[0-9]+/").unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let tokens = parser.tokenize("[1, 2, 3]").unwrap();
+    let tree = parser.parse(tokens).unwrap().flatten(parser.grammar());
+
+    let items = tree.child("items").unwrap();
+    let slices = |tree: &Tree, label| {
+        tree.children(label)
+            .map(|child| {
+                match child {
+                    Tree::Terminal { slice, .. } => slice.to_string(),
+                    _ => unreachable!(),
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(slices(items, "item"), ["1"]);
+
+    let rest = match items {
+        Tree::NonTerminal { pattern, .. } => &pattern[1],
+        _ => unreachable!(),
+    };
+    assert_eq!(slices(rest, "item"), ["2", "3"]);
+}
+
+#[test]
+fn correctly_parsing_lists_grammar_with_instantiated_rules() {
+    let grammar = Grammar::parse(common::grammars::LISTS).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let tokens = parser.tokenize(r#"[1, [2, 3], {"a": 1; "b": []}]"#).unwrap();
+    let tree = parser.parse(tokens).unwrap();
+
+    let values = match &tree {
+        Tree::NonTerminal { pattern, .. } => &pattern[1],
+        _ => unreachable!(),
+    };
+    match values {
+        Tree::NonTerminal { symbol, pattern, .. } => {
+            assert_eq!(symbol.as_str(), "List(Value, ',')?");
+            assert!(matches!(
+                &pattern[0],
+                Tree::NonTerminal { symbol, .. } if symbol.as_str() == "List(Value, ',')"
+            ));
+        },
+        _ => unreachable!(),
+    }
+
+    assert!(parser.parse(parser.tokenize("[1, 2,]").unwrap()).is_err());
+}
+
+#[test]
+fn correctly_tokenizing_keywords_and_prioritized_regular_expressions() {
+    let grammar = Grammar::parse(common::grammars::KEYWORDS).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let tokens = parser.tokenize("if iffy then face = facade; beaded = 12;").unwrap();
+    assert_eq!(
+        tokens.iter().map(|(token, _)| token.to_string()).collect::<Vec<_>>(),
+        [
+            "'if'",
+            "%identifier",
+            "'then'",
+            "%identifier",
+            "'='",
+            "%color",
+            "';'",
+            "%color",
+            "'='",
+            "%number",
+            "';'",
+            "$"
+        ],
+    );
+
+    let tokens = parser.tokenize("while = 1;").unwrap();
+    assert_eq!(tokens[0].0.to_string(), "'while'");
+
+    let error = parser.parse(tokens).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "unexpected token while at 1:1 (expected one of $, 'if', %identifier)",
+    );
+}
+
+#[test]
+fn correctly_tokenizing_interpolation_grammar_with_lexer_modes() {
+    let grammar = Grammar::parse(common::grammars::INTERPOLATION).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let input = "\"a ${ b + \"c${d}\" } \" +\n  e";
+    let tokens = parser.tokenize(input).unwrap();
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|(token, slice)| {
+                let span = token.span();
+                format!("{} {:?} {}:{}", **token, slice, span.line, span.column)
+            })
+            .collect::<Vec<_>>(),
+        [
+            r#"'"' "\"" 1:1"#,
+            r#"%text "a " 1:2"#,
+            r#"'${' "${" 1:4"#,
+            r#"%identifier "b" 1:7"#,
+            r#"'+' "+" 1:9"#,
+            r#"'"' "\"" 1:11"#,
+            r#"%text "c" 1:12"#,
+            r#"'${' "${" 1:13"#,
+            r#"%identifier "d" 1:15"#,
+            r#"'}' "}" 1:16"#,
+            r#"'"' "\"" 1:17"#,
+            r#"'}' "}" 1:19"#,
+            r#"%text " " 1:20"#,
+            r#"'"' "\"" 1:21"#,
+            r#"'+' "+" 1:23"#,
+            r#"%identifier "e" 2:3"#,
+            r#"$ "\0" 2:4"#,
+        ],
+    );
+
+    assert!(parser.parse(tokens).is_ok());
+}
+
+#[test]
+fn correctly_parsing_query_grammar_with_case_insensitive_tokens() {
+    let grammar = Grammar::parse(common::grammars::QUERY).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let tokens = parser.tokenize("SeLeCt a, Fromage FROM T Where x = 1").unwrap();
+    assert_eq!(
+        tokens.iter().map(|(token, slice)| format!("{} {}", **token, slice)).collect::<Vec<_>>(),
+        [
+            "'select' SeLeCt",
+            "%identifier a",
+            "',' ,",
+            "%identifier Fromage",
+            "'from' FROM",
+            "%identifier T",
+            "'where' Where",
+            "%identifier x",
+            "'=' =",
+            "%number 1",
+            "$ \0",
+        ],
+    );
+
+    let tree = parser.parse(tokens).unwrap();
+    match tree {
+        Tree::NonTerminal { pattern, .. } => {
+            match &pattern[0] {
+                Tree::Terminal { token, slice, .. } => {
+                    assert_eq!(token.to_string(), "'select'");
+                    assert_eq!(*slice, "SeLeCt");
+                },
+                _ => unreachable!(),
+            }
+        },
+        _ => unreachable!(),
+    }
+}
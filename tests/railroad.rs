@@ -0,0 +1,102 @@
+mod common;
+
+use dotlr::{
+    Grammar,
+    Symbol,
+};
+
+
+#[test]
+fn correctly_rendering_railroad_diagrams_of_correct_grammars() {
+    for grammar_string in common::grammars::CORRECT {
+        let grammar = Grammar::parse(grammar_string).unwrap();
+
+        let svg = grammar.to_railroad_svg();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" "));
+        assert!(svg.ends_with("</svg>\n"));
+
+        let titles = svg
+            .split("<text class=\"title\" ")
+            .skip(1)
+            .map(|part| part.split_once('>').unwrap().1.split_once("</text>").unwrap().0)
+            .collect::<Vec<_>>();
+        let symbols = grammar
+            .symbols()
+            .iter()
+            .filter(|symbol| !grammar.auxiliary_symbols().contains_key(*symbol))
+            .map(|symbol| symbol.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, symbols);
+    }
+}
+
+#[test]
+fn correctly_rendering_railroad_diagrams_of_lists_as_loops() {
+    let grammar = Grammar::parse("L -> L ',' %x | %x\n%x -> /x/\n").unwrap();
+
+    let svg = grammar.to_railroad_svg();
+    assert_eq!(
+        svg.split("</style>\n").nth(1).unwrap(),
+        r#"
+<text class="title" x="20" y="35">L</text>
+<path d="M20 52v20m10 -20v20M20 62h20"/>
+<path d="M40 62h10"/>
+<path d="M88 62h10"/>
+<rect class="regex-token" x="50" y="50" width="38" height="24" rx="12"/>
+<text class="regex-token" x="69" y="62">%x</text>
+<path d="M88 62a10 10 0 0 1 10 10v12a10 10 0 0 1 -10 10h-5"/>
+<path d="M54 94h-4a10 10 0 0 1 -10 -10v-12a10 10 0 0 1 10 -10"/>
+<rect class="constant-token" x="54" y="82" width="29" height="24" rx="12"/>
+<text class="constant-token" x="68" y="94">,</text>
+<path d="M98 62h20m-10 -10v20m10 -20v20"/>
+</svg>
+        "#
+        .trim_start()
+        .trim_end_matches(' '),
+    );
+
+    let grammar = Grammar::parse("L -> %x ';' L | %x\n%x -> /x/\n").unwrap();
+    let svg = grammar.to_railroad_svg();
+    assert_eq!(svg.matches(">%x</text>").count(), 1);
+    assert_eq!(svg.matches(">;</text>").count(), 1);
+}
+
+#[test]
+fn correctly_rendering_railroad_diagrams_of_symbols_with_multiple_rules() {
+    let grammar = Grammar::parse(common::grammars::STATEMENTS).unwrap();
+
+    let svg = grammar.to_railroad_svg_of(&Symbol::from("Statement")).unwrap();
+    assert_eq!(svg.matches("<text class=\"title\"").count(), 1);
+    assert_eq!(svg.matches("<rect class=\"symbol\"").count(), 2);
+    assert_eq!(svg.matches("<rect class=\"regex-token\"").count(), 1);
+    assert_eq!(svg.matches("<rect class=\"constant-token\"").count(), 4);
+
+    let svg = grammar.to_railroad_svg_of(&Symbol::from("Expr")).unwrap();
+    assert_eq!(svg.matches(">Term</text>").count(), 1);
+    assert_eq!(svg.matches(">+</text>").count(), 1);
+}
+
+#[test]
+fn correctly_escaping_railroad_diagrams() {
+    let grammar = Grammar::parse("Tag -> '<' %name '&' '\"' '>'\n%name -> /[a-z]+/\n").unwrap();
+
+    let svg = grammar.to_railroad_svg();
+    assert!(svg.contains(">&lt;</text>"));
+    assert!(svg.contains(">&amp;</text>"));
+    assert!(svg.contains(">&quot;</text>"));
+    assert!(svg.contains(">&gt;</text>"));
+
+    let grammar = Grammar::parse("Quote -> 'a\\'b' '\\\\'\n").unwrap();
+
+    let svg = grammar.to_railroad_svg();
+    assert!(svg.contains(">a'b</text>"));
+    assert!(svg.contains(">\\</text>"));
+}
+
+#[test]
+fn correctly_not_rendering_railroad_diagrams_of_unknown_and_auxiliary_symbols() {
+    let grammar = Grammar::parse(common::grammars::STATEMENTS).unwrap();
+
+    assert_eq!(grammar.to_railroad_svg_of(&Symbol::from("Unknown")), None);
+    assert_eq!(grammar.to_railroad_svg_of(&Symbol::from("Statement+")), None);
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use dotlr::{
+    Grammar,
+    Parser,
+};
+
+
+#[test]
+fn correctly_round_tripping_grammars() {
+    for grammar_string in common::grammars::CORRECT {
+        let grammar = Grammar::parse(grammar_string).unwrap();
+
+        let serialized = ron::to_string(&grammar).unwrap();
+        let deserialized = ron::from_str::<Grammar>(&serialized).unwrap();
+
+        assert_eq!(deserialized.to_string(), grammar.to_string());
+        assert_eq!(ron::to_string(&deserialized).unwrap(), serialized);
+    }
+}
+
+#[test]
+fn correctly_round_tripping_parsers() {
+    for grammar_string in common::grammars::CORRECT {
+        let grammar = Grammar::parse(grammar_string).unwrap();
+        let parser = Parser::lr(grammar).unwrap();
+
+        let serialized = ron::to_string(&parser).unwrap();
+        let deserialized = ron::from_str::<Parser>(&serialized).unwrap();
+
+        assert_eq!(ron::to_string(&deserialized).unwrap(), serialized);
+    }
+}
+
+#[test]
+fn correctly_parsing_with_deserialized_parser() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let serialized = ron::to_string(&parser).unwrap();
+    let deserialized = ron::from_str::<Parser>(&serialized).unwrap();
+
+    let input = "1 + 2 * (3 - 4) ^ 5";
+    assert_eq!(
+        deserialized.parse(deserialized.tokenize(input).unwrap()).unwrap().to_string(),
+        parser.parse(parser.tokenize(input).unwrap()).unwrap().to_string(),
+    );
+}
+
+
+#[test]
+fn raising_correct_error_when_deserializing_grammar_with_invalid_regex() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+
+    let serialized = ron::to_string(&grammar).unwrap();
+    let corrupted = serialized.replace("[0-9]+", "[0-9+");
+
+    let error = ron::from_str::<Grammar>(&corrupted).unwrap_err();
+    assert!(error.to_string().contains("regex parse error"));
+}
+
+#[test]
+fn raising_correct_error_when_deserializing_parser_with_tables_of_another_grammar() {
+    let calculator = Parser::lr(Grammar::parse(common::grammars::CALCULATOR).unwrap()).unwrap();
+    let conditional = Parser::lr(Grammar::parse(common::grammars::CONDITIONAL).unwrap()).unwrap();
+
+    let serialized = ron::to_string(&calculator).unwrap();
+    let corrupted = serialized.replace(
+        &ron::to_string(calculator.grammar()).unwrap(),
+        &ron::to_string(conditional.grammar()).unwrap(),
+    );
+
+    let error = ron::from_str::<Parser>(&corrupted).unwrap_err();
+    assert!(error.to_string().contains("table does not match the grammar"));
+}
+
+#[test]
+fn raising_correct_error_when_deserializing_parser_with_missing_action() {
+    let parser = Parser::lr(Grammar::parse("S -> 'a' S\nS -> 'b'").unwrap()).unwrap();
+
+    let serialized = ron::to_string(&parser).unwrap();
+    let corrupted = serialized.replace("(type:Eof):[(type:Accept)]", "(type:Eof):[]");
+    assert_ne!(corrupted, serialized);
+
+    let error = ron::from_str::<Parser>(&corrupted).unwrap_err();
+    assert!(error.to_string().contains("parsing tables do not match the grammar"));
+}
+
+#[test]
+fn raising_correct_error_when_deserializing_parser_with_missing_goto() {
+    let parser = Parser::lr(Grammar::parse("S -> 'a' S\nS -> 'b'").unwrap()).unwrap();
+
+    let serialized = ron::to_string(&parser).unwrap();
+    let corrupted = serialized.replace("{(\"S\"):4}", "{}");
+    assert_ne!(corrupted, serialized);
+
+    let error = ron::from_str::<Parser>(&corrupted).unwrap_err();
+    assert!(error.to_string().contains("parsing tables do not match the grammar"));
+}
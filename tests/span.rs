@@ -74,17 +74,52 @@ fn correctly_calculate_spans_multi_line() {
     let tokens = parser.tokenize(str).unwrap();
 
     check_spans(tokens, &[
-        Span { line: 1, column: 3, offset: 2, len: 2 },
-        Span { line: 1, column: 6, offset: 5, len: 1 },
-        Span { line: 1, column: 9, offset: 8, len: 3 },
-        Span { line: 1, column: 12, offset: 11, len: 1 },
-        Span { line: 1, column: 13, offset: 12, len: 1 },
-        Span { line: 2, column: 1, offset: 14, len: 1 },
-        Span { line: 2, column: 2, offset: 15, len: 2 },
-        Span { line: 4, column: 5, offset: 23, len: 1 },
-        Span { line: 4, column: 6, offset: 24, len: 2 },
-        Span { line: 4, column: 9, offset: 27, len: 1 },
-        Span { line: 4, column: 10, offset: 28, len: 1 },
-        Span { line: 4, column: 11, offset: 29, len: 0 },
+        Span { line: 1, column: 3, offset: 2, len: 2, source: None },
+        Span { line: 1, column: 6, offset: 5, len: 1, source: None },
+        Span { line: 1, column: 9, offset: 8, len: 3, source: None },
+        Span { line: 1, column: 12, offset: 11, len: 1, source: None },
+        Span { line: 1, column: 13, offset: 12, len: 1, source: None },
+        Span { line: 2, column: 1, offset: 14, len: 1, source: None },
+        Span { line: 2, column: 2, offset: 15, len: 2, source: None },
+        Span { line: 4, column: 5, offset: 23, len: 1, source: None },
+        Span { line: 4, column: 6, offset: 24, len: 2, source: None },
+        Span { line: 4, column: 9, offset: 27, len: 1, source: None },
+        Span { line: 4, column: 10, offset: 28, len: 1, source: None },
+        Span { line: 4, column: 11, offset: 29, len: 0, source: None },
     ]);
 }
+
+#[test]
+fn correctly_calculate_spans_of_trivia() {
+    let grammar = Grammar::parse(common::grammars::SUMS).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+    let str = "1  + 2 # two\n  3";
+    let (tokens, trivia) = parser.tokenize_with_trivia(str).unwrap();
+
+    check_spans(
+        tokens,
+        &[
+            Span { line: 1, column: 1, offset: 0, len: 1, source: None },
+            Span { line: 1, column: 4, offset: 3, len: 1, source: None },
+            Span { line: 1, column: 6, offset: 5, len: 1, source: None },
+            Span { line: 1, column: 13, offset: 12, len: 1, source: None },
+            Span { line: 2, column: 3, offset: 15, len: 1, source: None },
+            Span { line: 2, column: 4, offset: 16, len: 0, source: None },
+        ],
+    );
+
+    assert_eq!(
+        trivia.iter().map(|slice| *slice.value()).collect::<Vec<_>>(),
+        ["  ", " ", " ", "# two", "  ",]
+    );
+    assert_eq!(
+        trivia.iter().map(|slice| slice.span().clone()).collect::<Vec<_>>(),
+        [
+            Span { line: 1, column: 2, offset: 1, len: 2, source: None },
+            Span { line: 1, column: 5, offset: 4, len: 1, source: None },
+            Span { line: 1, column: 7, offset: 6, len: 1, source: None },
+            Span { line: 1, column: 8, offset: 7, len: 5, source: None },
+            Span { line: 2, column: 1, offset: 13, len: 2, source: None },
+        ]
+    );
+}
@@ -0,0 +1,182 @@
+mod common;
+
+use dotlr::{
+    Grammar,
+    Parser,
+    Rewrite,
+};
+
+
+#[test]
+fn correctly_eliminating_direct_left_recursion() {
+    let grammar =
+        Grammar::parse("E -> E '+' T | T\nT -> T '*' F | F\nF -> '(' E ')' | %n\n%n -> /[0-9]+/\n")
+            .unwrap();
+
+    let (transformed, rewrites) = grammar.eliminate_left_recursion();
+    assert_eq!(
+        transformed.to_string().trim(),
+        r#"
+E -> T ETail
+ETail -> '+' T ETail
+ETail -> ε
+T -> F TTail
+TTail -> '*' F TTail
+TTail -> ε
+F -> '(' E ')'
+F -> %n
+
+%n -> /^[0-9]+/
+        "#
+        .trim(),
+    );
+    assert_eq!(
+        rewrites.iter().map(|rewrite| rewrite.to_string()).collect::<Vec<_>>(),
+        [
+            "eliminated the left recursion of E by introducing ETail",
+            "eliminated the left recursion of T by introducing TTail",
+        ],
+    );
+
+    let parser = Parser::lr(transformed).unwrap();
+    assert!(parser.parse(parser.tokenize("1 + 2 * (3 + 4)").unwrap()).is_ok());
+}
+
+#[test]
+fn correctly_eliminating_indirect_left_recursion() {
+    let grammar = Grammar::parse("S -> A 'a' | 'b'\nA -> A 'c' | S 'd' | 'e'\n").unwrap();
+
+    let (transformed, rewrites) = grammar.eliminate_left_recursion();
+    assert_eq!(
+        transformed.to_string().trim(),
+        r#"
+S -> A 'a'
+S -> 'b'
+A -> 'b' 'd' ATail
+A -> 'e' ATail
+ATail -> 'c' ATail
+ATail -> 'a' 'd' ATail
+ATail -> ε
+        "#
+        .trim(),
+    );
+    assert_eq!(
+        rewrites,
+        [
+            Rewrite::RulesSubstituted { rule: grammar.rules()[3].clone(), symbol: "S".into() },
+            Rewrite::LeftRecursionEliminated { symbol: "A".into(), tail: "ATail".into() },
+        ]
+    );
+}
+
+#[test]
+fn correctly_eliminating_left_recursion_of_ebnf_constructs() {
+    let grammar = Grammar::parse(common::grammars::JSON_EBNF).unwrap();
+
+    let (transformed, _) = grammar.eliminate_left_recursion();
+    let transformed_string = transformed.to_string();
+    assert!(transformed_string.contains("ZeroOrMoreTail -> ',' Value ZeroOrMoreTail"));
+
+    // Renamed auxiliary symbols can be parsed back.
+    let reparsed = Grammar::parse(&transformed_string).unwrap();
+    assert_eq!(reparsed.to_string(), transformed_string);
+
+    let parser = Parser::lr(reparsed).unwrap();
+    assert!(parser.parse(parser.tokenize(r#"{"a": [1, 2, {"b": null}]}"#).unwrap()).is_ok());
+}
+
+#[test]
+fn correctly_keeping_grammars_without_left_recursion() {
+    let grammar = Grammar::parse(common::grammars::NOT_LALR).unwrap();
+    let (transformed, rewrites) = grammar.eliminate_left_recursion();
+
+    assert_eq!(transformed.to_string(), grammar.to_string());
+    assert_eq!(rewrites, []);
+}
+
+#[test]
+fn correctly_removing_trivial_cycles_when_eliminating_left_recursion() {
+    let grammar = Grammar::parse("E -> E | 'a'\n").unwrap();
+    let (transformed, rewrites) = grammar.eliminate_left_recursion();
+
+    assert_eq!(transformed.to_string(), "E -> 'a'\n");
+    assert_eq!(rewrites.len(), 1);
+    assert_eq!(rewrites[0].to_string(), "removed E -> E");
+}
+
+#[test]
+fn correctly_left_factoring_grammars() {
+    let grammar = Grammar::parse(
+        "S -> 'if' E 'then' S | 'if' E 'then' S 'else' S | 'x'\nE -> 'b' 'c' | 'b' 'd' | 'b'\n",
+    )
+    .unwrap();
+
+    let (transformed, rewrites) = grammar.left_factor();
+    assert_eq!(
+        transformed.to_string().trim(),
+        r#"
+S -> 'if' E 'then' S SSuffix
+S -> 'x'
+SSuffix -> ε
+SSuffix -> 'else' S
+E -> 'b' ESuffix
+ESuffix -> 'c'
+ESuffix -> 'd'
+ESuffix -> ε
+        "#
+        .trim(),
+    );
+    assert_eq!(
+        rewrites.iter().map(|rewrite| rewrite.to_string()).collect::<Vec<_>>(),
+        [
+            "factored 'if' E 'then' S out of the rules of S by introducing SSuffix",
+            "factored 'b' out of the rules of E by introducing ESuffix",
+        ],
+    );
+}
+
+#[test]
+fn correctly_left_factoring_introduced_symbols() {
+    let grammar = Grammar::parse("S -> 'a' 'b' 'c' | 'a' 'b' 'd' | 'a' 'e'\n").unwrap();
+
+    let (transformed, _) = grammar.left_factor();
+    assert_eq!(
+        transformed.to_string().trim(),
+        r#"
+S -> 'a' SSuffix
+SSuffix -> 'b' SSuffixSuffix
+SSuffix -> 'e'
+SSuffixSuffix -> 'c'
+SSuffixSuffix -> 'd'
+        "#
+        .trim(),
+    );
+}
+
+#[test]
+fn correctly_removing_useless_symbols() {
+    let grammar = Grammar::parse("S -> A 'x' | 'y'\nA -> A 'z'\nB -> 'b'\n").unwrap();
+
+    let (transformed, rewrites) = grammar.remove_useless_symbols();
+    assert_eq!(transformed.to_string(), "S -> 'y'\n");
+    assert_eq!(
+        rewrites.iter().map(|rewrite| rewrite.to_string()).collect::<Vec<_>>(),
+        [
+            "removed A as it cannot derive any sequence of tokens",
+            "removed S -> A 'x'",
+            "removed B as it is not reachable from the entry symbols",
+        ],
+    );
+    assert_eq!(transformed.lint(), []);
+}
+
+#[test]
+fn correctly_keeping_grammars_without_useless_symbols() {
+    for grammar_string in common::grammars::CORRECT {
+        let grammar = Grammar::parse(grammar_string).unwrap();
+        let (transformed, rewrites) = grammar.remove_useless_symbols();
+
+        assert_eq!(transformed.to_string(), grammar.to_string());
+        assert_eq!(rewrites, []);
+    }
+}